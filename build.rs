@@ -1,6 +1,9 @@
 fn main() {
-    winresource::WindowsResource::new()
-        .set_icon("assets/my_icon.ico")
-        .compile()
-        .expect("Failed to compile resources");
+    // Only embed the icon resource when targeting Windows
+    if std::env::var("CARGO_CFG_TARGET_OS").as_deref() == Ok("windows") {
+        winresource::WindowsResource::new()
+            .set_icon("assets/my_icon.ico")
+            .compile()
+            .expect("Failed to compile resources");
+    }
 }
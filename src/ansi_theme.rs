@@ -1,10 +1,23 @@
 // ansi_theme.rs
 use std::io::{self, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
 use crate::config::{ThemeConfig, TerminalColor};
 
+// Cleared in machine-readable output modes so themed text never reaches stdout
+static OUTPUT_ENABLED: AtomicBool = AtomicBool::new(true);
+
 pub struct AnsiTheme;
 
 impl AnsiTheme {
+    // Globally enable or silence all themed console output
+    pub fn set_output_enabled(enabled: bool) {
+        OUTPUT_ENABLED.store(enabled, Ordering::Relaxed);
+    }
+
+    pub fn output_enabled() -> bool {
+        OUTPUT_ENABLED.load(Ordering::Relaxed)
+    }
+
     // Enable ANSI support on Windows
     pub fn enable_ansi_support() {
         #[cfg(windows)]
@@ -28,6 +41,9 @@ impl AnsiTheme {
 
     // Apply the full theme (background + foreground) persistently
     pub fn apply_theme(theme: &ThemeConfig) {
+        if !Self::output_enabled() {
+            return;
+        }
         // Apply background and foreground colors without reset
         print!("{}{}",
             theme.background_color.to_ansi_bg_code(),
@@ -38,6 +54,9 @@ impl AnsiTheme {
 
     // Reset to terminal defaults
     pub fn reset() {
+        if !Self::output_enabled() {
+            return;
+        }
         print!("\x1b[0m");
         io::stdout().flush().unwrap();
     }
@@ -53,6 +72,9 @@ impl AnsiTheme {
 
     // Print styled text directly (maintains theme background)
     pub fn print_styled(text: &str, ansi_code: &str, theme: &ThemeConfig) {
+        if !Self::output_enabled() {
+            return;
+        }
         print!("{}{}{}",
             theme.background_color.to_ansi_bg_code(),
             ansi_code,
@@ -88,6 +110,9 @@ impl AnsiTheme {
     }
 
     pub fn print_logo(text: &str, theme: &ThemeConfig) {
+        if !Self::output_enabled() {
+            return;
+        }
         let logo_code = match &theme.logo_color {
             TerminalColor::Red => "\x1b[31m",
             TerminalColor::Green => "\x1b[32m",
@@ -122,6 +147,9 @@ impl AnsiTheme {
     }
 
     pub fn print_rgb(text: &str, r: u8, g: u8, b: u8, theme: &ThemeConfig) {
+        if !Self::output_enabled() {
+            return;
+        }
         print!("{}\x1b[38;2;{};{};{}m{}",
             theme.background_color.to_ansi_bg_code(),
             r, g, b,
@@ -252,6 +280,9 @@ impl AnsiTheme {
 
     // Method to print regular text that respects the theme (uses theme foreground)
     pub fn print_themed(text: &str, theme: &ThemeConfig) {
+        if !Self::output_enabled() {
+            return;
+        }
         print!("{}{}",
             theme.background_color.to_ansi_bg_code(),
             theme.foreground_color.to_ansi_fg_code()
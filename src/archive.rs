@@ -0,0 +1,210 @@
+// archive.rs
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ArchiveFormat {
+    #[serde(rename = "zip")]
+    Zip,
+    #[serde(rename = "tar.gz")]
+    TarGz,
+}
+
+impl ArchiveFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ArchiveFormat::Zip => "zip",
+            ArchiveFormat::TarGz => "tar.gz",
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<Self, String> {
+        match text.trim().to_lowercase().as_str() {
+            "zip" => Ok(ArchiveFormat::Zip),
+            "tar.gz" | "tgz" | "targz" => Ok(ArchiveFormat::TarGz),
+            other => Err(format!("Unknown archive format '{}' (expected zip or tar.gz)", other)),
+        }
+    }
+
+    /// What users of the current platform expect: zip on Windows, tar.gz elsewhere
+    pub fn platform_default() -> Self {
+        if cfg!(windows) { ArchiveFormat::Zip } else { ArchiveFormat::TarGz }
+    }
+}
+
+enum Writer {
+    Zip(zip::ZipWriter<File>),
+    TarGz(tar::Builder<GzEncoder<BufWriter<File>>>),
+}
+
+/// Writes files into a new `.zip` or `.tar.gz`, keeping Unix permissions so binaries stay executable
+pub struct ArchiveWriter {
+    writer: Writer,
+    entries: Vec<String>,
+    zip_compression: zip::CompressionMethod,
+}
+
+impl ArchiveWriter {
+    pub fn create(path: &Path, format: ArchiveFormat) -> Result<Self, String> {
+        let file = File::create(path)
+            .map_err(|e| format!("Failed to create {}: {}", path.display(), e))?;
+        let writer = match format {
+            ArchiveFormat::Zip => Writer::Zip(zip::ZipWriter::new(file)),
+            ArchiveFormat::TarGz => {
+                let encoder = GzEncoder::new(BufWriter::new(file), Compression::default());
+                Writer::TarGz(tar::Builder::new(encoder))
+            }
+        };
+        Ok(ArchiveWriter { writer, entries: Vec::new(), zip_compression: zip::CompressionMethod::Deflated })
+    }
+
+    /// Stores zip entries without compression when `compress` is false; tar.gz is always compressed
+    pub fn with_compression(mut self, compress: bool) -> Self {
+        self.zip_compression = if compress { zip::CompressionMethod::Deflated } else { zip::CompressionMethod::Stored };
+        self
+    }
+
+    /// Adds `source` as `name`, a relative path with forward slashes
+    pub fn add_file(&mut self, source: &Path, name: &str) -> Result<(), String> {
+        match &mut self.writer {
+            Writer::Zip(zip) => {
+                let options = zip::write::FileOptions::default()
+                    .compression_method(self.zip_compression)
+                    .unix_permissions(file_mode(source));
+                let data = fs::read(source)
+                    .map_err(|e| format!("Failed to read file {}: {}", source.display(), e))?;
+                zip.start_file(name, options)
+                    .map_err(|e| format!("Failed to start zip entry {}: {}", name, e))?;
+                zip.write_all(&data)
+                    .map_err(|e| format!("Failed to write {} to zip: {}", name, e))?;
+            }
+            Writer::TarGz(tar) => {
+                tar.append_path_with_name(source, name)
+                    .map_err(|e| format!("Failed to add {} to archive: {}", source.display(), e))?;
+            }
+        }
+        self.entries.push(name.to_string());
+        Ok(())
+    }
+
+    /// Recursively adds the files below `directory` under `prefix`, leaving out paths
+    /// for which `skip` returns true (a skipped directory is not descended into)
+    pub fn add_directory(&mut self, directory: &Path, prefix: &str, skip: &dyn Fn(&Path) -> bool) -> Result<(), String> {
+        let mut entries: Vec<PathBuf> = fs::read_dir(directory)
+            .map_err(|e| format!("Failed to read directory {}: {}", directory.display(), e))?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .collect();
+        // Stable archive order regardless of the filesystem's listing order
+        entries.sort();
+
+        for path in entries {
+            if skip(&path) {
+                continue;
+            }
+            let file_name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+            let name = if prefix.is_empty() { file_name } else { format!("{}/{}", prefix, file_name) };
+            if path.is_dir() {
+                self.add_directory(&path, &name, skip)?;
+            } else {
+                self.add_file(&path, &name)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Archive paths added so far, in order
+    pub fn entries(&self) -> &[String] {
+        &self.entries
+    }
+
+    pub fn finish(self) -> Result<(), String> {
+        let result = match self.writer {
+            Writer::Zip(mut zip) => zip.finish().map(|_| ()).map_err(|e| e.to_string()),
+            Writer::TarGz(tar) => tar.into_inner()
+                .and_then(|encoder| encoder.finish())
+                .and_then(|mut file| file.flush())
+                .map_err(|e| e.to_string()),
+        };
+        result.map_err(|e| format!("Failed to finalize archive: {}", e))
+    }
+}
+
+#[cfg(unix)]
+fn file_mode(path: &Path) -> u32 {
+    use std::os::unix::fs::PermissionsExt;
+    fs::metadata(path).map(|m| m.permissions().mode() & 0o777).unwrap_or(0o644)
+}
+
+#[cfg(not(unix))]
+fn file_mode(_path: &Path) -> u32 {
+    0o644
+}
+
+/// Lowercase hex SHA-256 of the file's contents
+pub fn sha256_file(path: &Path) -> Result<String, String> {
+    let mut file = File::open(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    let mut hasher = Sha256::new();
+    io::copy(&mut file, &mut hasher).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    Ok(hasher.finalize().iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+/// Writes `<file>.sha256` in the format `sha256sum -c` checks, returning its path and the hash
+pub fn write_checksum(path: &Path) -> Result<(PathBuf, String), String> {
+    let hash = sha256_file(path)?;
+    let file_name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    let checksum_path = path.with_file_name(format!("{}.sha256", file_name));
+    fs::write(&checksum_path, format!("{}  {}\n", hash, file_name))
+        .map_err(|e| format!("Failed to write {}: {}", checksum_path.display(), e))?;
+    Ok((checksum_path, hash))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+    use std::io::Read;
+
+    #[test]
+    fn writes_zip_and_tar_gz_with_checksums() {
+        let dir = TempDir::new("archive");
+        fs::create_dir_all(dir.join("docs/skip")).unwrap();
+        fs::write(dir.join("abc.txt"), "abc").unwrap();
+        fs::write(dir.join("docs/guide.md"), "guide").unwrap();
+        fs::write(dir.join("docs/skip/hidden.md"), "hidden").unwrap();
+
+        for format in [ArchiveFormat::Zip, ArchiveFormat::TarGz] {
+            let path = dir.join(format!("out.{}", format.extension()));
+            let mut archive = ArchiveWriter::create(&path, format).unwrap();
+            archive.add_file(&dir.join("abc.txt"), "pkg/abc.txt").unwrap();
+            archive.add_directory(&dir.join("docs"), "pkg/docs", &|p| p.ends_with("skip")).unwrap();
+            assert_eq!(archive.entries(), ["pkg/abc.txt", "pkg/docs/guide.md"]);
+            archive.finish().unwrap();
+
+            let names: Vec<String> = match format {
+                ArchiveFormat::Zip => {
+                    let zip = zip::ZipArchive::new(File::open(&path).unwrap()).unwrap();
+                    zip.file_names().map(|n| n.to_string()).collect()
+                }
+                ArchiveFormat::TarGz => {
+                    let mut tar = tar::Archive::new(flate2::read::GzDecoder::new(File::open(&path).unwrap()));
+                    tar.entries().unwrap()
+                        .map(|e| e.unwrap().path().unwrap().display().to_string())
+                        .collect()
+                }
+            };
+            assert!(names.contains(&"pkg/docs/guide.md".to_string()));
+            assert_eq!(names.len(), 2);
+        }
+
+        let (checksum_path, hash) = write_checksum(&dir.join("abc.txt")).unwrap();
+        assert_eq!(hash, "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+        let mut content = String::new();
+        File::open(&checksum_path).unwrap().read_to_string(&mut content).unwrap();
+        assert_eq!(content, format!("{}  abc.txt\n", hash));
+    }
+}
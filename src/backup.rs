@@ -0,0 +1,469 @@
+// backup.rs
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use ignore::WalkBuilder;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use crate::archive::{self, ArchiveFormat, ArchiveWriter};
+use crate::config::BackupConfig;
+
+// File contents shared by incremental backups, stored by SHA-256 below the backups directory
+const OBJECTS_DIR: &str = "objects";
+const MANIFEST_SUFFIX: &str = ".manifest.json";
+// Never backed up, whatever .gitignore says
+const SKIPPED_DIRS: [&str; 2] = ["target", ".git"];
+const SKIPPED_EXTENSIONS: [&str; 2] = ["zip", "backup"];
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BackupKind {
+    /// `<exe>-backup-v<version>-<unix time>.zip`
+    Full,
+    /// `<exe>-backup-v<version>-<unix time>.manifest.json` pointing into the object store
+    Incremental,
+}
+
+/// A backup written before a self-update
+#[derive(Debug, Clone, Serialize)]
+pub struct BackupInfo {
+    pub path: PathBuf,
+    pub kind: BackupKind,
+    /// The version the update was going to install
+    pub version: String,
+    /// Seconds since the Unix epoch
+    pub created: u64,
+    /// Size of the zip, or the total size of the files an incremental backup lists
+    pub size_bytes: u64,
+}
+
+impl BackupInfo {
+    fn from_path(path: &Path) -> Option<Self> {
+        let file_name = path.file_name()?.to_str()?;
+        let (kind, version, created) = parse_backup_name(file_name)?;
+        let size_bytes = match kind {
+            BackupKind::Full => fs::metadata(path).ok()?.len(),
+            BackupKind::Incremental => read_manifest(path).ok()?.files.iter().map(|f| f.size).sum(),
+        };
+        Some(BackupInfo { path: path.to_path_buf(), kind, version, created, size_bytes })
+    }
+
+    /// Local creation time, e.g. "2024-05-01 14:30"
+    pub fn created_display(&self) -> String {
+        chrono::DateTime::from_timestamp(self.created as i64, 0)
+            .map(|time| time.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_else(|| self.created.to_string())
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct BackupSummary {
+    pub path: PathBuf,
+    pub files: usize,
+    pub total_bytes: u64,
+    /// Bytes written to disk; for incremental backups only contents not stored before
+    pub stored_bytes: u64,
+    /// Backups deleted by the retention policy
+    pub removed: Vec<PathBuf>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Manifest {
+    version: String,
+    created: u64,
+    /// Whether objects first written by this backup are gzip-compressed
+    compressed: bool,
+    files: Vec<ManifestEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ManifestEntry {
+    path: String,
+    sha256: String,
+    size: u64,
+    #[serde(default)]
+    mode: u32,
+}
+
+/// Where `create_backup` writes a backup with these settings
+pub fn backup_path(backups_dir: &Path, name: &str, version: &str, now: u64, config: &BackupConfig) -> PathBuf {
+    let stem = format!("{}-backup-v{}-{}", name, version, now);
+    if config.incremental {
+        backups_dir.join(format!("{}{}", stem, MANIFEST_SUFFIX))
+    } else {
+        backups_dir.join(format!("{}.zip", stem))
+    }
+}
+
+/// Backs up `project_root` into `backups_dir` as `<name>-backup-v<version>-<now>`, then deletes
+/// backups the retention policy no longer keeps
+pub fn create_backup(project_root: &Path, backups_dir: &Path, name: &str, version: &str, now: u64, config: &BackupConfig) -> Result<BackupSummary, String> {
+    fs::create_dir_all(backups_dir)
+        .map_err(|e| format!("Failed to create backups directory {}: {}", backups_dir.display(), e))?;
+    let files = backup_files(project_root, backups_dir, config.respect_gitignore())?;
+    let total_bytes = files.iter().map(|(path, _)| fs::metadata(path).map(|m| m.len()).unwrap_or(0)).sum();
+    let path = backup_path(backups_dir, name, version, now, config);
+
+    let stored_bytes = if config.incremental {
+        write_incremental(&files, backups_dir, &path, version, now, config.compress())?
+    } else {
+        let mut zip = ArchiveWriter::create(&path, ArchiveFormat::Zip)?.with_compression(config.compress());
+        for (source, relative) in &files {
+            zip.add_file(source, relative)?;
+        }
+        zip.finish()?;
+        fs::metadata(&path).map(|m| m.len()).unwrap_or(0)
+    };
+
+    let removed = apply_retention(backups_dir, config, now)?;
+    Ok(BackupSummary { path, files: files.len(), total_bytes, stored_bytes, removed })
+}
+
+/// Backups in `backups_dir`, newest first
+pub fn list_backups(backups_dir: &Path) -> Result<Vec<BackupInfo>, String> {
+    if !backups_dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut backups: Vec<BackupInfo> = fs::read_dir(backups_dir)
+        .map_err(|e| format!("Failed to read backups directory {}: {}", backups_dir.display(), e))?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| BackupInfo::from_path(&entry.path()))
+        .collect();
+    backups.sort_by_key(|backup| std::cmp::Reverse(backup.created));
+    Ok(backups)
+}
+
+/// Writes the backup's files under `destination`, overwriting existing files, and returns how many
+pub fn restore_backup(backup: &BackupInfo, destination: &Path) -> Result<usize, String> {
+    match backup.kind {
+        BackupKind::Full => extract_zip(&backup.path, destination),
+        BackupKind::Incremental => {
+            let backups_dir = backup.path.parent().ok_or("Backup has no parent directory")?;
+            let manifest = read_manifest(&backup.path)?;
+            for entry in &manifest.files {
+                let relative = safe_relative_path(&entry.path)
+                    .ok_or_else(|| format!("Refusing to restore unsafe path: {}", entry.path))?;
+                let output_path = destination.join(relative);
+                if let Some(parent) = output_path.parent() {
+                    fs::create_dir_all(parent)
+                        .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+                }
+                copy_object(backups_dir, &entry.sha256, &output_path)?;
+                set_mode(&output_path, entry.mode);
+            }
+            Ok(manifest.files.len())
+        }
+    }
+}
+
+/// Deletes backups beyond `keep_count` or older than `keep_days` (always keeping the newest),
+/// then object-store contents no remaining manifest refers to
+pub fn apply_retention(backups_dir: &Path, config: &BackupConfig, now: u64) -> Result<Vec<PathBuf>, String> {
+    let mut removed = Vec::new();
+    for (index, backup) in list_backups(backups_dir)?.into_iter().enumerate().skip(1) {
+        let too_many = config.keep_count().is_some_and(|count| index >= count);
+        let too_old = config.keep_days.is_some_and(|days| now.saturating_sub(backup.created) > days * SECONDS_PER_DAY);
+        if too_many || too_old {
+            fs::remove_file(&backup.path)
+                .map_err(|e| format!("Failed to delete old backup {}: {}", backup.path.display(), e))?;
+            removed.push(backup.path);
+        }
+    }
+    collect_garbage(backups_dir)?;
+    Ok(removed)
+}
+
+// "app-backup-v1.2.3-1714550000.zip" -> (Full, "1.2.3", 1714550000)
+fn parse_backup_name(file_name: &str) -> Option<(BackupKind, String, u64)> {
+    let (kind, stem) = match file_name.strip_suffix(MANIFEST_SUFFIX) {
+        Some(stem) => (BackupKind::Incremental, stem),
+        None => (BackupKind::Full, file_name.strip_suffix(".zip")?),
+    };
+    let (rest, timestamp) = stem.rsplit_once('-')?;
+    let (_, version) = rest.rsplit_once("-backup-v")?;
+    Some((kind, version.to_string(), timestamp.parse().ok()?))
+}
+
+// Files below `root` as (absolute path, relative path with forward slashes), sorted
+fn backup_files(root: &Path, backups_dir: &Path, respect_gitignore: bool) -> Result<Vec<(PathBuf, String)>, String> {
+    let backups_dir = backups_dir.to_path_buf();
+    let walker = WalkBuilder::new(root)
+        .hidden(false)
+        .ignore(false)
+        .git_global(false)
+        .git_ignore(respect_gitignore)
+        .git_exclude(respect_gitignore)
+        .require_git(false)  // .gitignore applies even before `git init`
+        .filter_entry(move |entry| {
+            let name = entry.file_name().to_string_lossy();
+            let is_dir = entry.file_type().map(|t| t.is_dir()).unwrap_or(false);
+            let skipped_dir = is_dir && SKIPPED_DIRS.contains(&name.as_ref());
+            !skipped_dir && !entry.path().starts_with(&backups_dir)
+        })
+        .build();
+
+    let mut files = Vec::new();
+    for entry in walker {
+        let entry = entry.map_err(|e| format!("Failed to scan {}: {}", root.display(), e))?;
+        if !entry.file_type().map(|t| t.is_file()).unwrap_or(false) {
+            continue;
+        }
+        let path = entry.into_path();
+        let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+        if SKIPPED_EXTENSIONS.contains(&extension) {
+            continue;
+        }
+        let relative = path.strip_prefix(root)
+            .map_err(|e| format!("Failed to calculate relative path: {}", e))?
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy().to_string())
+            .collect::<Vec<_>>()
+            .join("/");
+        files.push((path, relative));
+    }
+    files.sort_by(|a, b| a.1.cmp(&b.1));
+    Ok(files)
+}
+
+// Stores contents not yet in the object store and writes the manifest; returns the bytes written
+fn write_incremental(files: &[(PathBuf, String)], backups_dir: &Path, manifest_path: &Path, version: &str, now: u64, compress: bool) -> Result<u64, String> {
+    let mut stored_bytes = 0;
+    let mut entries = Vec::new();
+    for (source, relative) in files {
+        let sha256 = archive::sha256_file(source)?;
+        let size = fs::metadata(source).map(|m| m.len()).unwrap_or(0);
+        if find_object(backups_dir, &sha256).is_none() {
+            stored_bytes += write_object(backups_dir, &sha256, source, compress)?;
+        }
+        entries.push(ManifestEntry { path: relative.clone(), sha256, size, mode: file_mode(source) });
+    }
+
+    let manifest = Manifest { version: version.to_string(), created: now, compressed: compress, files: entries };
+    let json = serde_json::to_string_pretty(&manifest)
+        .map_err(|e| format!("Failed to serialize backup manifest: {}", e))?;
+    fs::write(manifest_path, &json)
+        .map_err(|e| format!("Failed to write {}: {}", manifest_path.display(), e))?;
+    Ok(stored_bytes + json.len() as u64)
+}
+
+fn read_manifest(path: &Path) -> Result<Manifest, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Invalid backup manifest {}: {}", path.display(), e))
+}
+
+// objects/ab/abcdef... (uncompressed) or objects/ab/abcdef....gz
+fn object_path(backups_dir: &Path, sha256: &str, compressed: bool) -> PathBuf {
+    let name = if compressed { format!("{}.gz", sha256) } else { sha256.to_string() };
+    backups_dir.join(OBJECTS_DIR).join(&sha256[..2.min(sha256.len())]).join(name)
+}
+
+fn find_object(backups_dir: &Path, sha256: &str) -> Option<PathBuf> {
+    [true, false].into_iter()
+        .map(|compressed| object_path(backups_dir, sha256, compressed))
+        .find(|path| path.exists())
+}
+
+fn write_object(backups_dir: &Path, sha256: &str, source: &Path, compress: bool) -> Result<u64, String> {
+    let path = object_path(backups_dir, sha256, compress);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    // Written under a temporary name so an interrupted backup never leaves a truncated object
+    let partial = path.with_extension("partial");
+    let mut input = File::open(source).map_err(|e| format!("Failed to read file {}: {}", source.display(), e))?;
+    let output = File::create(&partial).map_err(|e| format!("Failed to create {}: {}", partial.display(), e))?;
+    let result = if compress {
+        let mut encoder = GzEncoder::new(output, Compression::default());
+        io::copy(&mut input, &mut encoder).and_then(|_| encoder.finish()).and_then(|mut file| file.flush())
+    } else {
+        let mut output = output;
+        io::copy(&mut input, &mut output).and_then(|_| output.flush())
+    };
+    result.map_err(|e| format!("Failed to store {}: {}", source.display(), e))?;
+    fs::rename(&partial, &path).map_err(|e| format!("Failed to store {}: {}", source.display(), e))?;
+    Ok(fs::metadata(&path).map(|m| m.len()).unwrap_or(0))
+}
+
+fn copy_object(backups_dir: &Path, sha256: &str, destination: &Path) -> Result<(), String> {
+    let path = find_object(backups_dir, sha256)
+        .ok_or_else(|| format!("Backup content {} is missing from {}", sha256, backups_dir.join(OBJECTS_DIR).display()))?;
+    let input = File::open(&path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    let mut output = File::create(destination)
+        .map_err(|e| format!("Failed to create {}: {}", destination.display(), e))?;
+    let result = if path.extension().is_some_and(|e| e == "gz") {
+        io::copy(&mut GzDecoder::new(input), &mut output)
+    } else {
+        io::copy(&mut { input }, &mut output)
+    };
+    result.map(|_| ()).map_err(|e| format!("Failed to restore {}: {}", destination.display(), e))
+}
+
+// Deletes objects that no manifest refers to any more
+fn collect_garbage(backups_dir: &Path) -> Result<(), String> {
+    let objects_dir = backups_dir.join(OBJECTS_DIR);
+    if !objects_dir.exists() {
+        return Ok(());
+    }
+    let mut referenced = HashSet::new();
+    for backup in list_backups(backups_dir)?.iter().filter(|b| b.kind == BackupKind::Incremental) {
+        referenced.extend(read_manifest(&backup.path)?.files.into_iter().map(|f| f.sha256));
+    }
+
+    let shards = fs::read_dir(&objects_dir)
+        .map_err(|e| format!("Failed to read {}: {}", objects_dir.display(), e))?;
+    for shard in shards.flatten() {
+        for object in fs::read_dir(shard.path()).into_iter().flatten().flatten() {
+            let name = object.file_name().to_string_lossy().to_string();
+            let hash = name.strip_suffix(".gz").unwrap_or(&name);
+            if !referenced.contains(hash) {
+                fs::remove_file(object.path())
+                    .map_err(|e| format!("Failed to delete {}: {}", object.path().display(), e))?;
+            }
+        }
+        let _ = fs::remove_dir(shard.path());  // only succeeds once the shard is empty
+    }
+    Ok(())
+}
+
+/// Writes every file in the zip under `destination`, overwriting existing files. Entries that
+/// would land outside `destination` are rejected.
+fn extract_zip(archive_path: &Path, destination: &Path) -> Result<usize, String> {
+    let file = File::open(archive_path)
+        .map_err(|e| format!("Failed to open {}: {}", archive_path.display(), e))?;
+    let mut archive = zip::ZipArchive::new(file)
+        .map_err(|e| format!("Failed to read {}: {}", archive_path.display(), e))?;
+
+    let mut count = 0;
+    for index in 0..archive.len() {
+        let mut entry = archive.by_index(index)
+            .map_err(|e| format!("Failed to read entry {} of {}: {}", index, archive_path.display(), e))?;
+        let relative = entry.enclosed_name()
+            .map(Path::to_path_buf)
+            .ok_or_else(|| format!("Refusing to extract unsafe path: {}", entry.name()))?;
+        let output_path = destination.join(relative);
+
+        if entry.is_dir() {
+            fs::create_dir_all(&output_path)
+                .map_err(|e| format!("Failed to create {}: {}", output_path.display(), e))?;
+            continue;
+        }
+        if let Some(parent) = output_path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        let mut output = File::create(&output_path)
+            .map_err(|e| format!("Failed to create {}: {}", output_path.display(), e))?;
+        io::copy(&mut entry, &mut output)
+            .map_err(|e| format!("Failed to extract {}: {}", output_path.display(), e))?;
+        set_mode(&output_path, entry.unix_mode().unwrap_or(0));
+        count += 1;
+    }
+    Ok(count)
+}
+
+// Manifest paths are written by us, but a hand-edited one must not escape the destination
+fn safe_relative_path(path: &str) -> Option<PathBuf> {
+    let relative = PathBuf::from(path);
+    let is_safe = relative.components().all(|c| matches!(c, std::path::Component::Normal(_)));
+    is_safe.then_some(relative)
+}
+
+#[cfg(unix)]
+fn file_mode(path: &Path) -> u32 {
+    use std::os::unix::fs::PermissionsExt;
+    fs::metadata(path).map(|m| m.permissions().mode() & 0o777).unwrap_or(0)
+}
+
+#[cfg(not(unix))]
+fn file_mode(_path: &Path) -> u32 {
+    0
+}
+
+#[cfg(unix)]
+fn set_mode(path: &Path, mode: u32) {
+    use std::os::unix::fs::PermissionsExt;
+    if mode & 0o777 != 0 {
+        let _ = fs::set_permissions(path, fs::Permissions::from_mode(mode & 0o777));
+    }
+}
+
+#[cfg(not(unix))]
+fn set_mode(_path: &Path, _mode: u32) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    #[test]
+    fn parses_backup_names_and_extracts_zips_safely() {
+        assert_eq!(parse_backup_name("rust_dev_terminal-backup-v1.2.3-1714550000.zip"), Some((BackupKind::Full, "1.2.3".to_string(), 1714550000)));
+        assert_eq!(parse_backup_name("my-app-backup-v0.1.0-beta-1714550000.manifest.json"), Some((BackupKind::Incremental, "0.1.0-beta".to_string(), 1714550000)));
+        assert_eq!(parse_backup_name("notes.zip"), None);
+
+        let workspace = TempDir::new("extract");
+        let archive_path = workspace.join("app-backup-v1.0.0-1.zip");
+        let mut zip = zip::ZipWriter::new(File::create(&archive_path).unwrap());
+        let options = zip::write::FileOptions::default();
+        zip.add_directory("src/", options).unwrap();
+        zip.start_file("src/main.rs", options).unwrap();
+        zip.write_all(b"fn main() {}").unwrap();
+        zip.finish().unwrap();
+
+        let restored = workspace.join("restored");
+        assert_eq!(extract_zip(&archive_path, &restored).unwrap(), 1);
+        assert_eq!(fs::read_to_string(restored.join("src/main.rs")).unwrap(), "fn main() {}");
+
+        let mut zip = zip::ZipWriter::new(File::create(&archive_path).unwrap());
+        zip.start_file("../escape.rs", options).unwrap();
+        zip.finish().unwrap();
+        assert!(extract_zip(&archive_path, &restored).is_err());
+        assert!(!workspace.join("escape.rs").exists());
+    }
+
+    #[test]
+    fn incremental_backups_share_contents_and_honor_retention() {
+        let workspace = TempDir::new("backup");
+        let project = workspace.join("app");
+        let backups = workspace.join("backups");
+        fs::create_dir_all(project.join("src")).unwrap();
+        fs::create_dir_all(project.join("target/release")).unwrap();
+        fs::create_dir_all(project.join("logs")).unwrap();
+        fs::write(project.join(".gitignore"), "logs/\n").unwrap();
+        fs::write(project.join("Cargo.toml"), "[package]\nname = \"app\"\n").unwrap();
+        fs::write(project.join("src/main.rs"), "fn main() {}").unwrap();
+        fs::write(project.join("target/release/app"), "binary").unwrap();
+        fs::write(project.join("logs/run.log"), "noise").unwrap();
+
+        let config = BackupConfig { keep_count: Some(2), incremental: true, ..BackupConfig::default() };
+        let first = create_backup(&project, &backups, "app", "1.0.0", 1_000, &config).unwrap();
+        assert_eq!(first.files, 3);  // .gitignore, Cargo.toml, src/main.rs
+
+        fs::write(project.join("src/main.rs"), "fn main() { println!(); }").unwrap();
+        let second = create_backup(&project, &backups, "app", "1.0.1", 2_000, &config).unwrap();
+        // Only the changed file's contents are stored again
+        assert!(second.stored_bytes < first.stored_bytes);
+
+        let third = create_backup(&project, &backups, "app", "1.0.2", 3_000, &config).unwrap();
+        assert_eq!(third.removed, vec![first.path.clone()]);
+        let listed: Vec<String> = list_backups(&backups).unwrap().iter().map(|b| b.version.clone()).collect();
+        assert_eq!(listed, ["1.0.2", "1.0.1"]);
+
+        let restored = workspace.join("restored");
+        let second_info = BackupInfo::from_path(&second.path).unwrap();
+        assert_eq!(restore_backup(&second_info, &restored).unwrap(), 3);
+        assert_eq!(fs::read_to_string(restored.join("src/main.rs")).unwrap(), "fn main() { println!(); }");
+        assert!(!restored.join("target").exists() && !restored.join("logs").exists());
+
+        // The first version of main.rs was only referenced by the deleted backup
+        let objects: usize = fs::read_dir(backups.join(OBJECTS_DIR)).unwrap()
+            .map(|shard| fs::read_dir(shard.unwrap().path()).unwrap().count())
+            .sum();
+        assert_eq!(objects, 3);
+    }
+}
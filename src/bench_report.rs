@@ -0,0 +1,296 @@
+// bench_report.rs
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use chrono::Local;
+use crate::ansi_theme::AnsiTheme;
+use crate::config::ThemeConfig;
+use crate::project_settings;
+
+const BASELINES_FILE: &str = "bench_baselines.json";
+
+/// One benchmark result, normalised to nanoseconds per iteration
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Benchmark {
+    pub name: String,
+    pub ns_per_iter: f64,
+    /// libtest's "+/-" spread, or half of criterion's confidence interval
+    pub deviation_ns: Option<f64>,
+}
+
+/// Parses `cargo bench` output from libtest (`test x ... bench: 1,234 ns/iter (+/- 5)`)
+/// and criterion (`x  time: [1.20 µs 1.23 µs 1.26 µs]`, name possibly on the line above)
+pub fn parse_bench_output(stdout: &str) -> Vec<Benchmark> {
+    let mut benchmarks = Vec::new();
+    let mut previous_line = "";
+
+    for line in stdout.lines() {
+        if let Some(benchmark) = parse_libtest_line(line).or_else(|| parse_criterion_line(line, previous_line)) {
+            benchmarks.push(benchmark);
+        }
+        if !line.trim().is_empty() {
+            previous_line = line;
+        }
+    }
+    benchmarks
+}
+
+fn parse_libtest_line(line: &str) -> Option<Benchmark> {
+    let rest = line.strip_prefix("test ")?;
+    let (name, outcome) = rest.split_once(" ... bench:")?;
+    let (value, rest) = outcome.trim().split_once(" ns/iter")?;
+    let deviation = rest.trim()
+        .strip_prefix("(+/-")
+        .and_then(|d| d.trim().strip_suffix(')'))
+        .and_then(parse_number);
+
+    Some(Benchmark {
+        name: name.trim().to_string(),
+        ns_per_iter: parse_number(value)?,
+        deviation_ns: deviation,
+    })
+}
+
+fn parse_criterion_line(line: &str, previous_line: &str) -> Option<Benchmark> {
+    let (name, estimates) = line.split_once("time:")?;
+    let estimates = estimates.trim().strip_prefix('[')?.strip_suffix(']')?;
+
+    // "[low unit estimate unit high unit]"
+    let fields: Vec<&str> = estimates.split_whitespace().collect();
+    if fields.len() != 6 {
+        return None;
+    }
+    let values: Vec<f64> = fields.chunks(2)
+        .map(|pair| Some(parse_number(pair[0])? * unit_in_ns(pair[1])?))
+        .collect::<Option<_>>()?;
+
+    // Long names get a line of their own, with "time:" indented below them
+    let name = match name.trim() {
+        "" => previous_line.trim(),
+        name => name,
+    };
+    if name.is_empty() {
+        return None;
+    }
+
+    Some(Benchmark {
+        name: name.to_string(),
+        ns_per_iter: values[1],
+        deviation_ns: Some((values[2] - values[0]) / 2.0),
+    })
+}
+
+fn parse_number(text: &str) -> Option<f64> {
+    text.trim().replace(',', "").parse().ok()
+}
+
+fn unit_in_ns(unit: &str) -> Option<f64> {
+    match unit {
+        "ps" => Some(0.001),
+        "ns" => Some(1.0),
+        "µs" | "μs" | "us" => Some(1_000.0),
+        "ms" => Some(1_000_000.0),
+        "s" => Some(1_000_000_000.0),
+        _ => None,
+    }
+}
+
+/// e.g. "812 ns", "12.35 µs", "1.20 s"
+pub fn format_ns(ns: f64) -> String {
+    match ns {
+        n if n < 1_000.0 => format!("{:.0} ns", n),
+        n if n < 1_000_000.0 => format!("{:.2} µs", n / 1_000.0),
+        n if n < 1_000_000_000.0 => format!("{:.2} ms", n / 1_000_000.0),
+        n => format!("{:.2} s", n / 1_000_000_000.0),
+    }
+}
+
+/// A saved set of results that later runs are compared against
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Baseline {
+    pub timestamp: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+    /// Nanoseconds per iteration by benchmark name
+    pub benchmarks: BTreeMap<String, f64>,
+}
+
+/// Named baselines stored in the project's state directory
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BenchBaselines {
+    #[serde(default)]
+    pub baselines: BTreeMap<String, Baseline>,
+}
+
+impl BenchBaselines {
+    pub fn load(project_path: &Path) -> Self {
+        fs::read_to_string(project_path.join(project_settings::STATE_DIR).join(BASELINES_FILE))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, project_path: &Path) -> Result<(), String> {
+        let path = project_settings::state_dir(project_path)?.join(BASELINES_FILE);
+        let content = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        fs::write(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+
+    /// Saves `benchmarks` under `name`, replacing any baseline with that name
+    pub fn insert(&mut self, name: &str, benchmarks: &[Benchmark], commit: Option<String>) {
+        self.baselines.insert(name.to_string(), Baseline {
+            timestamp: Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+            commit,
+            benchmarks: benchmarks.iter().map(|b| (b.name.clone(), b.ns_per_iter)).collect(),
+        });
+    }
+
+    /// Name of the most recently saved baseline
+    pub fn latest(&self) -> Option<&str> {
+        self.baselines.iter()
+            .max_by(|a, b| a.1.timestamp.cmp(&b.1.timestamp))
+            .map(|(name, _)| name.as_str())
+    }
+}
+
+/// One benchmark measured against a baseline
+#[derive(Debug, Clone, Serialize)]
+pub struct BenchComparison {
+    pub name: String,
+    pub ns_per_iter: f64,
+    /// None when the benchmark is new since the baseline was saved
+    pub baseline_ns: Option<f64>,
+    /// Positive when slower than the baseline
+    pub change_percent: Option<f64>,
+    pub regression: bool,
+}
+
+/// Compares each benchmark with the baseline; slowdowns above `threshold_percent` are regressions
+pub fn compare(benchmarks: &[Benchmark], baseline: &Baseline, threshold_percent: f64) -> Vec<BenchComparison> {
+    benchmarks.iter()
+        .map(|benchmark| {
+            let baseline_ns = baseline.benchmarks.get(&benchmark.name).copied().filter(|&ns| ns > 0.0);
+            let change_percent = baseline_ns.map(|base| (benchmark.ns_per_iter - base) / base * 100.0);
+            BenchComparison {
+                name: benchmark.name.clone(),
+                ns_per_iter: benchmark.ns_per_iter,
+                baseline_ns,
+                change_percent,
+                regression: change_percent.is_some_and(|change| change > threshold_percent),
+            }
+        })
+        .collect()
+}
+
+/// Results of one run measured against a named baseline
+#[derive(Debug, Clone, Serialize)]
+pub struct BaselineReport {
+    pub baseline: String,
+    pub threshold_percent: f64,
+    pub comparisons: Vec<BenchComparison>,
+}
+
+impl BaselineReport {
+    pub fn new(benchmarks: &[Benchmark], name: &str, baseline: &Baseline, threshold_percent: f64) -> Self {
+        BaselineReport {
+            baseline: name.to_string(),
+            threshold_percent,
+            comparisons: compare(benchmarks, baseline, threshold_percent),
+        }
+    }
+
+    pub fn regressions(&self) -> usize {
+        self.comparisons.iter().filter(|c| c.regression).count()
+    }
+
+    /// Regressions are shown in red and improvements beyond the threshold in green
+    pub fn print(&self, theme: &ThemeConfig) {
+        AnsiTheme::print_themed("\n", theme);
+        AnsiTheme::print_blue(&format!(" {:<40} {:>12} {:>12} {:>9}\n", "BENCHMARK", "TIME/ITER", "BASELINE", "CHANGE"), theme);
+        for comparison in &self.comparisons {
+            let (base, change) = match (comparison.baseline_ns, comparison.change_percent) {
+                (Some(base), Some(change)) => (format_ns(base), format!("{:+.1}%", change)),
+                _ => ("-".to_string(), "new".to_string()),
+            };
+            let line = format!(" {:<40} {:>12} {:>12} {:>9}", comparison.name, format_ns(comparison.ns_per_iter), base, change);
+            if comparison.regression {
+                AnsiTheme::print_error(&format!("{}  regression\n", line), theme);
+            } else if comparison.change_percent.is_some_and(|c| c < -self.threshold_percent) {
+                AnsiTheme::print_success(&format!("{}  faster\n", line), theme);
+            } else {
+                AnsiTheme::print_themed(&format!("{}\n", line), theme);
+            }
+        }
+
+        match self.regressions() {
+            0 => AnsiTheme::print_info(&format!(" No regressions beyond {}% against '{}'\n", self.threshold_percent, self.baseline), theme),
+            n => AnsiTheme::print_error(&format!(" {} benchmark(s) regressed more than {}% against '{}'\n", n, self.threshold_percent, self.baseline), theme),
+        }
+    }
+}
+
+/// Plain results table, for runs without a baseline
+pub fn print_benchmarks(benchmarks: &[Benchmark], theme: &ThemeConfig) {
+    AnsiTheme::print_themed("\n", theme);
+    AnsiTheme::print_blue(&format!(" {:<40} {:>12} {:>12}\n", "BENCHMARK", "TIME/ITER", "+/-"), theme);
+    for benchmark in benchmarks {
+        let deviation = benchmark.deviation_ns.map(format_ns).unwrap_or_default();
+        AnsiTheme::print_themed(&format!(" {:<40} {:>12} {:>12}\n", benchmark.name, format_ns(benchmark.ns_per_iter), deviation), theme);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OUTPUT: &str = "
+running 2 tests
+test bench_parse  ... bench:       1,234 ns/iter (+/- 56)
+test bench_small  ... bench:          12.50 ns/iter (+/- 0.40)
+
+test result: ok. 0 passed; 0 failed; 0 ignored; 2 measured; 0 filtered out
+
+fib 20                  time:   [26.029 µs 26.251 µs 26.505 µs]
+                        change: [-1.2%  +0.3%  +1.5%] (p = 0.61 > 0.05)
+                        No change in performance detected.
+group/a/very/long/benchmark/name
+                        time:   [1.0000 ms 1.2000 ms 1.4000 ms]
+";
+
+    #[test]
+    fn parses_libtest_and_criterion_results() {
+        let benchmarks = parse_bench_output(OUTPUT);
+        let names: Vec<&str> = benchmarks.iter().map(|b| b.name.as_str()).collect();
+        assert_eq!(names, vec!["bench_parse", "bench_small", "fib 20", "group/a/very/long/benchmark/name"]);
+
+        assert_eq!(benchmarks[0].ns_per_iter, 1234.0);
+        assert_eq!(benchmarks[0].deviation_ns, Some(56.0));
+        assert_eq!(benchmarks[1].ns_per_iter, 12.5);
+        assert!((benchmarks[2].ns_per_iter - 26_251.0).abs() < 0.01);
+        assert!((benchmarks[3].ns_per_iter - 1_200_000.0).abs() < 0.01);
+        assert!((benchmarks[3].deviation_ns.unwrap() - 200_000.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn flags_slowdowns_beyond_the_threshold() {
+        let mut baselines = BenchBaselines::default();
+        baselines.insert("main", &[
+            Benchmark { name: "fast".to_string(), ns_per_iter: 100.0, deviation_ns: None },
+            Benchmark { name: "slow".to_string(), ns_per_iter: 100.0, deviation_ns: None },
+        ], None);
+
+        let current = [
+            Benchmark { name: "fast".to_string(), ns_per_iter: 104.0, deviation_ns: None },
+            Benchmark { name: "slow".to_string(), ns_per_iter: 120.0, deviation_ns: None },
+            Benchmark { name: "new".to_string(), ns_per_iter: 50.0, deviation_ns: None },
+        ];
+        let comparisons = compare(&current, &baselines.baselines["main"], 5.0);
+
+        assert!(!comparisons[0].regression);
+        assert!(comparisons[1].regression);
+        assert!((comparisons[1].change_percent.unwrap() - 20.0).abs() < 1e-9);
+        assert_eq!((comparisons[2].baseline_ns, comparisons[2].regression), (None, false));
+        assert_eq!(baselines.latest(), Some("main"));
+    }
+}
@@ -0,0 +1,337 @@
+// build_history.rs
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+use chrono::Local;
+use crate::ansi_theme::AnsiTheme;
+use crate::config::ThemeConfig;
+use crate::timings_report::TimingsSummary;
+
+const HISTORY_FILE: &str = "build_history.jsonl";
+// Entries kept per project; older ones are dropped as new ones are recorded
+const HISTORY_LIMIT: usize = 1000;
+// Successful runs before the latest one that form the baseline for regression checks
+const BASELINE_RUNS: usize = 10;
+// A run counts as a regression when it is this much slower than the baseline median...
+const REGRESSION_RATIO: f64 = 1.25;
+// ...and slower by at least this much, so millisecond noise on tiny projects is ignored
+const REGRESSION_MIN: Duration = Duration::from_millis(500);
+
+/// One recorded build, check, test or run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub timestamp: String,
+    pub project: String,
+    pub command: String,
+    pub profile: String,
+    pub success: bool,
+    pub duration_ms: u64,
+    #[serde(default)]
+    pub warnings: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+    /// Compile-time breakdown of `--timings` builds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timings: Option<TimingsSummary>,
+}
+
+impl HistoryEntry {
+    pub fn new(project: &str, command: &str, profile: &str, success: bool, duration: Duration, warnings: usize) -> Self {
+        HistoryEntry {
+            timestamp: Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+            project: project.to_string(),
+            command: command.to_string(),
+            profile: profile.to_string(),
+            success,
+            duration_ms: duration.as_millis() as u64,
+            warnings,
+            commit: None,
+            timings: None,
+        }
+    }
+
+    pub fn duration(&self) -> Duration {
+        Duration::from_millis(self.duration_ms)
+    }
+}
+
+/// JSON-lines store of every project's operations in the per-user config directory, keyed by
+/// project name and trimmed to the most recent `HISTORY_LIMIT` entries per project
+pub struct BuildHistory {
+    path: PathBuf,
+    limit: usize,
+}
+
+impl BuildHistory {
+    pub fn new(path: PathBuf) -> Self {
+        BuildHistory { path, limit: HISTORY_LIMIT }
+    }
+
+    /// A fixed per-user file, wherever the terminal was started from
+    pub fn default_path() -> PathBuf {
+        dirs::config_dir()
+            .unwrap_or_else(std::env::temp_dir)
+            .join("rust_dev_terminal")
+            .join(HISTORY_FILE)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn record(&self, entry: &HistoryEntry) -> Result<(), String> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+        }
+        let line = serde_json::to_string(entry).map_err(|e| e.to_string())?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(|e| format!("Failed to open {}: {}", self.path.display(), e))?;
+        writeln!(file, "{}", line).map_err(|e| format!("Failed to write {}: {}", self.path.display(), e))?;
+        drop(file);
+
+        // Once the project has grown past the limit, rewrite the file without its oldest entries
+        let content = fs::read_to_string(&self.path)
+            .map_err(|e| format!("Failed to read {}: {}", self.path.display(), e))?;
+        let lines: Vec<&str> = content.lines().collect();
+        let is_project = |line: &str| serde_json::from_str::<HistoryEntry>(line).is_ok_and(|e| e.project == entry.project);
+        let excess = lines.iter().filter(|line| is_project(line)).count().saturating_sub(self.limit);
+        if excess > 0 {
+            let mut dropped = 0;
+            let kept: Vec<&str> = lines.into_iter()
+                .filter(|line| {
+                    let oldest = dropped < excess && is_project(line);
+                    dropped += oldest as usize;
+                    !oldest
+                })
+                .collect();
+            fs::write(&self.path, kept.join("\n") + "\n")
+                .map_err(|e| format!("Failed to write {}: {}", self.path.display(), e))?;
+        }
+        Ok(())
+    }
+
+    /// All entries for `project`, oldest first. Unreadable lines are skipped.
+    pub fn entries(&self, project: &str) -> Vec<HistoryEntry> {
+        fs::read_to_string(&self.path)
+            .unwrap_or_default()
+            .lines()
+            .filter_map(|line| serde_json::from_str::<HistoryEntry>(line).ok())
+            .filter(|entry| entry.project == project)
+            .collect()
+    }
+}
+
+/// Short hash of the project's checked-out commit, if it is a git repository
+pub fn git_commit(project_path: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .current_dir(project_path)
+        .output()
+        .ok()?;
+    let hash = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if output.status.success() && !hash.is_empty() { Some(hash) } else { None }
+}
+
+/// Timing trend for one command/profile pair, e.g. `build` + `release`
+#[derive(Debug, Clone, Serialize)]
+pub struct Trend {
+    pub command: String,
+    pub profile: String,
+    pub runs: usize,
+    pub successes: usize,
+    pub last_duration_ms: u64,
+    /// Median of the successful runs before the latest one
+    pub baseline_ms: Option<u64>,
+    pub regression: bool,
+}
+
+impl Trend {
+    fn from_entries(entries: &[&HistoryEntry]) -> Option<Self> {
+        let last = entries.last()?;
+        let successful: Vec<u64> = entries.iter().filter(|e| e.success).map(|e| e.duration_ms).collect();
+
+        // The latest successful run is measured against the ones before it
+        let baseline_ms = match successful.split_last() {
+            Some((_, previous)) if !previous.is_empty() => {
+                Some(median(&previous[previous.len().saturating_sub(BASELINE_RUNS)..]))
+            }
+            _ => None,
+        };
+        let regression = match (successful.last(), baseline_ms) {
+            (Some(&latest), Some(baseline)) => {
+                latest as f64 > baseline as f64 * REGRESSION_RATIO
+                    && latest.saturating_sub(baseline) >= REGRESSION_MIN.as_millis() as u64
+            }
+            _ => false,
+        };
+
+        Some(Trend {
+            command: last.command.clone(),
+            profile: last.profile.clone(),
+            runs: entries.len(),
+            successes: successful.len(),
+            last_duration_ms: last.duration_ms,
+            baseline_ms,
+            regression,
+        })
+    }
+}
+
+fn median(values: &[u64]) -> u64 {
+    let mut sorted = values.to_vec();
+    sorted.sort_unstable();
+    sorted[sorted.len() / 2]
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HistoryReport {
+    pub project: String,
+    pub last_green_build: Option<HistoryEntry>,
+    pub trends: Vec<Trend>,
+    pub recent: Vec<HistoryEntry>,
+}
+
+impl HistoryReport {
+    pub fn new(project: &str, entries: &[HistoryEntry], recent_count: usize) -> Self {
+        let mut groups: BTreeMap<(&str, &str), Vec<&HistoryEntry>> = BTreeMap::new();
+        for entry in entries {
+            groups.entry((entry.command.as_str(), entry.profile.as_str())).or_default().push(entry);
+        }
+
+        HistoryReport {
+            project: project.to_string(),
+            last_green_build: entries.iter().rev().find(|e| e.command == "build" && e.success).cloned(),
+            trends: groups.values().filter_map(|group| Trend::from_entries(group)).collect(),
+            recent: entries[entries.len().saturating_sub(recent_count)..].iter().rev().cloned().collect(),
+        }
+    }
+
+    pub fn print(&self, theme: &ThemeConfig) {
+        AnsiTheme::print_themed("\n", theme);
+        AnsiTheme::print_yellow(&format!("Build History - {}\n", self.project), theme);
+
+        if self.recent.is_empty() {
+            AnsiTheme::print_themed("No recorded builds yet.\n", theme);
+            return;
+        }
+
+        match &self.last_green_build {
+            Some(entry) => AnsiTheme::print_success(&format!(" Last green build: {} ({}, {:.2?}{})\n",
+                entry.timestamp, entry.profile, entry.duration(), commit_suffix(entry)), theme),
+            None => AnsiTheme::print_warning(" No successful build recorded\n", theme),
+        }
+
+        AnsiTheme::print_themed("\n", theme);
+        AnsiTheme::print_blue(&format!(" {:<8} {:<10} {:>5} {:>8} {:>10} {:>10}\n",
+            "COMMAND", "PROFILE", "RUNS", "SUCCESS", "LAST", "MEDIAN"), theme);
+        for trend in &self.trends {
+            let baseline = trend.baseline_ms
+                .map(|ms| format!("{:.2?}", Duration::from_millis(ms)))
+                .unwrap_or_else(|| "-".to_string());
+            let line = format!(" {:<8} {:<10} {:>5} {:>7}% {:>10} {:>10}",
+                trend.command, trend.profile, trend.runs, trend.successes * 100 / trend.runs,
+                format!("{:.2?}", Duration::from_millis(trend.last_duration_ms)), baseline);
+            if trend.regression {
+                AnsiTheme::print_error(&format!("{}  slower than usual\n", line), theme);
+            } else {
+                AnsiTheme::print_themed(&format!("{}\n", line), theme);
+            }
+        }
+
+        AnsiTheme::print_themed("\n", theme);
+        AnsiTheme::print_cyan("Recent:\n", theme);
+        for entry in &self.recent {
+            let line = format!(" {} {:<6} {:<8} {:<10} {:>10} {} warnings{}{}\n",
+                entry.timestamp, if entry.success { "ok" } else { "FAILED" }, entry.command, entry.profile,
+                format!("{:.2?}", entry.duration()), entry.warnings, commit_suffix(entry), timings_suffix(entry));
+            if entry.success {
+                AnsiTheme::print_themed(&line, theme);
+            } else {
+                AnsiTheme::print_error(&line, theme);
+            }
+        }
+    }
+}
+
+fn commit_suffix(entry: &HistoryEntry) -> String {
+    entry.commit.as_ref().map(|c| format!(" @ {}", c)).unwrap_or_default()
+}
+
+// e.g. ", critical path 12.40s, slowest syn 4.10s"
+fn timings_suffix(entry: &HistoryEntry) -> String {
+    let Some(timings) = &entry.timings else { return String::new() };
+    let slowest = timings.slowest.first()
+        .map(|(name, ms)| format!(", slowest {} {:.2?}", name, Duration::from_millis(*ms)))
+        .unwrap_or_default();
+    format!(", critical path {:.2?}{}", Duration::from_millis(timings.critical_path_ms), slowest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    fn entry(command: &str, success: bool, ms: u64) -> HistoryEntry {
+        HistoryEntry::new("demo", command, "dev", success, Duration::from_millis(ms), 0)
+    }
+
+    #[test]
+    fn records_and_reads_entries_per_project() {
+        let dir = TempDir::new("history");
+        let history = BuildHistory::new(dir.join("config").join(HISTORY_FILE));
+
+        history.record(&entry("build", true, 1200)).unwrap();
+        history.record(&HistoryEntry { project: "other".to_string(), ..entry("check", true, 300) }).unwrap();
+
+        let entries = history.entries("demo");
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].duration(), Duration::from_millis(1200));
+        assert_eq!(history.entries("other").len(), 1);
+    }
+
+    #[test]
+    fn keeps_only_the_newest_entries_of_each_project() {
+        let dir = TempDir::new("history_limit");
+        let history = BuildHistory { path: dir.join(HISTORY_FILE), limit: 3 };
+
+        history.record(&HistoryEntry { project: "other".to_string(), ..entry("build", true, 100) }).unwrap();
+        for ms in 1..=5 {
+            history.record(&entry("build", true, ms)).unwrap();
+        }
+        let durations: Vec<u64> = history.entries("demo").iter().map(|e| e.duration_ms).collect();
+        assert_eq!(durations, vec![3, 4, 5]);
+        assert_eq!(history.entries("other").len(), 1);
+    }
+
+    #[test]
+    fn flags_regressions_against_the_median_and_finds_last_green_build() {
+        let entries = vec![
+            entry("build", true, 2000),
+            entry("build", true, 2100),
+            entry("build", true, 1900),
+            entry("build", false, 400),
+            entry("build", true, 3500),
+            entry("check", true, 800),
+            entry("check", true, 900),
+        ];
+        let report = HistoryReport::new("demo", &entries, 5);
+
+        let build = report.trends.iter().find(|t| t.command == "build").unwrap();
+        assert_eq!((build.runs, build.successes, build.baseline_ms), (5, 4, Some(2000)));
+        assert!(build.regression);
+
+        let check = report.trends.iter().find(|t| t.command == "check").unwrap();
+        assert!(!check.regression);
+
+        assert_eq!(report.last_green_build.unwrap().duration_ms, 3500);
+        assert_eq!(report.recent.len(), 5);
+        assert_eq!(report.recent[0].command, "check");
+    }
+}
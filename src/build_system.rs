@@ -1,83 +1,44 @@
 // build_system.rs (CONVERTED)
-use std::process::{Command, Stdio};
-use std::path::PathBuf;
-use std::fs::OpenOptions;
-use std::io::Write;
-use chrono::Utc;
-use std::backtrace::{Backtrace, BacktraceStatus};
+use std::process::{Child, Command, Output, Stdio};
+use std::path::{Path, PathBuf};
+use std::io::{BufRead, BufReader, IsTerminal, Read, Write};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::time::{Duration, SystemTime};
+use std::thread::{self, JoinHandle};
 use crate::ansi_theme::AnsiTheme;
-use crate::config::ThemeConfig;
-
-#[derive(Debug, Clone)]
-pub struct BuildLogger {
-    log_file: Option<String>,
-    verbose: bool,
-}
-
-impl BuildLogger {
-    pub fn new(log_file: Option<String>, verbose: bool) -> Self {
-        BuildLogger { log_file, verbose }
-    }
-
-    pub fn log(&self, level: &str, message: &str, context: Option<&str>, theme: &ThemeConfig) {
-        let timestamp = Utc::now().format("%Y-%m-%d %H:%M:%S%.3f");
-        let context_str = context.map(|c| format!(" [{}]", c)).unwrap_or_default();
-        let log_line = format!("{} {}{}: {}", timestamp, level, context_str, message);
-
-        // Always print to console if verbose or if it's an error/warning
-        if self.verbose || level == "ERROR" || level == "WARN" {
-            match level {
-                "ERROR" => AnsiTheme::print_error(&log_line, theme),
-                "WARN" => AnsiTheme::print_warning(&log_line, theme),
-                "INFO" => AnsiTheme::print_info(&log_line, theme),
-                "DEBUG" => AnsiTheme::print_themed(&log_line, theme),
-                _ => AnsiTheme::print_themed(&log_line, theme),
-            }
-        }
-
-        // Write to log file if specified
-        if let Some(ref log_path) = self.log_file {
-            if let Ok(mut file) = OpenOptions::new()
-                .create(true)
-                .append(true)
-                .open(log_path)
-            {
-                let _ = writeln!(file, "{}", log_line);
-            }
-        }
-    }
-
-    pub fn log_error_with_backtrace(&self, error: &str, context: Option<&str>, theme: &ThemeConfig) {
-        let backtrace = Backtrace::capture();
-        self.log("ERROR", error, context, theme);
-        
-        if backtrace.status() == BacktraceStatus::Captured {
-            self.log("DEBUG", &format!("Backtrace:\n{}", backtrace), context, theme);
-        }
-    }
-
-    pub fn log_command(&self, command: &str, args: &[String], context: Option<&str>, theme: &ThemeConfig) {
-        let full_command = if args.is_empty() {
-            command.to_string()
-        } else {
-            format!("{} {}", command, args.join(" "))
-        };
-        self.log("DEBUG", &format!("Executing: {}", full_command), context, theme);
-    }
-}
+use crate::archive::ArchiveFormat;
+use crate::bench_report::{self, Benchmark};
+use crate::build_history::{self, BuildHistory, HistoryEntry};
+use crate::config::{AppConfig, ThemeConfig};
+use crate::diagnostics::{self, Diagnostic, DiagnosticLevel};
+use crate::logger::{self, BuildLogger, LogLevel};
+use crate::packager::{PackageResult, ReleasePackager};
+use crate::process::{SupervisedCommand, Supervisor, Termination};
+use crate::project_settings::{BuildConfiguration, RunConfiguration};
+use crate::size_report::{BinarySizeReport, SizeComparison, SizeHistory, SizeReport};
+use crate::test_report::{self, TestCase, TestHistory, TestStatus};
+use crate::timings_report::{self, TimingsReport};
+use serde::Serialize;
 
 pub struct BuildSystem {
+    config: AppConfig,
     logger: BuildLogger,
+    history: BuildHistory,
 }
 
 impl BuildSystem {
-    pub fn new(log_file: Option<String>, verbose: bool) -> Self {
-        let logger = BuildLogger::new(log_file, verbose);
+    pub fn new_with_config(config: AppConfig) -> Self {
+        let logger = BuildLogger::from_config(&config);
         
         // Note: We can't log here since we don't have theme context
         // Logging will happen in methods that receive theme parameter
 
-        BuildSystem { logger }
+        BuildSystem { config, logger, history: BuildHistory::new(BuildHistory::default_path()) }
+    }
+
+    pub fn new() -> Self {
+        let config_manager = crate::config::ConfigManager::new();
+        Self::new_with_config(config_manager.get_config().clone())
     }
 
     // Helper method to execute commands with consistent logging and backtrace capture
@@ -89,54 +50,155 @@ impl BuildSystem {
         context: &str,
         capture_child_output: bool,
         theme: &ThemeConfig,
-    ) -> Result<(std::process::Output, std::time::Duration), String> {
-        let start_time = std::time::Instant::now();
-
-        self.logger.log("DEBUG", &format!("Project path: {}", project_path.display()), Some(context), theme);
+    ) -> Result<(std::process::Output, std::time::Duration, Termination), String> {
+        self.logger.log(LogLevel::Debug, &format!("Project path: {}", project_path.display()), Some(context), theme);
         
         let mut cmd = Command::new(command);
-        cmd.current_dir(project_path)
-           .env("RUST_BACKTRACE", "1");  // Enable backtraces in child processes
+        cmd.current_dir(project_path);
 
         for arg in args {
             cmd.arg(arg);
         }
 
-        // Only capture output if requested (for operations that produce meaningful output)
-        if capture_child_output {
-            cmd.stdout(Stdio::piped()).stderr(Stdio::piped());
-        }
+        self.execute_prepared(&mut cmd, context, capture_child_output, theme)
+    }
+
+    // Runs a command whose arguments, directory and environment are already set up
+    fn execute_prepared(
+        &self,
+        cmd: &mut Command,
+        context: &str,
+        capture_child_output: bool,
+        theme: &ThemeConfig,
+    ) -> Result<(std::process::Output, std::time::Duration, Termination), String> {
+        let start_time = std::time::Instant::now();
+        let command = cmd.get_program().to_string_lossy().to_string();
+        let args: Vec<String> = cmd.get_args().map(|a| a.to_string_lossy().to_string()).collect();
+        let timeout = self.config.timeouts.get(args.first().map(|a| a.as_str()).unwrap_or_default());
 
-        self.logger.log("DEBUG", &format!("Executing: {} {}", command, args.join(" ")), Some(context), theme);
+        cmd.env("RUST_BACKTRACE", "1");  // Enable backtraces in child processes
 
-        let output = cmd.output().map_err(|e| {
+        self.logger.log(LogLevel::Debug, &format!("Executing: {} {}", command, args.join(" ")), Some(context), theme);
+
+        // Only stream and capture output if requested (for operations that produce meaningful output)
+        let output = if capture_child_output {
+            if command == "cargo" {
+                // Cargo only draws its progress bar for terminals unless asked to
+                cmd.env("CARGO_TERM_PROGRESS_WHEN", "always")
+                   .env("CARGO_TERM_PROGRESS_WIDTH", "80");
+            }
+            let json_messages = args.iter().any(|a| a.starts_with("--message-format=json"));
+            self.stream_command(cmd, context, json_messages, timeout, theme)
+        } else {
+            cmd.stdin(Stdio::null()).supervised_output(timeout).map_err(|e| e.to_string())
+        }.map_err(|e| {
             let error_msg = format!("Failed to execute {} {}: {}", command, args.join(" "), e);
             self.logger.log_error_with_backtrace(&error_msg, Some(context), theme);
             error_msg
         })?;
-
+        let (output, termination) = output;
         let duration = start_time.elapsed();
 
-        // Log child process output if we captured it
-        if capture_child_output {
-            let stdout = String::from_utf8_lossy(&output.stdout);
-            let stderr = String::from_utf8_lossy(&output.stderr);
+        match termination {
+            Termination::TimedOut => self.logger.log(LogLevel::Warn, &format!("{} {} timed out after {:.2?}, process tree killed",
+                command, args.join(" "), duration), Some(context), theme),
+            Termination::Cancelled => self.logger.log(LogLevel::Warn, &format!("{} {} cancelled by Ctrl-C",
+                command, args.join(" ")), Some(context), theme),
+            Termination::Exited => {}
+        }
+
+        Ok((output, duration, termination))
+    }
 
-            if !stdout.trim().is_empty() {
-                self.logger.log("DEBUG", &format!("Child process stdout:\n{}", stdout), Some(context), theme);
+    // Runs the command, echoing each stdout/stderr line to the console and the log file as it
+    // arrives, while collecting everything into the returned Output. With `json_messages`, cargo's
+    // JSON lines are kept in stdout for parsing but only rustc's rendered text is shown.
+    // The child's process tree is killed when `timeout` passes or Ctrl-C is pressed.
+    fn stream_command(&self, cmd: &mut Command, context: &str, json_messages: bool, timeout: Option<Duration>, theme: &ThemeConfig) -> Result<(Output, Termination), String> {
+        let mut child = cmd.stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| e.to_string())?;
+        let mut supervisor = Supervisor::start(timeout);
+
+        let (sender, receiver) = mpsc::channel();
+        let readers = [
+            spawn_line_reader(child.stdout.take().expect("stdout is piped"), OutputStream::Stdout, sender.clone()),
+            spawn_line_reader(child.stderr.take().expect("stderr is piped"), OutputStream::Stderr, sender),
+        ];
+
+        let show_progress = std::io::stdout().is_terminal() && AnsiTheme::output_enabled();
+        let mut progress_visible = false;
+        let mut stdout = String::new();
+        let mut stderr = String::new();
+
+        // Ends once both readers have hit EOF and dropped their senders. The supervisor is polled on
+        // every pass, so a child that never stops printing still times out or is cancelled.
+        loop {
+            let message = receiver.recv_timeout(Duration::from_millis(100));
+            supervisor.poll(&mut child);
+            let (stream, line) = match message {
+                Ok(message) => message,
+                Err(RecvTimeoutError::Timeout) => continue,
+                Err(RecvTimeoutError::Disconnected) => break,
+            };
+            if let Some(progress) = BuildProgress::parse(&line) {
+                if show_progress {
+                    // Keep the status on one row so the carriage return can redraw it
+                    let width = crossterm::terminal::size().map(|(w, _)| w as usize).unwrap_or(80);
+                    let status: String = format!(" {}", progress).chars().take(width.saturating_sub(1)).collect();
+                    AnsiTheme::print_info(&format!("\r\x1b[2K{}", status), theme);
+                    let _ = std::io::stdout().flush();
+                    progress_visible = true;
+                }
+                continue;
             }
-            if !stderr.trim().is_empty() {
-                let log_level = if output.status.success() { "DEBUG" } else { "ERROR" };
-                self.logger.log(log_level, &format!("Child process stderr:\n{}", stderr), Some(context), theme);
+
+            let shown = match stream {
+                OutputStream::Stdout if json_messages => diagnostics::console_text(&line),
+                _ => None,
+            }.unwrap_or_else(|| Some(line.clone()));
+
+            if let Some(text) = shown {
+                if progress_visible {
+                    AnsiTheme::print_themed("\r\x1b[2K", theme);
+                    progress_visible = false;
+                }
+                AnsiTheme::print_themed(&format!("{}\n", text), theme);
+                self.logger.log_to_file(LogLevel::Debug, &format!("{}: {}", stream.label(), text), Some(context));
             }
+
+            let buffer = match stream {
+                OutputStream::Stdout => &mut stdout,
+                OutputStream::Stderr => &mut stderr,
+            };
+            buffer.push_str(&line);
+            buffer.push('\n');
         }
 
-        Ok((output, duration))
+        if progress_visible {
+            AnsiTheme::print_themed("\r\x1b[2K", theme);
+        }
+
+        for reader in readers {
+            let _ = reader.join();
+        }
+        let (status, termination) = supervisor.wait(&mut child).map_err(|e| e.to_string())?;
+
+        Ok((Output {
+            status,
+            stdout: stdout.into_bytes(),
+            stderr: stderr.into_bytes(),
+        }, termination))
+    }
+
+    pub fn get_project_path(&self, project_name: &str) -> PathBuf {
+        self.config.get_project_path(project_name)
     }
 
     // Helper to validate project existence
     fn validate_project_exists(&self, project_name: &str, context: &str, theme: &ThemeConfig) -> Result<PathBuf, String> {
-        let project_path = PathBuf::from("D:\\RustProjects").join(project_name);
+        let project_path = self.config.get_project_path(project_name);
         
         if !project_path.exists() {
             let error_msg = format!("Project '{}' does not exist!", project_name);
@@ -147,48 +209,71 @@ impl BuildSystem {
         Ok(project_path)
     }
 
+    // Appends the outcome to the build history; a failure to record never fails the operation
+    fn record_history(&self, mut entry: HistoryEntry, project_path: &Path, context: &str, theme: &ThemeConfig) {
+        entry.commit = build_history::git_commit(project_path);
+        if let Err(e) = self.history.record(&entry) {
+            self.logger.log(LogLevel::Warn, &format!("Could not record build history: {}", e), Some(context), theme);
+        }
+    }
+
+    pub fn history_entries(&self, project_name: &str) -> Vec<HistoryEntry> {
+        self.history.entries(project_name)
+    }
+
+    /// Percent slowdown against a baseline that counts as a benchmark regression
+    pub fn bench_regression_threshold(&self) -> f64 {
+        self.config.bench.regression_threshold()
+    }
+
     pub fn build_self(&self, theme: &ThemeConfig) -> Result<BuildResult, String> {
-        let context = "self-build";
-        self.logger.log("INFO", "Starting self-build", Some(context), theme);
-        
         let current_dir = std::env::current_dir()
             .map_err(|e| {
                 let error_msg = format!("Failed to get current directory: {}", e);
-                self.logger.log_error_with_backtrace(&error_msg, Some(context), theme);
+                self.logger.log_error_with_backtrace(&error_msg, Some("self-build"), theme);
                 error_msg
             })?;
+        self.build_self_in(&current_dir, None, theme)
+    }
+
+    /// Release build of the terminal's own source tree in `directory`, e.g. a restored backup.
+    /// `target_dir` overrides cargo's target directory so the build can leave `directory/target` alone.
+    pub fn build_self_in(&self, directory: &Path, target_dir: Option<&Path>, theme: &ThemeConfig) -> Result<BuildResult, String> {
+        let context = "self-build";
+        let _operation = logger::begin_operation();
+        self.logger.log(LogLevel::Info, "Starting self-build", Some(context), theme);
+
+        self.logger.log(LogLevel::Debug, &format!("Building in directory: {}", directory.display()), Some(context), theme);
 
-        self.logger.log("DEBUG", &format!("Building in directory: {}", current_dir.display()), Some(context), theme);
+        let target_dir = target_dir.map(|dir| dir.to_string_lossy().to_string());
+        let mut args = vec!["build", "--release"];
+        if let Some(dir) = &target_dir {
+            args.extend(["--target-dir", dir.as_str()]);
+        }
         
-        let (output, duration) = self.execute_command(
+        let (output, duration, termination) = self.execute_command(
             "cargo",
-            &["build", "--release"],
-            &current_dir,
+            &args,
+            &directory.to_path_buf(),
             context,
             true,  // Capture output for self-build
             theme,
         )?;
 
-        let success = output.status.success();
-        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+        let result = BuildResult::from_output(output, duration, termination);
 
-        if success {
-            self.logger.log("INFO", &format!("Self-build completed successfully in {:.2?}", duration), Some(context), theme);
+        if result.success {
+            self.logger.log(LogLevel::Info, &format!("Self-build completed successfully in {:.2?}{}", duration, result.summary_suffix()), Some(context), theme);
         } else {
-            self.logger.log("ERROR", &format!("Self-build failed after {:.2?}", duration), Some(context), theme);
+            self.logger.log(LogLevel::Error, &format!("Self-build failed after {:.2?}{}", duration, result.summary_suffix()), Some(context), theme);
         }
 
-        Ok(BuildResult {
-            success,
-            stdout,
-            stderr,
-            duration,
-        })
+        Ok(result)
     }
 
     pub fn get_self_binary_path(&self, theme: &ThemeConfig) -> Result<PathBuf, String> {
         let context = "binary-path-lookup";
+        let _operation = logger::begin_operation();
 
         let current_exe = std::env::current_exe()
             .map_err(|e| {
@@ -208,18 +293,18 @@ impl BuildSystem {
         let binary_name = if cfg!(windows) { "rust_dev_terminal.exe" } else { "rust_dev_terminal" };
         let binary_path = target_dir.join(binary_name);
         
-        self.logger.log("DEBUG", &format!("Looking for binary at: {}", binary_path.display()), Some(context), theme);
+        self.logger.log(LogLevel::Debug, &format!("Looking for binary at: {}", binary_path.display()), Some(context), theme);
         
         if binary_path.exists() {
-            self.logger.log("DEBUG", "Binary found at primary location", Some(context), theme);
+            self.logger.log(LogLevel::Debug, "Binary found at primary location", Some(context), theme);
             Ok(binary_path)
         } else {
-            self.logger.log("WARN", "Binary not found at primary location, searching project root", Some(context), theme);
-            if let Some(project_root) = Self::find_project_root(&current_dir.to_path_buf()) {
+            self.logger.log(LogLevel::Warn, "Binary not found at primary location, searching project root", Some(context), theme);
+            if let Some(project_root) = Self::find_project_root(current_dir) {
                 let alt_binary_path = project_root.join("target").join("release").join(binary_name);
-                self.logger.log("DEBUG", &format!("Alternative binary path: {}", alt_binary_path.display()), Some(context), theme);
+                self.logger.log(LogLevel::Debug, &format!("Alternative binary path: {}", alt_binary_path.display()), Some(context), theme);
                 if alt_binary_path.exists() {
-                    self.logger.log("DEBUG", "Binary found at alternative location", Some(context), theme);
+                    self.logger.log(LogLevel::Debug, "Binary found at alternative location", Some(context), theme);
                     return Ok(alt_binary_path);
                 }
             }
@@ -229,8 +314,8 @@ impl BuildSystem {
         }
     }
 
-    fn find_project_root(start_dir: &PathBuf) -> Option<PathBuf> {
-        let mut current = start_dir.clone();
+    fn find_project_root(start_dir: &Path) -> Option<PathBuf> {
+        let mut current = start_dir.to_path_buf();
         
         while current.parent().is_some() {
             if current.join("Cargo.toml").exists() {
@@ -244,110 +329,149 @@ impl BuildSystem {
 
     pub fn build_project(&self, project_name: &str, theme: &ThemeConfig) -> Result<BuildResult, String> {
         let context = &format!("build:{}", project_name);
-        self.logger.log("INFO", &format!("Starting build for project: {}", project_name), Some(context), theme);
+        let _operation = logger::begin_operation();
+        self.logger.log(LogLevel::Info, &format!("Starting build for project: {}", project_name), Some(context), theme);
         
         let project_path = self.validate_project_exists(project_name, context, theme)?;
 
-        let (output, duration) = self.execute_command(
+        let (output, duration, termination) = self.execute_command(
             "cargo",
-            &["build"],
+            &["build", "--message-format=json"],
             &project_path,
             context,
             true,  // Capture build output
             theme,
         )?;
 
-        let success = output.status.success();
-        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+        let result = BuildResult::from_output(output, duration, termination);
 
-        if success {
-            self.logger.log("INFO", &format!("Build completed successfully in {:.2?}", duration), Some(context), theme);
+        if result.success {
+            self.logger.log(LogLevel::Info, &format!("Build completed successfully in {:.2?}{}", duration, result.summary_suffix()), Some(context), theme);
         } else {
-            self.logger.log("ERROR", &format!("Build failed after {:.2?}", duration), Some(context), theme);
+            self.logger.log(LogLevel::Error, &format!("Build failed after {:.2?}{}", duration, result.summary_suffix()), Some(context), theme);
         }
 
-        Ok(BuildResult {
-            success,
-            stdout,
-            stderr,
-            duration,
-        })
+        self.record_history(
+            HistoryEntry::new(project_name, "build", "dev", result.success, duration, result.count(DiagnosticLevel::Warning)),
+            &project_path, context, theme,
+        );
+
+        Ok(result)
     }
 
     pub fn build_release(&self, project_name: &str, theme: &ThemeConfig) -> Result<BuildResult, String> {
         let context = &format!("release-build:{}", project_name);
-        self.logger.log("INFO", &format!("Starting release build for project: {}", project_name), Some(context), theme);
+        let _operation = logger::begin_operation();
+        self.logger.log(LogLevel::Info, &format!("Starting release build for project: {}", project_name), Some(context), theme);
         
         let project_path = self.validate_project_exists(project_name, context, theme)?;
 
-        let (output, duration) = self.execute_command(
+        let (output, duration, termination) = self.execute_command(
             "cargo",
-            &["build", "--release"],
+            &["build", "--release", "--message-format=json"],
             &project_path,
             context,
             true,  // Capture build output
             theme,
         )?;
 
-        let success = output.status.success();
-        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+        let result = BuildResult::from_output(output, duration, termination);
 
-        if success {
-            self.logger.log("INFO", &format!("Release build completed successfully in {:.2?}", duration), Some(context), theme);
+        if result.success {
+            self.logger.log(LogLevel::Info, &format!("Release build completed successfully in {:.2?}{}", duration, result.summary_suffix()), Some(context), theme);
         } else {
-            self.logger.log("ERROR", &format!("Release build failed after {:.2?}", duration), Some(context), theme);
+            self.logger.log(LogLevel::Error, &format!("Release build failed after {:.2?}{}", duration, result.summary_suffix()), Some(context), theme);
         }
 
-        Ok(BuildResult {
-            success,
-            stdout,
-            stderr,
-            duration,
-        })
+        self.record_history(
+            HistoryEntry::new(project_name, "build", "release", result.success, duration, result.count(DiagnosticLevel::Warning)),
+            &project_path, context, theme,
+        );
+        if result.success {
+            self.record_release_size(project_name, &project_path, context, theme);
+        }
+
+        Ok(result)
+    }
+
+    pub fn build_with_configuration(&self, project_name: &str, configuration: &BuildConfiguration, theme: &ThemeConfig) -> Result<BuildResult, String> {
+        let context = &format!("build[{}]:{}", configuration.name, project_name);
+        let _operation = logger::begin_operation();
+        self.logger.log(LogLevel::Info, &format!("Starting '{}' build for project: {}", configuration.name, project_name), Some(context), theme);
+
+        configuration.validate()
+            .inspect_err(|e| self.logger.log(LogLevel::Error, e, Some(context), theme))?;
+        let project_path = self.validate_project_exists(project_name, context, theme)?;
+
+        let configured_args = configuration.cargo_args();
+        let mut args = vec!["build", "--message-format=json"];
+        args.extend(configured_args.iter().map(|a| a.as_str()));
+
+        let (output, duration, termination) = self.execute_command(
+            "cargo",
+            &args,
+            &project_path,
+            context,
+            true,  // Capture build output
+            theme,
+        )?;
+
+        let result = BuildResult::from_output(output, duration, termination);
+
+        if result.success {
+            self.logger.log(LogLevel::Info, &format!("Build '{}' completed successfully in {:.2?}{}", configuration.name, duration, result.summary_suffix()), Some(context), theme);
+        } else {
+            self.logger.log(LogLevel::Error, &format!("Build '{}' failed after {:.2?}{}", configuration.name, duration, result.summary_suffix()), Some(context), theme);
+        }
+
+        self.record_history(
+            HistoryEntry::new(project_name, "build", configuration.profile.as_deref().unwrap_or("dev"), result.success, duration, result.count(DiagnosticLevel::Warning)),
+            &project_path, context, theme,
+        );
+
+        Ok(result)
     }
 
     pub fn check_project(&self, project_name: &str, theme: &ThemeConfig) -> Result<BuildResult, String> {
         let context = &format!("check:{}", project_name);
-        self.logger.log("INFO", &format!("Checking project: {}", project_name), Some(context), theme);
+        let _operation = logger::begin_operation();
+        self.logger.log(LogLevel::Info, &format!("Checking project: {}", project_name), Some(context), theme);
         
         let project_path = self.validate_project_exists(project_name, context, theme)?;
 
-        let (output, duration) = self.execute_command(
+        let (output, duration, termination) = self.execute_command(
             "cargo",
-            &["check"],
+            &["check", "--message-format=json"],
             &project_path,
             context,
             true,  // Capture check output
             theme,
         )?;
 
-        let success = output.status.success();
-        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+        let result = BuildResult::from_output(output, duration, termination);
 
-        if success {
-            self.logger.log("INFO", &format!("Check completed successfully in {:.2?}", duration), Some(context), theme);
+        if result.success {
+            self.logger.log(LogLevel::Info, &format!("Check completed successfully in {:.2?}{}", duration, result.summary_suffix()), Some(context), theme);
         } else {
-            self.logger.log("ERROR", &format!("Check failed after {:.2?}", duration), Some(context), theme);
+            self.logger.log(LogLevel::Error, &format!("Check failed after {:.2?}{}", duration, result.summary_suffix()), Some(context), theme);
         }
 
-        Ok(BuildResult {
-            success,
-            stdout,
-            stderr,
-            duration,
-        })
+        self.record_history(
+            HistoryEntry::new(project_name, "check", "dev", result.success, duration, result.count(DiagnosticLevel::Warning)),
+            &project_path, context, theme,
+        );
+
+        Ok(result)
     }
 
     pub fn run_project(&self, project_name: &str, theme: &ThemeConfig) -> Result<RunResult, String> {
         let context = &format!("run:{}", project_name);
-        self.logger.log("INFO", &format!("Running project: {}", project_name), Some(context), theme);
+        let _operation = logger::begin_operation();
+        self.logger.log(LogLevel::Info, &format!("Running project: {}", project_name), Some(context), theme);
         
         let project_path = self.validate_project_exists(project_name, context, theme)?;
 
-        let (output, duration) = self.execute_command(
+        let (output, duration, termination) = self.execute_command(
             "cargo",
             &["run"],
             &project_path,
@@ -356,36 +480,64 @@ impl BuildSystem {
             theme,
         )?;
 
-        let success = output.status.success();
+        let success = output.status.success() && termination == Termination::Exited;
         let stdout = String::from_utf8_lossy(&output.stdout).to_string();
         let stderr = String::from_utf8_lossy(&output.stderr).to_string();
 
-        self.logger.log("INFO", &format!("Execution completed in {:.2?} with exit code: {}", 
+        self.logger.log(LogLevel::Info, &format!("Execution completed in {:.2?} with exit code: {}", 
             duration, output.status.code().unwrap_or(-1)), Some(context), theme);
 
+        self.record_history(HistoryEntry::new(project_name, "run", "dev", success, duration, 0), &project_path, context, theme);
+
         Ok(RunResult {
             success,
             stdout,
             stderr,
             exit_code: output.status.code().unwrap_or(-1),
             duration,
+            termination,
+        })
+    }
+
+    /// Starts `cargo run` attached to the terminal's stdin and output without waiting for it, for
+    /// callers that supervise the program themselves (watch mode restarts it on changes). In JSON
+    /// mode the program's stdout goes to stderr so it cannot interleave with the JSON documents.
+    pub fn spawn_run(&self, project_name: &str, theme: &ThemeConfig) -> Result<Child, String> {
+        let context = &format!("run:{}", project_name);
+        self.logger.log(LogLevel::Info, &format!("Starting project: {}", project_name), Some(context), theme);
+
+        let project_path = self.validate_project_exists(project_name, context, theme)?;
+        let mut cmd = Command::new("cargo");
+        cmd.arg("run")
+            .current_dir(&project_path)
+            .env("RUST_BACKTRACE", "1")
+            .stdin(Stdio::inherit());
+        if crate::output::is_json() {
+            cmd.stdout(std::io::stderr());
+        }
+
+        cmd.spawn().map_err(|e| {
+            let error_msg = format!("Failed to execute cargo run: {}", e);
+            self.logger.log_error_with_backtrace(&error_msg, Some(context), theme);
+            error_msg
         })
     }
 
     pub fn run_with_args(&self, project_name: &str, args: &[String], theme: &ThemeConfig) -> Result<RunResult, String> {
         let context = &format!("run-with-args:{}", project_name);
-        self.logger.log("INFO", &format!("Running project with arguments: {}", project_name), Some(context), theme);
-        self.logger.log("DEBUG", &format!("Arguments: {:?}", args), Some(context), theme);
+        let _operation = logger::begin_operation();
+        self.logger.log(LogLevel::Info, &format!("Running project with arguments: {}", project_name), Some(context), theme);
+        self.logger.log(LogLevel::Debug, &format!("Arguments: {:?}", args), Some(context), theme);
         
         let project_path = self.validate_project_exists(project_name, context, theme)?;
 
-        // Build command with dynamic args
-        let mut cmd_args = vec!["run"];
+        // Everything after `--` goes to the program, not to cargo
+        let mut cmd_args = vec!["run", "--"];
         for arg in args {
             cmd_args.push(arg);
         }
 
-        let (output, duration) = self.execute_command(
+        let (output, duration, termination) = self.execute_command(
             "cargo",
             &cmd_args,
             &project_path,
@@ -394,29 +546,86 @@ impl BuildSystem {
             theme,
         )?;
 
-        let success = output.status.success();
+        let success = output.status.success() && termination == Termination::Exited;
         let stdout = String::from_utf8_lossy(&output.stdout).to_string();
         let stderr = String::from_utf8_lossy(&output.stderr).to_string();
 
-        self.logger.log("INFO", &format!("Execution with args completed in {:.2?} with exit code: {}", 
+        self.logger.log(LogLevel::Info, &format!("Execution with args completed in {:.2?} with exit code: {}", 
             duration, output.status.code().unwrap_or(-1)), Some(context), theme);
 
+        self.record_history(HistoryEntry::new(project_name, "run", "dev", success, duration, 0), &project_path, context, theme);
+
+        Ok(RunResult {
+            success,
+            stdout,
+            stderr,
+            exit_code: output.status.code().unwrap_or(-1),
+            duration,
+            termination,
+        })
+    }
+
+    /// `cargo run` with a saved run configuration's arguments, environment, stdin and working
+    /// directory. `extra_args` are passed to the program after the configured ones.
+    pub fn run_with_configuration(&self, project_name: &str, configuration: &RunConfiguration, extra_args: &[String], theme: &ThemeConfig) -> Result<RunResult, String> {
+        let context = &format!("run[{}]:{}", configuration.name, project_name);
+        let _operation = logger::begin_operation();
+        self.logger.log(LogLevel::Info, &format!("Running '{}' for project: {}", configuration.name, project_name), Some(context), theme);
+
+        configuration.validate()
+            .inspect_err(|e| self.logger.log(LogLevel::Error, e, Some(context), theme))?;
+        if configuration.is_python() {
+            let error_msg = format!("Run configuration '{}' runs a Python script, not cargo", configuration.name);
+            self.logger.log(LogLevel::Error, &error_msg, Some(context), theme);
+            return Err(error_msg);
+        }
+        let project_path = self.validate_project_exists(project_name, context, theme)?;
+
+        // The manifest path lets the program run from a working directory outside the project root
+        let mut cmd = Command::new("cargo");
+        cmd.arg("run")
+           .arg("--manifest-path")
+           .arg(project_path.join("Cargo.toml"))
+           .arg("--")
+           .args(&configuration.args)
+           .args(extra_args);
+        configuration.apply(&mut cmd, &project_path)
+            .inspect_err(|e| self.logger.log(LogLevel::Error, e, Some(context), theme))?;
+
+        let (output, duration, termination) = self.execute_prepared(
+            &mut cmd,
+            context,
+            true,  // Capture run output
+            theme,
+        )?;
+
+        let success = output.status.success() && termination == Termination::Exited;
+        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+
+        self.logger.log(LogLevel::Info, &format!("Run '{}' completed in {:.2?} with exit code: {}",
+            configuration.name, duration, output.status.code().unwrap_or(-1)), Some(context), theme);
+
+        self.record_history(HistoryEntry::new(project_name, "run", "dev", success, duration, 0), &project_path, context, theme);
+
         Ok(RunResult {
             success,
             stdout,
             stderr,
             exit_code: output.status.code().unwrap_or(-1),
             duration,
+            termination,
         })
     }
 
     pub fn clean_project(&self, project_name: &str, theme: &ThemeConfig) -> Result<(), String> {
         let context = &format!("clean:{}", project_name);
-        self.logger.log("INFO", &format!("Cleaning project: {}", project_name), Some(context), theme);
+        let _operation = logger::begin_operation();
+        self.logger.log(LogLevel::Info, &format!("Cleaning project: {}", project_name), Some(context), theme);
         
         let project_path = self.validate_project_exists(project_name, context, theme)?;
 
-        let (output, duration) = self.execute_command(
+        let (output, duration, termination) = self.execute_command(
             "cargo",
             &["clean"],
             &project_path,
@@ -425,8 +634,10 @@ impl BuildSystem {
             theme,
         )?;
 
-        if output.status.success() {
-            self.logger.log("INFO", &format!("Project cleaned successfully in {:.2?}", duration), Some(context), theme);
+        if let Some(reason) = termination.interruption() {
+            Err(format!("Clean {}", reason))
+        } else if output.status.success() {
+            self.logger.log(LogLevel::Info, &format!("Project cleaned successfully in {:.2?}", duration), Some(context), theme);
             Ok(())
         } else {
             let error_msg = "Failed to clean project".to_string();
@@ -436,61 +647,275 @@ impl BuildSystem {
     }
 
     pub fn test_project(&self, project_name: &str, theme: &ThemeConfig) -> Result<TestResult, String> {
+        self.test_with_args(project_name, &[], theme)
+    }
+
+    /// Runs `cargo test --no-fail-fast -- <test_args>`, e.g. a name filter or `--exact` test names,
+    /// and records per-test outcomes in the project's test history
+    pub fn test_with_args(&self, project_name: &str, test_args: &[String], theme: &ThemeConfig) -> Result<TestResult, String> {
         let context = &format!("test:{}", project_name);
-        self.logger.log("INFO", &format!("Running tests for: {}", project_name), Some(context), theme);
+        let _operation = logger::begin_operation();
+        self.logger.log(LogLevel::Info, &format!("Running tests for: {}", project_name), Some(context), theme);
         
         let project_path = self.validate_project_exists(project_name, context, theme)?;
 
-        let (output, duration) = self.execute_command(
+        // Without --no-fail-fast cargo stops at the first failing test binary
+        let mut args = vec!["test", "--no-fail-fast"];
+        if !test_args.is_empty() {
+            args.push("--");
+            args.extend(test_args.iter().map(|a| a.as_str()));
+        }
+
+        let (output, duration, termination) = self.execute_command(
             "cargo",
-            &["test"],
+            &args,
             &project_path,
             context,
             true,  // Capture test output
             theme,
         )?;
 
-        let success = output.status.success();
+        let success = output.status.success() && termination == Termination::Exited;
         let stdout = String::from_utf8_lossy(&output.stdout).to_string();
         let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+        let cases = test_report::parse_test_output(&stdout);
+
+        let mut history = TestHistory::load(&project_path);
+        if !cases.is_empty() {
+            history.record(&cases);
+            if let Err(e) = history.save(&project_path) {
+                self.logger.log(LogLevel::Warn, &format!("Could not save test history: {}", e), Some(context), theme);
+            }
+        }
+        let flaky = cases.iter()
+            .filter(|c| history.is_flaky(&c.name))
+            .map(|c| c.name.clone())
+            .collect();
 
         if success {
-            self.logger.log("INFO", &format!("Tests completed successfully in {:.2?}", duration), Some(context), theme);
+            self.logger.log(LogLevel::Info, &format!("Tests completed successfully in {:.2?}", duration), Some(context), theme);
         } else {
-            self.logger.log("ERROR", &format!("Tests failed after {:.2?}", duration), Some(context), theme);
+            self.logger.log(LogLevel::Error, &format!("Tests failed after {:.2?}", duration), Some(context), theme);
         }
 
+        self.record_history(HistoryEntry::new(project_name, "test", "dev", success, duration, 0), &project_path, context, theme);
+
         Ok(TestResult {
             success,
             stdout,
             stderr,
             duration,
+            termination,
+            cases,
+            flaky,
+        })
+    }
+
+    /// Runs `cargo build --timings` and parses the HTML report cargo writes into per-crate
+    /// compile times, the critical path and the frontend/codegen split
+    pub fn build_with_timings(&self, project_name: &str, release: bool, theme: &ThemeConfig) -> Result<TimingsBuild, String> {
+        let context = &format!("timings:{}", project_name);
+        let _operation = logger::begin_operation();
+        self.logger.log(LogLevel::Info, &format!("Starting timed build for project: {}", project_name), Some(context), theme);
+
+        let project_path = self.validate_project_exists(project_name, context, theme)?;
+        let mut args = vec!["build", "--timings", "--message-format=json"];
+        if release {
+            args.push("--release");
+        }
+
+        let started = SystemTime::now();
+        let (output, duration, termination) = self.execute_command("cargo", &args, &project_path, context, true, theme)?;
+        let build = BuildResult::from_output(output, duration, termination);
+
+        // A report older than this build belongs to an earlier run
+        let report_path = project_path.join("target").join(timings_report::REPORT_PATH);
+        let fresh = std::fs::metadata(&report_path)
+            .and_then(|m| m.modified())
+            .is_ok_and(|modified| modified >= started);
+        let timings = if fresh {
+            std::fs::read_to_string(&report_path)
+                .map_err(|e| format!("Failed to read {}: {}", report_path.display(), e))
+                .and_then(|html| TimingsReport::from_html(&html, &report_path))
+                .inspect_err(|e| self.logger.log(LogLevel::Warn, e, Some(context), theme))
+                .ok()
+        } else {
+            self.logger.log(LogLevel::Warn, "Cargo did not write a timings report", Some(context), theme);
+            None
+        };
+
+        match &timings {
+            Some(report) => self.logger.log(LogLevel::Info, &format!("Timed build finished in {:.2?}: {} units, critical path {:.2}s",
+                duration, report.units, report.critical_path_seconds()), Some(context), theme),
+            None => self.logger.log(LogLevel::Info, &format!("Timed build finished in {:.2?}", duration), Some(context), theme),
+        }
+
+        let mut entry = HistoryEntry::new(project_name, "timings", if release { "release" } else { "dev" },
+            build.success, duration, build.count(DiagnosticLevel::Warning));
+        entry.timings = timings.as_ref().filter(|report| report.compiled()).map(TimingsReport::summary);
+        self.record_history(entry, &project_path, context, theme);
+
+        Ok(TimingsBuild { build, timings })
+    }
+
+    /// Runs `cargo bench`, passing `bench_args` (e.g. a name filter) to the bench harness
+    pub fn bench(&self, project_name: &str, bench_args: &[String], theme: &ThemeConfig) -> Result<BenchResult, String> {
+        let context = &format!("bench:{}", project_name);
+        let _operation = logger::begin_operation();
+        self.logger.log(LogLevel::Info, &format!("Running benchmarks for: {}", project_name), Some(context), theme);
+
+        let project_path = self.validate_project_exists(project_name, context, theme)?;
+
+        let mut args = vec!["bench"];
+        if !bench_args.is_empty() {
+            args.push("--");
+            args.extend(bench_args.iter().map(|a| a.as_str()));
+        }
+
+        let (output, duration, termination) = self.execute_command(
+            "cargo",
+            &args,
+            &project_path,
+            context,
+            true,  // Capture benchmark output
+            theme,
+        )?;
+
+        let success = output.status.success() && termination == Termination::Exited;
+        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+        let benchmarks = bench_report::parse_bench_output(&stdout);
+
+        if success {
+            self.logger.log(LogLevel::Info, &format!("{} benchmarks completed in {:.2?}", benchmarks.len(), duration), Some(context), theme);
+        } else {
+            self.logger.log(LogLevel::Error, &format!("Benchmarks failed after {:.2?}", duration), Some(context), theme);
+        }
+
+        self.record_history(HistoryEntry::new(project_name, "bench", "bench", success, duration, 0), &project_path, context, theme);
+
+        Ok(BenchResult {
+            success,
+            stdout,
+            stderr,
+            duration,
+            termination,
+            benchmarks,
         })
     }
 
     pub fn get_build_info(&self, project_name: &str, theme: &ThemeConfig) -> Result<BuildInfo, String> {
         let context = &format!("build-info:{}", project_name);
-        self.logger.log("INFO", &format!("Getting build info for: {}", project_name), Some(context), theme);
+        let _operation = logger::begin_operation();
+        self.logger.log(LogLevel::Info, &format!("Getting build info for: {}", project_name), Some(context), theme);
         
         let project_path = self.validate_project_exists(project_name, context, theme)?;
-        let debug_binary = project_path.join("target").join("debug").join(project_name);
-        let release_binary = project_path.join("target").join("release").join(project_name);
-
-        self.logger.log("DEBUG", &format!("Debug binary path: {}", debug_binary.display()), Some(context), theme);
-        self.logger.log("DEBUG", &format!("Release binary path: {}", release_binary.display()), Some(context), theme);
+        let names = binary_names(&project_path, project_name);
+        let binary_file = format!("{}{}", names[0], std::env::consts::EXE_SUFFIX);
+        let debug_binary = project_path.join("target").join("debug").join(&binary_file);
+        let release_binary = project_path.join("target").join("release").join(&binary_file);
+        let release_binaries: Vec<PathBuf> = names.iter()
+            .map(|name| project_path.join("target").join("release").join(format!("{}{}", name, std::env::consts::EXE_SUFFIX)))
+            .filter(|path| path.is_file())
+            .collect();
+
+        self.logger.log(LogLevel::Debug, &format!("Debug binary path: {}", debug_binary.display()), Some(context), theme);
+        self.logger.log(LogLevel::Debug, &format!("Release binary path: {}", release_binary.display()), Some(context), theme);
 
         Ok(BuildInfo {
             debug_exists: debug_binary.exists(),
             release_exists: release_binary.exists(),
             debug_path: debug_binary,
             release_path: release_binary,
+            release_binaries,
         })
     }
 
+    /// Section, crate and symbol sizes of the debug and release binaries, compared with each
+    /// other and with the previous release build. The release build is recorded for next time.
+    pub fn binary_size_report(&self, project_name: &str, top_symbols: usize, theme: &ThemeConfig) -> Result<BinarySizeReport, String> {
+        let context = &format!("size:{}", project_name);
+        let _operation = logger::begin_operation();
+        let build_info = self.get_build_info(project_name, theme)?;
+        if !build_info.debug_exists && !build_info.release_exists {
+            return Err("No debug or release binary found - build the project first".to_string());
+        }
+
+        let analyze = |path: &Path| SizeReport::analyze(path, top_symbols)
+            .inspect_err(|e| self.logger.log(LogLevel::Error, e, Some(context), theme));
+        let debug = if build_info.debug_exists { Some(analyze(&build_info.debug_path)?) } else { None };
+        let release = if build_info.release_exists { Some(analyze(&build_info.release_path)?) } else { None };
+
+        let project_path = self.get_project_path(project_name);
+        let commit = build_history::git_commit(&project_path);
+        let debug_snapshot = debug.as_ref().map(|report| report.snapshot(commit.clone()));
+        let release_snapshot = release.as_ref().map(|report| report.snapshot(commit));
+
+        let debug_vs_release = match (&debug_snapshot, &release_snapshot) {
+            (Some(debug), Some(release)) => Some(SizeComparison::new("debug", debug, "release", release)),
+            _ => None,
+        };
+
+        let mut since_previous_release = None;
+        let mut previous_release = None;
+        if let Some(current) = release_snapshot {
+            let mut history = SizeHistory::load(&project_path);
+            if let Some(previous) = history.previous(&current) {
+                let label = format!("previous release ({})", previous.commit.as_deref().unwrap_or(&previous.timestamp));
+                since_previous_release = Some(SizeComparison::new(&label, previous, "release", &current));
+                previous_release = Some(previous.timestamp.clone());
+            }
+            // Release builds made outside the terminal are picked up here
+            if history.record(current) {
+                if let Err(e) = history.save(&project_path) {
+                    self.logger.log(LogLevel::Warn, &format!("Could not record binary size: {}", e), Some(context), theme);
+                }
+            }
+        }
+
+        Ok(BinarySizeReport { debug, release, debug_vs_release, since_previous_release, previous_release })
+    }
+
+    // Remembers the sizes of a fresh release build so the next one can be compared with it
+    fn record_release_size(&self, project_name: &str, project_path: &Path, context: &str, theme: &ThemeConfig) {
+        let release_path = match self.get_build_info(project_name, theme) {
+            Ok(info) if info.release_exists => info.release_path,
+            _ => return,
+        };
+        let recorded = SizeReport::analyze(&release_path, 0).and_then(|report| {
+            let mut history = SizeHistory::load(project_path);
+            if history.record(report.snapshot(build_history::git_commit(project_path))) {
+                history.save(project_path)?;
+            }
+            Ok(())
+        });
+        if let Err(e) = recorded {
+            self.logger.log(LogLevel::Warn, &format!("Could not record binary size: {}", e), Some(context), theme);
+        }
+    }
+
+    /// Bundles the release binaries, README/LICENSE and configured extra files into a versioned
+    /// archive with a SHA-256 checksum file. `format` overrides the project's configured format.
+    pub fn package_release(&self, project_name: &str, format: Option<ArchiveFormat>, theme: &ThemeConfig) -> Result<PackageResult, String> {
+        let context = &format!("package:{}", project_name);
+        let _operation = logger::begin_operation();
+        self.logger.log(LogLevel::Info, &format!("Packaging release for: {}", project_name), Some(context), theme);
+
+        let build_info = self.get_build_info(project_name, theme)?;
+        let result = ReleasePackager::new_with_config(self.config.clone())
+            .package(project_name, &build_info, format)
+            .inspect_err(|e| self.logger.log(LogLevel::Error, e, Some(context), theme))?;
+
+        self.logger.log(LogLevel::Info, &format!("Packaged {} ({} files, {} bytes, sha256 {})",
+            result.archive_path.display(), result.files.len(), result.size_bytes, result.sha256), Some(context), theme);
+        Ok(result)
+    }
+
     // Debug method to test backtrace logging
     pub fn test_backtrace_logging(&self, theme: &ThemeConfig) -> Result<(), String> {
         let context = "backtrace-test";
-        self.logger.log("INFO", "Testing backtrace logging...", Some(context), theme);
+        let _operation = logger::begin_operation();
+        self.logger.log(LogLevel::Info, "Testing backtrace logging...", Some(context), theme);
         
         // Force an error to demonstrate backtrace
         std::fs::read_to_string("THIS_FILE_DOES_NOT_EXIST_12345.txt")
@@ -510,35 +935,14 @@ impl BuildSystem {
         }
 
         AnsiTheme::print_info(&format!("Opening log file: {}\n", log_path.display()), theme);
-        
-        #[cfg(target_os = "windows")]
-        let status = std::process::Command::new("notepad.exe")
-            .arg(&log_path)
-            .status();
-
-        #[cfg(target_family = "unix")]
-        let status = std::process::Command::new("xdg-open")
-            .arg(&log_path)
-            .status();
-
-        #[cfg(target_os = "macos")]
-        let status = std::process::Command::new("open")
-            .arg(&log_path)
-            .status();
-
-        match status {
-            Ok(status) if status.success() => {
-                AnsiTheme::print_success(" Log file opened successfully\n", theme);
-                Ok(())
-            }
-            Ok(_) => Err("Failed to open log file (editor exited with error)".to_string()),
-            Err(e) => Err(format!("Failed to open log file: {}", e)),
-        }
+
+        crate::opener::open_file(&log_path, None, &self.config)
+            .map_err(|e| format!("Failed to open log file: {}", e))
     }
 
     pub fn get_log_file_path(&self) -> std::path::PathBuf {
-        if let Some(ref log_path) = self.logger.log_file {
-            std::path::PathBuf::from(log_path)
+        if let Some(log_path) = self.logger.log_file() {
+            log_path.to_path_buf()
         } else {
             std::path::PathBuf::from("build_tool.log")
         }
@@ -571,38 +975,367 @@ impl BuildSystem {
             AnsiTheme::print_warning("Status: Not created yet\n", theme);
             AnsiTheme::print_themed("Run a build operation to create the log file\n", theme);
         }
+
+        AnsiTheme::print_themed(&format!("Settings: {}\n", self.config.logging.describe()), theme);
+        let rotated = logger::rotated_files(&log_path);
+        if !rotated.is_empty() {
+            AnsiTheme::print_themed(&format!("Rotated files ({}):\n", rotated.len()), theme);
+            for path in rotated.iter().rev() {
+                let size = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+                AnsiTheme::print_themed(&format!("  {} ({} bytes)\n", path.display(), size), theme);
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum OutputStream {
+    Stdout,
+    Stderr,
+}
+
+impl OutputStream {
+    fn label(&self) -> &'static str {
+        match self {
+            OutputStream::Stdout => "stdout",
+            OutputStream::Stderr => "stderr",
+        }
     }
 }
 
-#[derive(Debug)]
+// Forwards each line of a child pipe to the channel. Carriage returns also end a line so
+// cargo's redrawn progress bar arrives as separate updates.
+fn spawn_line_reader<R: Read + Send + 'static>(pipe: R, stream: OutputStream, sender: Sender<(OutputStream, String)>) -> JoinHandle<()> {
+    thread::spawn(move || {
+        let mut reader = BufReader::new(pipe);
+        let mut buffer = Vec::new();
+        while let Ok(read) = reader.read_until(b'\n', &mut buffer) {
+            if read == 0 {
+                break;
+            }
+            let text = String::from_utf8_lossy(&buffer);
+            let line = text.trim_end_matches(['\n', '\r']);
+            let segments: Vec<&str> = line.split('\r').filter(|s| !s.trim().is_empty()).collect();
+            if segments.is_empty() {
+                let _ = sender.send((stream, String::new()));
+            }
+            for segment in segments {
+                if sender.send((stream, segment.to_string())).is_err() {
+                    return;
+                }
+            }
+            buffer.clear();
+        }
+    })
+}
+
+/// One update of cargo's progress bar, e.g. `Building [====>   ] 12/50: serde, toml`
+#[derive(Debug, Clone, PartialEq)]
+pub struct BuildProgress {
+    pub current: usize,
+    pub total: usize,
+    pub units: String,
+}
+
+impl BuildProgress {
+    pub fn parse(line: &str) -> Option<Self> {
+        let rest = line.trim_start().strip_prefix("Building [")?;
+        let (_, rest) = rest.split_once("] ")?;
+        let (counts, units) = rest.split_once(':').unwrap_or((rest, ""));
+        let (current, total) = counts.trim().split_once('/')?;
+
+        Some(BuildProgress {
+            current: current.parse().ok()?,
+            total: total.parse().ok()?,
+            units: units.trim().to_string(),
+        })
+    }
+}
+
+impl std::fmt::Display for BuildProgress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Compiling ({}/{}) {}", self.current, self.total, self.units)
+    }
+}
+
+#[derive(Debug, Serialize)]
 pub struct BuildResult {
     pub success: bool,
     pub stdout: String,
     pub stderr: String,
+    #[serde(rename = "duration_ms", serialize_with = "crate::output::duration_ms")]
     pub duration: std::time::Duration,
+    /// Whether the process exited by itself, hit its timeout or was cancelled with Ctrl-C
+    pub termination: Termination,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl BuildResult {
+    // Parses any --message-format=json lines out of stdout into diagnostics
+    fn from_output(output: Output, duration: std::time::Duration, termination: Termination) -> Self {
+        let (diagnostics, stdout) = diagnostics::parse_cargo_output(&String::from_utf8_lossy(&output.stdout));
+        BuildResult {
+            success: output.status.success() && termination == Termination::Exited,
+            stdout,
+            stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+            duration,
+            termination,
+            diagnostics,
+        }
+    }
+
+    pub fn count(&self, level: DiagnosticLevel) -> usize {
+        self.diagnostics.iter().filter(|d| d.level == level).count()
+    }
+
+    // e.g. " (2 errors, 5 warnings)", empty when there are no diagnostics
+    fn summary_suffix(&self) -> String {
+        if self.diagnostics.is_empty() {
+            String::new()
+        } else {
+            format!(" ({} errors, {} warnings)", self.count(DiagnosticLevel::Error), self.count(DiagnosticLevel::Warning))
+        }
+    }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct RunResult {
     pub success: bool,
     pub stdout: String,
     pub stderr: String,
     pub exit_code: i32,
+    #[serde(rename = "duration_ms", serialize_with = "crate::output::duration_ms")]
     pub duration: std::time::Duration,
+    /// Whether the process exited by itself, hit its timeout or was cancelled with Ctrl-C
+    pub termination: Termination,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct TestResult {
     pub success: bool,
     pub stdout: String,
     pub stderr: String,
+    #[serde(rename = "duration_ms", serialize_with = "crate::output::duration_ms")]
+    pub duration: std::time::Duration,
+    /// Whether the process exited by itself, hit its timeout or was cancelled with Ctrl-C
+    pub termination: Termination,
+    pub cases: Vec<TestCase>,
+    /// Names of tests whose recent history flips between pass and fail
+    pub flaky: Vec<String>,
+}
+
+impl TestResult {
+    pub fn count(&self, status: TestStatus) -> usize {
+        self.cases.iter().filter(|c| c.status == status).count()
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct BenchResult {
+    pub success: bool,
+    pub stdout: String,
+    pub stderr: String,
+    #[serde(rename = "duration_ms", serialize_with = "crate::output::duration_ms")]
     pub duration: std::time::Duration,
+    /// Whether the process exited by itself, hit its timeout or was cancelled with Ctrl-C
+    pub termination: Termination,
+    pub benchmarks: Vec<Benchmark>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
+pub struct TimingsBuild {
+    #[serde(flatten)]
+    pub build: BuildResult,
+    /// None when cargo wrote no report, e.g. because the build was cancelled
+    pub timings: Option<TimingsReport>,
+}
+
+#[derive(Debug, Serialize)]
 pub struct BuildInfo {
     pub debug_exists: bool,
     pub release_exists: bool,
     pub debug_path: PathBuf,
     pub release_path: PathBuf,
-}
\ No newline at end of file
+    /// Every binary target of the package that has a release build
+    pub release_binaries: Vec<PathBuf>,
+}
+
+// Binary target names the way cargo discovers them: the package's own binary when
+// src/main.rs exists, [[bin]] entries, then src/bin/*.rs and src/bin/*/main.rs.
+// The first name is the main binary; it falls back to the project directory name.
+fn binary_names(project_path: &Path, project_name: &str) -> Vec<String> {
+    let manifest: Option<toml::Value> = std::fs::read_to_string(project_path.join("Cargo.toml"))
+        .ok()
+        .and_then(|content| toml::from_str(&content).ok());
+    let package_name = manifest.as_ref()
+        .and_then(|m| m.get("package")?.get("name")?.as_str())
+        .unwrap_or(project_name)
+        .to_string();
+
+    let mut names = Vec::new();
+    if project_path.join("src").join("main.rs").exists() {
+        names.push(package_name.clone());
+    }
+    if let Some(bins) = manifest.as_ref().and_then(|m| m.get("bin")?.as_array()) {
+        names.extend(bins.iter().filter_map(|bin| bin.get("name")?.as_str().map(|n| n.to_string())));
+    }
+    if let Ok(entries) = std::fs::read_dir(project_path.join("src").join("bin")) {
+        let mut discovered: Vec<String> = entries
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter_map(|path| {
+                if path.extension().is_some_and(|ext| ext == "rs") {
+                    path.file_stem().map(|s| s.to_string_lossy().to_string())
+                } else if path.join("main.rs").exists() {
+                    path.file_name().map(|s| s.to_string_lossy().to_string())
+                } else {
+                    None
+                }
+            })
+            .collect();
+        discovered.sort();
+        names.extend(discovered);
+    }
+
+    let mut unique = Vec::new();
+    for name in names {
+        if !unique.contains(&name) {
+            unique.push(name);
+        }
+    }
+    if unique.is_empty() {
+        unique.push(package_name);
+    }
+    unique
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+    use std::fs;
+
+    // Scratch workspace holding one small cargo project
+    struct TempWorkspace {
+        root: TempDir,
+    }
+
+    impl TempWorkspace {
+        fn new(name: &str) -> Self {
+            let root = TempDir::new(name);
+            fs::create_dir_all(root.join("sample").join("src")).unwrap();
+
+            fs::write(
+                root.join("sample").join("Cargo.toml"),
+                "[package]\nname = \"sample\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[dependencies]\n",
+            ).unwrap();
+            fs::write(
+                root.join("sample").join("src").join("main.rs"),
+                r#"fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    println!("args: {}", args.join(","));
+    if args.iter().any(|a| a == "fail") {
+        std::process::exit(3);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn passes() {
+        assert_eq!(1 + 1, 2);
+    }
+}
+"#,
+            ).unwrap();
+
+            TempWorkspace { root }
+        }
+
+        fn build_system(&self) -> BuildSystem {
+            let mut build_system = BuildSystem::new_with_config(AppConfig {
+                workspace_path: self.root.display().to_string(),
+                log_file: self.root.join("build_tool.log").display().to_string(),
+                verbose_logging: false,
+                ..AppConfig::default()
+            });
+            build_system.history = BuildHistory::new(self.root.join("build_history.jsonl"));
+            build_system
+        }
+    }
+
+    #[test]
+    fn resolves_projects_from_configured_workspace() {
+        let workspace = TempWorkspace::new("resolve");
+        let build_system = workspace.build_system();
+        let theme = ThemeConfig::default();
+
+        let info = build_system.get_build_info("sample", &theme).unwrap();
+        assert!(info.debug_path.starts_with(&*workspace.root));
+        assert!(!info.debug_exists);
+    }
+
+    #[test]
+    fn parses_cargo_progress_bar() {
+        let progress = BuildProgress::parse("    Building [=====>      ] 12/50: serde, toml(build)   ").unwrap();
+        assert_eq!(progress.current, 12);
+        assert_eq!(progress.total, 50);
+        assert_eq!(progress.units, "serde, toml(build)");
+        assert_eq!(BuildProgress::parse("   Compiling serde v1.0.0"), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn chatty_children_still_time_out() {
+        let workspace = TempWorkspace::new("chatty");
+        let build_system = workspace.build_system();
+        let started = std::time::Instant::now();
+
+        let mut command = Command::new("sh");
+        command.args(["-c", "while true; do echo tick; sleep 0.01; done"]);
+        let (output, termination) = build_system
+            .stream_command(&mut command, "chatty", false, Some(Duration::from_millis(500)), &ThemeConfig::default())
+            .unwrap();
+
+        assert_eq!(termination, Termination::TimedOut);
+        assert!(String::from_utf8_lossy(&output.stdout).starts_with("tick\n"));
+        assert!(started.elapsed() < Duration::from_secs(10));
+    }
+
+    #[test]
+    fn missing_project_is_an_error() {
+        let workspace = TempWorkspace::new("missing");
+        let build_system = workspace.build_system();
+
+        let err = build_system.build_project("does_not_exist", &ThemeConfig::default()).unwrap_err();
+        assert!(err.contains("does not exist"));
+    }
+
+    #[test]
+    fn builds_runs_tests_and_cleans_project() {
+        let workspace = TempWorkspace::new("lifecycle");
+        let build_system = workspace.build_system();
+        let theme = ThemeConfig::default();
+
+        let build = build_system.build_project("sample", &theme).unwrap();
+        assert!(build.success, "build failed: {}", build.stderr);
+        assert!(workspace.root.join("sample").join("target").join("debug").exists());
+
+        let run = build_system.run_with_args("sample", &["a".to_string(), "--port".to_string(), "8080".to_string()], &theme).unwrap();
+        assert!(run.success, "run failed: {}", run.stderr);
+        assert!(run.stdout.contains("args: a,--port,8080"));
+
+        let failed = build_system.run_with_args("sample", &["fail".to_string()], &theme).unwrap();
+        assert!(!failed.success);
+        assert_eq!(failed.exit_code, 3);
+
+        let test = build_system.test_project("sample", &theme).unwrap();
+        assert!(test.success, "tests failed: {}", test.stderr);
+        assert!(test.stdout.contains("test tests::passes ... ok"));
+
+        let recorded: Vec<String> = build_system.history_entries("sample").into_iter().map(|e| e.command).collect();
+        assert_eq!(recorded, vec!["build", "run", "run", "test"]);
+
+        build_system.clean_project("sample", &theme).unwrap();
+        assert!(!workspace.root.join("sample").join("target").exists());
+    }
+}
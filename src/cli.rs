@@ -0,0 +1,1051 @@
+// cli.rs
+use serde::Serialize;
+use crate::ansi_theme::AnsiTheme;
+use crate::archive::ArchiveFormat;
+use crate::bench_report::{self, BaselineReport, BenchBaselines};
+use crate::build_history::{self, HistoryReport};
+use crate::build_system::{BenchResult, BuildSystem};
+use crate::config::{AppConfig, ThemeConfig};
+use crate::file_manager::FileManager;
+use crate::git_manager::GitManager;
+use crate::output;
+use crate::project_creator::ProjectCreator;
+use crate::project_settings::ProjectSettings;
+use crate::process::Termination;
+use crate::python_manager::PythonManager;
+use crate::self_update::{SelfUpdater, UpdateOptions, UpdateSource};
+use crate::test_report::{TestHistory, TestStatus};
+use crate::version::{Bump, Channel};
+use crate::watcher::{self, WatchAction};
+use std::path::PathBuf;
+use std::sync::atomic::AtomicBool;
+
+// Exit codes returned to the calling shell
+pub const EXIT_SUCCESS: i32 = 0;
+pub const EXIT_FAILURE: i32 = 1;
+pub const EXIT_USAGE: i32 = 2;
+// Same codes as timeout(1) and a shell interrupted by Ctrl-C
+pub const EXIT_TIMED_OUT: i32 = 124;
+pub const EXIT_CANCELLED: i32 = 130;
+
+pub enum CliError {
+    Usage(String),
+    Failed(String),
+}
+
+type CliResult = Result<i32, CliError>;
+
+const USAGE: &str = r#"Usage: rust_dev_terminal [--json] [COMMAND] [ARGS...]
+
+Run without a command to start the interactive menu.
+Pass --json to print one machine-readable JSON document per command
+instead of themed text.
+
+Project commands:
+  projects                                 List projects in the workspace
+  new <name> [--template basic|library|cli]
+                                           Create a new project
+  files <project>                          List Rust files in a project
+  version <project> [patch|minor|major|alpha|beta|rc|release|X.Y.Z[-PRE]]
+          [--build <metadata>]
+                                           Show or change the package version:
+                                           bump it, move to the next alpha/beta/rc
+                                           pre-release, promote a pre-release to
+                                           its release, or set +build metadata
+  add-dep <project> <name> <version>       Add a dependency to Cargo.toml
+
+Build commands:
+  build <project> [--release | --config <name>] [--timings]
+                                           Build a project, optionally with a saved
+                                           build configuration (see the Build menu).
+                                           --timings reports the slowest crates, the
+                                           critical path and frontend/codegen time
+  check <project>                          Fast compile check
+  run <project> [--config <name>] [-- ARGS...]
+                                           Run a project with optional arguments, or with
+                                           a saved run configuration (args, env, stdin,
+                                           working directory; cargo or Python)
+  test <project> [FILTER] [--failed] [-- ARGS...]
+                                           Run tests, optionally only those matching
+                                           FILTER or those that failed last time
+  clean <project>                          Remove build artifacts
+  package <project> [--format zip|tar.gz] [--build]
+                                           Package release binaries, README/LICENSE
+                                           and configured extra files into a versioned
+                                           archive with a SHA-256 checksum file
+  info <project>                           Show build artifact information
+  watch <project> [check|build|test|run]   Re-run an action whenever sources change
+                                           (default: check; stop with Ctrl-C). run keeps
+                                           the program running and restarts it on
+                                           changes. With --json, prints one compact
+                                           document per line
+  bench <project> [FILTER] [--baseline <name>] [--save <name>]
+        [--threshold <percent>] [-- ARGS...]
+                                           Run benchmarks, compare against a saved
+                                           baseline (exit 1 on regressions) and
+                                           optionally save the results as a baseline
+  history <project> [-n <count>]           Show build timing trends, regressions and
+                                           the last green build
+  size <project> [--top <count>]           Break the debug/release binaries down into
+                                           sections, crates and largest symbols, and
+                                           compare with the previous release build
+
+Git commands:
+  git init <project>                       Initialize a repository
+  git status <project>                     Show working tree status
+  git add <project> [FILES...]             Stage files (all changes if none given)
+  git commit <project> -m <message> [--all]
+                                           Commit staged changes
+  git push <project> [remote] [branch]     Push (defaults: origin main)
+  git pull <project> [remote] [branch]     Pull (defaults: origin main)
+  git log <project> [-n <count>]           Show recent commits
+  git tag <project> <name> [-m <message>]  Create a tag
+  git remotes <project>                    List remotes
+
+Python commands:
+  python run <project> <script> [ARGS...]  Run a Python script
+  python list <project>                    List Python files
+  python venv <project>                    Create a virtual environment
+  python install <project> <package>       Install a package with pip
+  python requirements <project>            Install requirements.txt
+
+Other:
+  self-update [--git <ref> | --dir <path>] [--dry-run]
+                                           Rebuild and install the terminal from the
+                                           working tree (bumping the patch version),
+                                           a git tag/branch/commit or another checkout;
+                                           --dry-run shows the version change, backup
+                                           location and build output only
+  self-update --rollback                   Swap back the executable kept by the last
+                                           update
+  help, -h, --help                         Show this help
+  -V, --version                            Show the terminal version
+"#;
+
+/// Strips global flags such as `--json` from the arguments and applies them.
+/// Must run before the config is loaded so nothing themed reaches stdout in JSON mode.
+pub fn apply_global_flags(args: &mut Vec<String>) {
+    let end = args.iter().position(|a| a == "--").unwrap_or(args.len());
+    if let Some(pos) = args[..end].iter().position(|a| a == "--json") {
+        args.remove(pos);
+        output::enable_json_mode();
+    }
+}
+
+/// Entry point for the non-interactive front end. Returns the process exit code.
+pub fn run(args: &[String], config: AppConfig) -> i32 {
+    let theme = config.theme.clone();
+    let label = command_label(args);
+
+    match dispatch(args, &config) {
+        Ok(code) => code,
+        Err(CliError::Usage(msg)) => {
+            if output::is_json() {
+                output::emit_error(&label, &msg);
+            }
+            AnsiTheme::print_error(&format!("Error: {}\n", msg), &theme);
+            AnsiTheme::print_themed("Run 'rust_dev_terminal help' for usage.\n", &theme);
+            EXIT_USAGE
+        }
+        Err(CliError::Failed(msg)) => {
+            if output::is_json() {
+                output::emit_error(&label, &msg);
+            }
+            AnsiTheme::print_error(&format!("Error: {}\n", msg), &theme);
+            EXIT_FAILURE
+        }
+    }
+}
+
+/// Name used for the `command` field of JSON documents, e.g. `build` or `git.commit`
+fn command_label(args: &[String]) -> String {
+    match args {
+        [group, sub, ..] if group == "git" || group == "python" => format!("{}.{}", group, sub),
+        [command, ..] => command.clone(),
+        [] => String::new(),
+    }
+}
+
+/// Emits `data` as a JSON document in JSON mode, otherwise renders it as themed text
+fn report<T: Serialize>(command: &str, success: bool, data: T, render: impl FnOnce(&T)) {
+    if output::is_json() {
+        output::emit_success(command, success, data);
+    } else {
+        render(&data);
+    }
+}
+
+#[derive(Serialize)]
+struct VersionInfo {
+    name: String,
+    version: String,
+}
+
+#[derive(Serialize)]
+struct ProjectVersion {
+    project: String,
+    version: String,
+}
+
+#[derive(Serialize)]
+struct Message {
+    message: String,
+}
+
+fn dispatch(args: &[String], config: &AppConfig) -> CliResult {
+    let theme = &config.theme;
+    let (command, rest) = match args.split_first() {
+        Some((command, rest)) => (command.as_str(), rest.to_vec()),
+        None => return Err(CliError::Usage("No command given".to_string())),
+    };
+
+    match command {
+        "help" | "-h" | "--help" => {
+            report("help", true, Message { message: USAGE.to_string() }, |m| {
+                AnsiTheme::print_themed(&m.message, theme);
+            });
+            Ok(EXIT_SUCCESS)
+        }
+        "-V" | "--version" => {
+            let info = VersionInfo {
+                name: env!("CARGO_PKG_NAME").to_string(),
+                version: env!("CARGO_PKG_VERSION").to_string(),
+            };
+            report("version", true, info, |info| {
+                AnsiTheme::print_themed(&format!("{} {}\n", info.name, info.version), theme);
+            });
+            Ok(EXIT_SUCCESS)
+        }
+        "projects" => {
+            reject_unknown(&rest)?;
+            let projects = crate::projects::find_projects(config).map_err(CliError::Failed)?;
+            report("projects", true, projects, |projects| {
+                for project in projects {
+                    AnsiTheme::print_themed(&format!("{}\n", project), theme);
+                }
+            });
+            Ok(EXIT_SUCCESS)
+        }
+        "new" => cmd_new(rest, config),
+        "files" => cmd_files(rest, config),
+        "version" => cmd_version(rest, config),
+        "add-dep" => cmd_add_dep(rest, config),
+        "build" | "check" | "run" | "test" | "clean" | "info" => cmd_build(command, rest, config),
+        "watch" => cmd_watch(rest, config),
+        "history" => cmd_history(rest, config),
+        "bench" => cmd_bench(rest, config),
+        "package" => cmd_package(rest, config),
+        "size" => cmd_size(rest, config),
+        "git" => cmd_git(rest, config),
+        "python" => cmd_python(rest, config),
+        "self-update" => cmd_self_update(rest, config),
+        other => Err(CliError::Usage(format!("Unknown command '{}'", other))),
+    }
+}
+
+// ---- Argument helpers ----
+
+/// Removes `flag` from `args`, returning whether it was present
+fn take_flag(args: &mut Vec<String>, flag: &str) -> bool {
+    if let Some(pos) = args.iter().position(|a| a == flag) {
+        args.remove(pos);
+        true
+    } else {
+        false
+    }
+}
+
+/// Removes every occurrence of any of `names` (e.g. `--all` and `-a`), returning whether one was present
+fn take_flags(args: &mut Vec<String>, names: &[&str]) -> bool {
+    let before = args.len();
+    args.retain(|a| !names.contains(&a.as_str()));
+    args.len() != before
+}
+
+/// Removes an option and its value (e.g. `-m "message"`) from `args`
+fn take_option(args: &mut Vec<String>, names: &[&str]) -> Result<Option<String>, CliError> {
+    if let Some(pos) = args.iter().position(|a| names.contains(&a.as_str())) {
+        if pos + 1 >= args.len() {
+            return Err(CliError::Usage(format!("Option '{}' requires a value", args[pos])));
+        }
+        let value = args.remove(pos + 1);
+        args.remove(pos);
+        Ok(Some(value))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Splits off everything after a `--` separator
+fn take_trailing(args: &mut Vec<String>) -> Vec<String> {
+    match args.iter().position(|a| a == "--") {
+        Some(pos) => {
+            let trailing = args.split_off(pos + 1);
+            args.pop();
+            trailing
+        }
+        None => Vec::new(),
+    }
+}
+
+fn take_project(args: &mut Vec<String>) -> Result<String, CliError> {
+    if args.is_empty() || args[0].starts_with('-') {
+        return Err(CliError::Usage("Missing project name".to_string()));
+    }
+    Ok(args.remove(0))
+}
+
+fn reject_unknown(args: &[String]) -> Result<(), CliError> {
+    match args.first() {
+        Some(arg) => Err(CliError::Usage(format!("Unexpected argument '{}'", arg))),
+        None => Ok(()),
+    }
+}
+
+fn exit_code_for(success: bool) -> i32 {
+    if success { EXIT_SUCCESS } else { EXIT_FAILURE }
+}
+
+/// Exit code for an operation that may have been stopped early
+fn outcome_exit_code(success: bool, termination: Termination) -> i32 {
+    match termination {
+        Termination::TimedOut => EXIT_TIMED_OUT,
+        Termination::Cancelled => EXIT_CANCELLED,
+        Termination::Exited => exit_code_for(success),
+    }
+}
+
+/// Exit code for a child program: its own code when it failed with one, otherwise a generic failure
+fn child_exit_code(success: bool, exit_code: i32, termination: Termination) -> i32 {
+    if termination != Termination::Exited {
+        outcome_exit_code(success, termination)
+    } else if success {
+        EXIT_SUCCESS
+    } else if exit_code > 0 {
+        exit_code
+    } else {
+        EXIT_FAILURE
+    }
+}
+
+// ---- Project commands ----
+
+fn cmd_new(mut args: Vec<String>, config: &AppConfig) -> CliResult {
+    let template = take_option(&mut args, &["-t", "--template"])?.unwrap_or_else(|| "basic".to_string());
+    let name = take_project(&mut args)?;
+    reject_unknown(&args)?;
+
+    if !name.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '-') {
+        return Err(CliError::Usage("Project name can only contain alphanumeric characters, underscores, and hyphens".to_string()));
+    }
+
+    let creator = ProjectCreator::new_with_config(config.clone());
+    creator.create_project_with_template(&name, &template, &config.theme)
+        .map_err(CliError::Failed)?;
+    let path = config.get_project_path(&name);
+    report("new", true, path, |_| {});
+    Ok(EXIT_SUCCESS)
+}
+
+fn cmd_files(mut args: Vec<String>, config: &AppConfig) -> CliResult {
+    let project = take_project(&mut args)?;
+    reject_unknown(&args)?;
+
+    let file_manager = FileManager::new_with_config(config.clone());
+    let files = file_manager.list_project_files(&project).map_err(CliError::Failed)?;
+    let src_path = config.get_src_path(&project);
+    let relative: Vec<String> = files.iter()
+        .map(|(_, path)| path.strip_prefix(&src_path).unwrap_or(path).display().to_string())
+        .collect();
+
+    report("files", true, relative, |files| {
+        for file in files {
+            AnsiTheme::print_themed(&format!("{}\n", file), &config.theme);
+        }
+    });
+    Ok(EXIT_SUCCESS)
+}
+
+fn cmd_version(mut args: Vec<String>, config: &AppConfig) -> CliResult {
+    let build = take_option(&mut args, &["--build"])?;
+    let project = take_project(&mut args)?;
+    let action = if args.is_empty() { None } else { Some(args.remove(0)) };
+    reject_unknown(&args)?;
+
+    let file_manager = FileManager::new_with_config(config.clone());
+    let bump = match action.as_deref() {
+        Some("patch") => Some(Bump::Patch),
+        Some("minor") => Some(Bump::Minor),
+        Some("major") => Some(Bump::Major),
+        Some("release") => Some(Bump::Release),
+        Some(channel @ ("alpha" | "beta" | "rc")) => Channel::parse(channel).map(Bump::Pre),
+        _ => None,
+    };
+    match (bump, action.as_deref()) {
+        (Some(bump), _) => { file_manager.bump_version(&project, bump).map_err(CliError::Failed)?; }
+        (None, Some(version)) => file_manager.set_version(&project, version).map_err(CliError::Failed)?,
+        (None, None) => {}
+    }
+    if let Some(build) = build {
+        file_manager.set_build_metadata(&project, &build).map_err(CliError::Failed)?;
+    }
+
+    let version = file_manager.read_cargo_toml(&project).map_err(CliError::Failed)?.package.version;
+    report("version", true, ProjectVersion { project, version }, |v| {
+        AnsiTheme::print_info(&format!(" Current version: {}\n", v.version), &config.theme);
+    });
+    Ok(EXIT_SUCCESS)
+}
+
+fn cmd_add_dep(mut args: Vec<String>, config: &AppConfig) -> CliResult {
+    let project = take_project(&mut args)?;
+    if args.len() != 2 {
+        return Err(CliError::Usage("Expected: add-dep <project> <name> <version>".to_string()));
+    }
+
+    let file_manager = FileManager::new_with_config(config.clone());
+    file_manager.add_dependency(&project, &args[0], &args[1]).map_err(CliError::Failed)?;
+    report("add-dep", true, Message { message: format!("Dependency '{}' added", args[0]) }, |_| {
+        AnsiTheme::print_success(&format!(" Dependency '{}' added successfully!\n", args[0]), &config.theme);
+    });
+    Ok(EXIT_SUCCESS)
+}
+
+// ---- Build commands ----
+
+fn cmd_build(command: &str, mut args: Vec<String>, config: &AppConfig) -> CliResult {
+    let theme = &config.theme;
+    let trailing = take_trailing(&mut args);
+    let release = take_flag(&mut args, "--release");
+    let timings = take_flag(&mut args, "--timings");
+    let failed_only = take_flag(&mut args, "--failed");
+    let configuration_name = take_option(&mut args, &["-c", "--config"])?;
+    let project = take_project(&mut args)?;
+    let filter = if command == "test" && !args.is_empty() && !args[0].starts_with('-') {
+        Some(args.remove(0))
+    } else {
+        None
+    };
+    reject_unknown(&args)?;
+    let configurable = (command == "build" && !release) || command == "run";
+    if configuration_name.is_some() && !configurable {
+        return Err(CliError::Usage("--config can only be used with build (without --release) or run".to_string()));
+    }
+    if timings && (command != "build" || configuration_name.is_some()) {
+        return Err(CliError::Usage("--timings can only be used with build, without --config".to_string()));
+    }
+    if failed_only && (command != "test" || filter.is_some()) {
+        return Err(CliError::Usage("--failed can only be used with test, without a filter".to_string()));
+    }
+
+    let build_system = BuildSystem::new_with_config(config.clone());
+
+    match command {
+        "build" if timings => {
+            let result = build_system.build_with_timings(&project, release, theme).map_err(CliError::Failed)?;
+            let (success, code) = (result.build.success, outcome_exit_code(result.build.success, result.build.termination));
+            report("build", success, result, |r| {
+                print_outcome(r.build.success, r.build.termination, "Build", r.build.duration, theme);
+                match &r.timings {
+                    Some(timings) => timings.print(10, theme),
+                    None => AnsiTheme::print_warning(" Cargo did not write a timings report\n", theme),
+                }
+            });
+            Ok(code)
+        }
+        "build" => {
+            let result = if let Some(name) = configuration_name {
+                let settings = ProjectSettings::load(&build_system.get_project_path(&project))
+                    .map_err(CliError::Failed)?;
+                let configuration = settings.build_configuration(&name)
+                    .ok_or_else(|| CliError::Failed(format!("No build configuration named '{}' in project '{}'", name, project)))?;
+                build_system.build_with_configuration(&project, configuration, theme)
+            } else if release {
+                build_system.build_release(&project, theme)
+            } else {
+                build_system.build_project(&project, theme)
+            }.map_err(CliError::Failed)?;
+            let (success, code) = (result.success, outcome_exit_code(result.success, result.termination));
+            report("build", success, result, |r| {
+                print_outcome(r.success, r.termination, "Build", r.duration, theme);
+            });
+            Ok(code)
+        }
+        "check" => {
+            let result = build_system.check_project(&project, theme).map_err(CliError::Failed)?;
+            let (success, code) = (result.success, outcome_exit_code(result.success, result.termination));
+            report("check", success, result, |r| {
+                print_outcome(r.success, r.termination, "Check", r.duration, theme);
+            });
+            Ok(code)
+        }
+        "run" => {
+            if let Some(name) = configuration_name {
+                return run_configuration(&project, &name, &trailing, config);
+            }
+            let result = if trailing.is_empty() {
+                build_system.run_project(&project, theme)
+            } else {
+                build_system.run_with_args(&project, &trailing, theme)
+            }.map_err(CliError::Failed)?;
+            let code = child_exit_code(result.success, result.exit_code, result.termination);
+            report("run", result.success, result, |r| {
+                match r.termination.interruption() {
+                    Some(reason) => AnsiTheme::print_error(&format!(" Run {} ({:.2?})\n", reason, r.duration), theme),
+                    None => AnsiTheme::print_blue(&format!(" Exit code: {} (Duration: {:.2?})\n", r.exit_code, r.duration), theme),
+                }
+            });
+            Ok(code)
+        }
+        "test" => {
+            let mut test_args: Vec<String> = filter.into_iter().collect();
+            if failed_only {
+                let failed = TestHistory::load(&build_system.get_project_path(&project)).last_failed;
+                if failed.is_empty() {
+                    return Err(CliError::Failed("No failed tests recorded for the last run".to_string()));
+                }
+                test_args.push("--exact".to_string());
+                test_args.extend(failed);
+            }
+            test_args.extend(trailing);
+
+            let result = build_system.test_with_args(&project, &test_args, theme).map_err(CliError::Failed)?;
+            let (success, code) = (result.success, outcome_exit_code(result.success, result.termination));
+            report("test", success, result, |r| {
+                for case in r.cases.iter().filter(|c| c.status == TestStatus::Failed) {
+                    AnsiTheme::print_error(&format!(" FAILED {}\n", case.name), theme);
+                }
+                for name in &r.flaky {
+                    AnsiTheme::print_warning(&format!(" flaky  {}\n", name), theme);
+                }
+                AnsiTheme::print_info(&format!(" {} passed, {} failed, {} ignored\n",
+                    r.count(TestStatus::Ok), r.count(TestStatus::Failed), r.count(TestStatus::Ignored)), theme);
+                print_outcome(r.success, r.termination, "Tests", r.duration, theme);
+            });
+            Ok(code)
+        }
+        "clean" => {
+            build_system.clean_project(&project, theme).map_err(CliError::Failed)?;
+            report("clean", true, Message { message: "Project cleaned".to_string() }, |_| {
+                AnsiTheme::print_success(" Project cleaned successfully\n", theme);
+            });
+            Ok(EXIT_SUCCESS)
+        }
+        "info" => {
+            let info = build_system.get_build_info(&project, theme).map_err(CliError::Failed)?;
+            report("info", true, info, |info| {
+                AnsiTheme::print_themed(&format!("Debug build: {}\n",
+                    if info.debug_exists { info.debug_path.display().to_string() } else { "not built".to_string() }), theme);
+                AnsiTheme::print_themed(&format!("Release build: {}\n",
+                    if info.release_exists { info.release_path.display().to_string() } else { "not built".to_string() }), theme);
+            });
+            Ok(EXIT_SUCCESS)
+        }
+        _ => unreachable!("cmd_build called with unsupported command"),
+    }
+}
+
+// `run --config <name>`: cargo run or a Python script, depending on the saved configuration
+fn run_configuration(project: &str, name: &str, extra_args: &[String], config: &AppConfig) -> CliResult {
+    let theme = &config.theme;
+    let build_system = BuildSystem::new_with_config(config.clone());
+    let settings = ProjectSettings::load(&build_system.get_project_path(project)).map_err(CliError::Failed)?;
+    let configuration = settings.run_configuration(name)
+        .ok_or_else(|| CliError::Failed(format!("No run configuration named '{}' in project '{}'", name, project)))?;
+
+    if configuration.is_python() {
+        let result = PythonManager::new_with_config(config.clone())
+            .run_with_configuration(project, configuration, extra_args, theme)
+            .map_err(CliError::Failed)?;
+        let code = child_exit_code(result.success, result.exit_code, result.termination);
+        report("run", result.success, result, |r| {
+            print_output(&r.stdout, &r.stderr, theme);
+        });
+        return Ok(code);
+    }
+
+    let result = build_system.run_with_configuration(project, configuration, extra_args, theme).map_err(CliError::Failed)?;
+    let code = child_exit_code(result.success, result.exit_code, result.termination);
+    report("run", result.success, result, |r| {
+        match r.termination.interruption() {
+            Some(reason) => AnsiTheme::print_error(&format!(" Run {} ({:.2?})\n", reason, r.duration), theme),
+            None => AnsiTheme::print_blue(&format!(" Exit code: {} (Duration: {:.2?})\n", r.exit_code, r.duration), theme),
+        }
+    });
+    Ok(code)
+}
+
+#[derive(Serialize)]
+struct WatchRun {
+    action: &'static str,
+    success: bool,
+}
+
+fn cmd_watch(mut args: Vec<String>, config: &AppConfig) -> CliResult {
+    let theme = &config.theme;
+    let project = take_project(&mut args)?;
+    let action = match args.first() {
+        Some(name) => WatchAction::parse(name)
+            .ok_or_else(|| CliError::Usage(format!("Unknown watch action '{}' (expected check, build, test or run)", name)))?,
+        None => WatchAction::Check,
+    };
+    if args.len() > 1 {
+        reject_unknown(&args[1..])?;
+    }
+
+    let build_system = BuildSystem::new_with_config(config.clone());
+    if !build_system.get_project_path(&project).exists() {
+        return Err(CliError::Failed(format!("Project '{}' does not exist!", project)));
+    }
+
+    // Runs until the process is interrupted, reporting once per run
+    output::enable_json_lines();
+    let stop = AtomicBool::new(false);
+    AnsiTheme::print_info(&format!(" Watching {} - running {} on changes. Press Ctrl-C to stop.\n", project, action.name()), theme);
+    watcher::watch_project(&build_system, &project, action, &stop, theme, |action, result| {
+        match result {
+            Ok(success) => report("watch", success, WatchRun { action: action.name(), success }, |run| {
+                if run.success {
+                    AnsiTheme::print_success(&format!(" {} passed - waiting for changes...\n", run.action), theme);
+                } else {
+                    AnsiTheme::print_error(&format!(" {} failed - waiting for changes...\n", run.action), theme);
+                }
+            }),
+            Err(e) => {
+                if output::is_json() {
+                    output::emit_error("watch", &e);
+                }
+                AnsiTheme::print_error(&format!("Error: {}\n", e), theme);
+            }
+        }
+    });
+    Ok(EXIT_SUCCESS)
+}
+
+fn cmd_history(mut args: Vec<String>, config: &AppConfig) -> CliResult {
+    let count = match take_option(&mut args, &["-n", "--count"])? {
+        Some(n) => n.parse::<usize>()
+            .map_err(|_| CliError::Usage(format!("Invalid count '{}'", n)))?,
+        None => 10,
+    };
+    let project = take_project(&mut args)?;
+    reject_unknown(&args)?;
+
+    let build_system = BuildSystem::new_with_config(config.clone());
+    let history = HistoryReport::new(&project, &build_system.history_entries(&project), count);
+    report("history", true, history, |history| history.print(&config.theme));
+    Ok(EXIT_SUCCESS)
+}
+
+fn cmd_size(mut args: Vec<String>, config: &AppConfig) -> CliResult {
+    let top = match take_option(&mut args, &["--top"])? {
+        Some(n) => n.parse::<usize>()
+            .map_err(|_| CliError::Usage(format!("Invalid symbol count '{}'", n)))?,
+        None => 10,
+    };
+    let project = take_project(&mut args)?;
+    reject_unknown(&args)?;
+
+    let build_system = BuildSystem::new_with_config(config.clone());
+    let sizes = build_system.binary_size_report(&project, top, &config.theme).map_err(CliError::Failed)?;
+    report("size", true, sizes, |sizes| sizes.print(top, &config.theme));
+    Ok(EXIT_SUCCESS)
+}
+
+#[derive(Serialize)]
+struct Rollback {
+    restored_path: PathBuf,
+}
+
+fn cmd_self_update(mut args: Vec<String>, config: &AppConfig) -> CliResult {
+    let updater = SelfUpdater::new_with_config(config.clone());
+    if take_flag(&mut args, "--rollback") {
+        reject_unknown(&args)?;
+        let restored_path = updater.rollback_executable(&config.theme).map_err(CliError::Failed)?;
+        report("self-update", true, Rollback { restored_path }, |r| {
+            AnsiTheme::print_success(&format!(" Previous executable restored as {}\n", r.restored_path.display()), &config.theme);
+        });
+        return Ok(EXIT_SUCCESS);
+    }
+    let dry_run = take_flag(&mut args, "--dry-run");
+    let git_ref = take_option(&mut args, &["--git"])?;
+    let directory = take_option(&mut args, &["--dir"])?;
+    reject_unknown(&args)?;
+
+    let source = match (git_ref, directory) {
+        (Some(_), Some(_)) => return Err(CliError::Usage("Use either --git or --dir, not both".to_string())),
+        (Some(reference), None) => UpdateSource::GitRef(reference),
+        (None, Some(directory)) => UpdateSource::Directory(PathBuf::from(directory)),
+        (None, None) => UpdateSource::WorkingTree,
+    };
+    let outcome = updater.perform_self_update(&UpdateOptions { source, dry_run, restart: false }, &config.theme)
+        .map_err(CliError::Failed)?;
+    report("self-update", true, outcome, |_| {});
+    Ok(EXIT_SUCCESS)
+}
+
+fn cmd_package(mut args: Vec<String>, config: &AppConfig) -> CliResult {
+    let theme = &config.theme;
+    let build_first = take_flag(&mut args, "--build");
+    let format = take_option(&mut args, &["--format"])?
+        .map(|f| ArchiveFormat::parse(&f).map_err(CliError::Usage))
+        .transpose()?;
+    let project = take_project(&mut args)?;
+    reject_unknown(&args)?;
+
+    let build_system = BuildSystem::new_with_config(config.clone());
+    if build_first {
+        let result = build_system.build_release(&project, theme).map_err(CliError::Failed)?;
+        if !result.success {
+            print_outcome(false, result.termination, "Release build", result.duration, theme);
+            return Err(CliError::Failed("Release build failed - nothing packaged".to_string()));
+        }
+    }
+
+    let result = build_system.package_release(&project, format, theme).map_err(CliError::Failed)?;
+    report("package", true, result, |r| {
+        for file in &r.files {
+            AnsiTheme::print_themed(&format!("  {}\n", file), theme);
+        }
+        AnsiTheme::print_success(&format!(" Packaged {} ({} bytes)\n", r.archive_path.display(), r.size_bytes), theme);
+        AnsiTheme::print_info(&format!(" SHA-256: {} ({})\n", r.sha256, r.checksum_path.display()), theme);
+    });
+    Ok(EXIT_SUCCESS)
+}
+
+#[derive(Serialize)]
+struct BenchRun {
+    #[serde(flatten)]
+    result: BenchResult,
+    comparison: Option<BaselineReport>,
+    saved_baseline: Option<String>,
+}
+
+fn cmd_bench(mut args: Vec<String>, config: &AppConfig) -> CliResult {
+    let theme = &config.theme;
+    let trailing = take_trailing(&mut args);
+    let baseline_name = take_option(&mut args, &["--baseline"])?;
+    let save_as = take_option(&mut args, &["--save"])?;
+    let threshold = match take_option(&mut args, &["--threshold"])? {
+        Some(t) => t.parse::<f64>()
+            .ok()
+            .filter(|t| *t >= 0.0)
+            .ok_or_else(|| CliError::Usage(format!("Invalid threshold '{}'", t)))?,
+        None => config.bench.regression_threshold(),
+    };
+    let project = take_project(&mut args)?;
+    let filter = if !args.is_empty() && !args[0].starts_with('-') { Some(args.remove(0)) } else { None };
+    reject_unknown(&args)?;
+
+    let build_system = BuildSystem::new_with_config(config.clone());
+    let project_path = build_system.get_project_path(&project);
+    let mut baselines = BenchBaselines::load(&project_path);
+    // Fail before a long benchmark run rather than after it
+    if let Some(name) = &baseline_name {
+        if !baselines.baselines.contains_key(name) {
+            return Err(CliError::Failed(format!("No benchmark baseline named '{}' in project '{}'", name, project)));
+        }
+    }
+
+    let mut bench_args: Vec<String> = filter.into_iter().collect();
+    bench_args.extend(trailing);
+    let result = build_system.bench(&project, &bench_args, theme).map_err(CliError::Failed)?;
+
+    let comparison = baseline_name.as_ref()
+        .map(|name| BaselineReport::new(&result.benchmarks, name, &baselines.baselines[name], threshold));
+    let mut saved_baseline = None;
+    if let Some(name) = save_as.filter(|_| result.success && !result.benchmarks.is_empty()) {
+        baselines.insert(&name, &result.benchmarks, build_history::git_commit(&project_path));
+        baselines.save(&project_path).map_err(CliError::Failed)?;
+        saved_baseline = Some(name);
+    }
+
+    let regressed = comparison.as_ref().is_some_and(|c| c.regressions() > 0);
+    let success = result.success && !regressed;
+    let code = if regressed { EXIT_FAILURE } else { outcome_exit_code(result.success, result.termination) };
+    let run = BenchRun { result, comparison, saved_baseline };
+    report("bench", success, run, |run| {
+        match &run.comparison {
+            Some(comparison) => comparison.print(theme),
+            None => bench_report::print_benchmarks(&run.result.benchmarks, theme),
+        }
+        if let Some(name) = &run.saved_baseline {
+            AnsiTheme::print_success(&format!(" Saved {} benchmarks as baseline '{}'\n", run.result.benchmarks.len(), name), theme);
+        }
+        print_outcome(run.result.success, run.result.termination, "Benchmarks", run.result.duration, theme);
+    });
+    Ok(code)
+}
+
+fn print_output(stdout: &str, stderr: &str, theme: &ThemeConfig) {
+    if !stderr.is_empty() {
+        AnsiTheme::print_themed(stderr, theme);
+    }
+    if !stdout.is_empty() {
+        AnsiTheme::print_themed(stdout, theme);
+    }
+}
+
+fn print_outcome(success: bool, termination: Termination, label: &str, duration: std::time::Duration, theme: &ThemeConfig) {
+    if let Some(reason) = termination.interruption() {
+        AnsiTheme::print_error(&format!(" {} {} ({:.2?})\n", label, reason, duration), theme);
+    } else if success {
+        AnsiTheme::print_success(&format!(" {} succeeded ({:.2?})\n", label, duration), theme);
+    } else {
+        AnsiTheme::print_error(&format!(" {} failed ({:.2?})\n", label, duration), theme);
+    }
+}
+
+// ---- Git commands ----
+
+fn cmd_git(mut args: Vec<String>, config: &AppConfig) -> CliResult {
+    let theme = &config.theme;
+    if args.is_empty() {
+        return Err(CliError::Usage("Missing git subcommand".to_string()));
+    }
+    let subcommand = args.remove(0);
+    let label = format!("git.{}", subcommand);
+    let git_manager = GitManager::new_with_config(config.clone());
+
+    match subcommand.as_str() {
+        "init" => {
+            let project = take_project(&mut args)?;
+            reject_unknown(&args)?;
+            git_manager.initialize_git(&project, theme).map_err(CliError::Failed)?;
+            report(&label, true, Message { message: "Repository initialized".to_string() }, |_| {});
+        }
+        "status" => {
+            let project = take_project(&mut args)?;
+            reject_unknown(&args)?;
+            let status = git_manager.get_status(&project).map_err(CliError::Failed)?;
+            report(&label, true, status, |status| {
+                AnsiTheme::print_cyan(&format!("Branch: {}\n", status.current_branch), theme);
+                if status.has_changes {
+                    for file in &status.files {
+                        AnsiTheme::print_themed(&format!(" {:2} {}\n", file.status, file.file_path), theme);
+                    }
+                } else {
+                    AnsiTheme::print_success(" No changes to commit\n", theme);
+                }
+            });
+        }
+        "add" => {
+            let project = take_project(&mut args)?;
+            if args.is_empty() {
+                git_manager.add_all(&project).map_err(CliError::Failed)?;
+            } else {
+                git_manager.add_files(&project, &args).map_err(CliError::Failed)?;
+            }
+            report(&label, true, Message { message: "Files added".to_string() }, |_| {
+                AnsiTheme::print_success(" Files added\n", theme);
+            });
+        }
+        "commit" => {
+            let message = take_option(&mut args, &["-m", "--message"])?
+                .ok_or_else(|| CliError::Usage("git commit requires -m <message>".to_string()))?;
+            let all = take_flags(&mut args, &["--all", "-a"]);
+            let project = take_project(&mut args)?;
+            reject_unknown(&args)?;
+            if message.trim().is_empty() {
+                return Err(CliError::Usage("Commit message cannot be empty".to_string()));
+            }
+            if all {
+                git_manager.add_all(&project).map_err(CliError::Failed)?;
+            }
+            git_manager.commit(&project, &message).map_err(CliError::Failed)?;
+            // Report the commit that was just created
+            let commit = git_manager.get_log(&project, 1).map_err(CliError::Failed)?.into_iter().next();
+            report(&label, true, commit, |_| {
+                AnsiTheme::print_success(" Changes committed successfully\n", theme);
+            });
+        }
+        "push" | "pull" => {
+            let project = take_project(&mut args)?;
+            let remote = if args.is_empty() { "origin".to_string() } else { args.remove(0) };
+            let branch = if args.is_empty() { "main".to_string() } else { args.remove(0) };
+            reject_unknown(&args)?;
+            if subcommand == "push" {
+                git_manager.push(&project, &remote, &branch).map_err(CliError::Failed)?;
+            } else {
+                git_manager.pull(&project, &remote, &branch).map_err(CliError::Failed)?;
+            }
+            let message = format!("{} {}/{} successful", subcommand, remote, branch);
+            report(&label, true, Message { message }, |m| {
+                AnsiTheme::print_success(&format!(" {}\n", m.message), theme);
+            });
+        }
+        "log" => {
+            let count = match take_option(&mut args, &["-n", "--count"])? {
+                Some(n) => n.parse::<usize>()
+                    .map_err(|_| CliError::Usage(format!("Invalid count '{}'", n)))?,
+                None => 10,
+            };
+            let project = take_project(&mut args)?;
+            reject_unknown(&args)?;
+            let commits = git_manager.get_log(&project, count).map_err(CliError::Failed)?;
+            report(&label, true, commits, |commits| {
+                for commit in commits {
+                    AnsiTheme::print_themed(&format!("{} {}\n", commit.hash, commit.message), theme);
+                }
+            });
+        }
+        "tag" => {
+            let message = take_option(&mut args, &["-m", "--message"])?;
+            let project = take_project(&mut args)?;
+            if args.len() != 1 {
+                return Err(CliError::Usage("Expected: git tag <project> <name> [-m <message>]".to_string()));
+            }
+            git_manager.create_tag(&project, &args[0], message.as_deref()).map_err(CliError::Failed)?;
+            report(&label, true, Message { message: format!("Tag '{}' created", args[0]) }, |m| {
+                AnsiTheme::print_success(&format!(" {}\n", m.message), theme);
+            });
+        }
+        "remotes" => {
+            let project = take_project(&mut args)?;
+            reject_unknown(&args)?;
+            let remotes = git_manager.get_remotes(&project).map_err(CliError::Failed)?;
+            report(&label, true, remotes, |remotes| {
+                for remote in remotes {
+                    AnsiTheme::print_themed(&format!("{}\t{} {}\n", remote.name, remote.url, remote.kind), theme);
+                }
+            });
+        }
+        other => return Err(CliError::Usage(format!("Unknown git subcommand '{}'", other))),
+    }
+
+    Ok(EXIT_SUCCESS)
+}
+
+// ---- Python commands ----
+
+fn cmd_python(mut args: Vec<String>, config: &AppConfig) -> CliResult {
+    let theme = &config.theme;
+    if args.is_empty() {
+        return Err(CliError::Usage("Missing python subcommand".to_string()));
+    }
+    let subcommand = args.remove(0);
+    let label = format!("python.{}", subcommand);
+    let python_manager = PythonManager::new_with_config(config.clone());
+
+    match subcommand.as_str() {
+        "run" => {
+            let project = take_project(&mut args)?;
+            if args.is_empty() {
+                return Err(CliError::Usage("Missing script path".to_string()));
+            }
+            let script = args.remove(0);
+            // Allow an optional `--` before the script arguments
+            if args.first().map(|a| a == "--").unwrap_or(false) {
+                args.remove(0);
+            }
+            let result = python_manager.run_python_script(&project, &script, &args, theme)
+                .map_err(CliError::Failed)?;
+            let code = child_exit_code(result.success, result.exit_code, result.termination);
+            report(&label, result.success, result, |r| {
+                print_output(&r.stdout, &r.stderr, theme);
+            });
+            return Ok(code);
+        }
+        "list" => {
+            let project = take_project(&mut args)?;
+            reject_unknown(&args)?;
+            let project_path = python_manager.get_project_path(&project);
+            let files: Vec<String> = python_manager.list_python_files(&project).map_err(CliError::Failed)?
+                .iter()
+                .map(|(_, path)| path.strip_prefix(&project_path).unwrap_or(path).display().to_string())
+                .collect();
+            report(&label, true, files, |files| {
+                for file in files {
+                    AnsiTheme::print_themed(&format!("{}\n", file), theme);
+                }
+            });
+        }
+        "venv" => {
+            let project = take_project(&mut args)?;
+            reject_unknown(&args)?;
+            python_manager.create_venv(&project, theme).map_err(CliError::Failed)?;
+            report(&label, true, Message { message: "Virtual environment created".to_string() }, |_| {});
+        }
+        "install" => {
+            let project = take_project(&mut args)?;
+            if args.len() != 1 {
+                return Err(CliError::Usage("Expected: python install <project> <package>".to_string()));
+            }
+            python_manager.install_package(&project, &args[0], theme).map_err(CliError::Failed)?;
+            report(&label, true, Message { message: format!("Package '{}' installed", args[0]) }, |_| {});
+        }
+        "requirements" => {
+            let project = take_project(&mut args)?;
+            reject_unknown(&args)?;
+            python_manager.install_requirements(&project, theme).map_err(CliError::Failed)?;
+            report(&label, true, Message { message: "Requirements installed".to_string() }, |_| {});
+        }
+        other => return Err(CliError::Usage(format!("Unknown python subcommand '{}'", other))),
+    }
+
+    Ok(EXIT_SUCCESS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn takes_flags_options_and_trailing_arguments() {
+        let mut rest = args(&["myproj", "--release", "-m", "msg", "--", "--port", "8080"]);
+        assert_eq!(take_trailing(&mut rest), args(&["--port", "8080"]));
+        assert!(take_flag(&mut rest, "--release"));
+        assert!(!take_flag(&mut rest, "--release"));
+        assert_eq!(take_option(&mut rest, &["-m", "--message"]).ok().flatten(), Some("msg".to_string()));
+        assert_eq!(take_project(&mut rest).ok(), Some("myproj".to_string()));
+        assert!(reject_unknown(&rest).is_ok());
+
+        let mut missing_value = args(&["myproj", "-m"]);
+        assert!(matches!(take_option(&mut missing_value, &["-m"]), Err(CliError::Usage(_))));
+        assert!(matches!(reject_unknown(&args(&["--bogus"])), Err(CliError::Usage(msg)) if msg.contains("--bogus")));
+        assert!(take_project(&mut args(&["--release"])).is_err());
+    }
+
+    #[test]
+    fn commit_accepts_both_spellings_of_all() {
+        let mut rest = args(&["myproj", "-a", "--all"]);
+        assert!(take_flags(&mut rest, &["--all", "-a"]));
+        assert_eq!(rest, args(&["myproj"]));
+        assert!(!take_flags(&mut rest, &["--all", "-a"]));
+    }
+
+    #[test]
+    fn global_json_flag_stops_at_separator() {
+        let mut rest = args(&["run", "myproj", "--", "--json"]);
+        apply_global_flags(&mut rest);
+        assert_eq!(rest, args(&["run", "myproj", "--", "--json"]));
+    }
+
+    #[test]
+    fn labels_commands_for_json_documents() {
+        assert_eq!(command_label(&args(&["git", "commit", "myproj"])), "git.commit");
+        assert_eq!(command_label(&args(&["python", "run"])), "python.run");
+        assert_eq!(command_label(&args(&["build", "myproj"])), "build");
+        assert_eq!(command_label(&[]), "");
+    }
+
+    #[test]
+    fn maps_outcomes_to_exit_codes() {
+        assert_eq!(outcome_exit_code(true, Termination::Exited), EXIT_SUCCESS);
+        assert_eq!(outcome_exit_code(false, Termination::Exited), EXIT_FAILURE);
+        assert_eq!(outcome_exit_code(false, Termination::TimedOut), EXIT_TIMED_OUT);
+        assert_eq!(outcome_exit_code(false, Termination::Cancelled), EXIT_CANCELLED);
+        assert_eq!(child_exit_code(false, 3, Termination::Exited), 3);
+        assert_eq!(child_exit_code(false, -1, Termination::Exited), EXIT_FAILURE);
+        assert_eq!(child_exit_code(true, 0, Termination::Exited), EXIT_SUCCESS);
+
+        let config = AppConfig::default();
+        assert_eq!(run(&args(&["--version"]), config.clone()), EXIT_SUCCESS);
+        assert_eq!(run(&args(&["bogus"]), config.clone()), EXIT_USAGE);
+        assert_eq!(run(&args(&["git", "commit", "myproj", "-a"]), config), EXIT_USAGE);
+    }
+}
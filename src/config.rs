@@ -4,6 +4,7 @@ use std::fs;
 use std::path::PathBuf;
 use std::io::{self, Write};
 use crate::ansi_theme::AnsiTheme;
+use crate::logger::{LogFormat, LogLevel, LogRotation};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct AppConfig {
@@ -11,6 +12,186 @@ pub struct AppConfig {
     pub log_file: String,
     pub verbose_logging: bool,
     pub theme: ThemeConfig,  // Added theme
+    // Editor used to open files, e.g. "code --goto {file}:{line}:{column}"; falls back to $VISUAL/$EDITOR
+    #[serde(default)]
+    pub editor_command: Option<String>,
+    #[serde(default)]
+    pub timeouts: TimeoutConfig,
+    #[serde(default)]
+    pub bench: BenchConfig,
+    #[serde(default)]
+    pub logging: LoggingConfig,
+    #[serde(default)]
+    pub backup: BackupConfig,
+}
+
+/// Log file level, format and rotation
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct LoggingConfig {
+    /// Messages below this level are not logged at all (default debug)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub level: Option<LogLevel>,
+    /// Lowest level also printed to the console; debug with verbose_logging, otherwise warn
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub console_level: Option<LogLevel>,
+    #[serde(default)]
+    pub format: LogFormat,
+    #[serde(default)]
+    pub rotation: LogRotation,
+    /// Size at which the file is rotated with `rotation = "size"` (default 10)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_size_mb: Option<u64>,
+    /// Rotated files kept; older ones are deleted (default 5)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keep_files: Option<usize>,
+}
+
+impl LoggingConfig {
+    pub const DEFAULT_MAX_SIZE_MB: u64 = 10;
+    pub const DEFAULT_KEEP_FILES: usize = 5;
+
+    pub fn min_level(&self) -> LogLevel {
+        self.level.unwrap_or(LogLevel::Debug)
+    }
+
+    pub fn max_size_bytes(&self) -> u64 {
+        self.max_size_mb.unwrap_or(Self::DEFAULT_MAX_SIZE_MB).max(1) * 1024 * 1024
+    }
+
+    pub fn keep_files(&self) -> usize {
+        self.keep_files.unwrap_or(Self::DEFAULT_KEEP_FILES)
+    }
+
+    /// e.g. "info and above, json, rotate at 10 MB, keep 5"
+    pub fn describe(&self) -> String {
+        let format = match self.format {
+            LogFormat::Text => "text",
+            LogFormat::Json => "json",
+        };
+        let rotation = match self.rotation {
+            LogRotation::Never => "never rotate".to_string(),
+            LogRotation::Size => format!("rotate at {} MB, keep {}", self.max_size_bytes() / (1024 * 1024), self.keep_files()),
+            LogRotation::Daily => format!("rotate daily, keep {}", self.keep_files()),
+        };
+        format!("{} and above, {}, {}", self.min_level().as_str().to_lowercase(), format, rotation)
+    }
+}
+
+/// Benchmark comparison settings
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct BenchConfig {
+    /// A benchmark this many percent slower than its baseline is a regression (default 5)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub regression_percent: Option<f64>,
+}
+
+impl BenchConfig {
+    pub const DEFAULT_REGRESSION_PERCENT: f64 = 5.0;
+
+    pub fn regression_threshold(&self) -> f64 {
+        self.regression_percent.unwrap_or(Self::DEFAULT_REGRESSION_PERCENT)
+    }
+}
+
+/// What self-update backups contain and how long they are kept
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct BackupConfig {
+    /// Newest backups kept; older ones are deleted after each backup (default 10, 0 keeps all)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keep_count: Option<usize>,
+    /// Backups older than this are deleted; the newest one is always kept (default no limit)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keep_days: Option<u64>,
+    /// Deflate/gzip backup contents (default true)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compress: Option<bool>,
+    /// Leave out files matched by the project's .gitignore files (default true)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub respect_gitignore: Option<bool>,
+    /// Store each distinct file once in a shared object store and write a manifest per backup
+    /// instead of a zip (default false)
+    #[serde(default)]
+    pub incremental: bool,
+}
+
+impl BackupConfig {
+    pub const DEFAULT_KEEP_COUNT: usize = 10;
+
+    /// None when every backup is kept
+    pub fn keep_count(&self) -> Option<usize> {
+        Some(self.keep_count.unwrap_or(Self::DEFAULT_KEEP_COUNT)).filter(|&n| n > 0)
+    }
+
+    pub fn compress(&self) -> bool {
+        self.compress.unwrap_or(true)
+    }
+
+    pub fn respect_gitignore(&self) -> bool {
+        self.respect_gitignore.unwrap_or(true)
+    }
+
+    /// e.g. "incremental, compressed, .gitignore respected, keep 10 newest, up to 30 days"
+    pub fn describe(&self) -> String {
+        let mut parts = vec![
+            if self.incremental { "incremental" } else { "full zip" }.to_string(),
+            if self.compress() { "compressed" } else { "uncompressed" }.to_string(),
+            if self.respect_gitignore() { ".gitignore respected" } else { ".gitignore ignored" }.to_string(),
+        ];
+        parts.push(match self.keep_count() {
+            Some(count) => format!("keep {} newest", count),
+            None => "keep all".to_string(),
+        });
+        if let Some(days) = self.keep_days {
+            parts.push(format!("up to {} days", days));
+        }
+        parts.join(", ")
+    }
+}
+
+/// Per-operation time limits in seconds; unset or 0 means no limit.
+/// Ctrl-C cancels a running operation either way.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct TimeoutConfig {
+    /// cargo build, check and clean
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build_secs: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub test_secs: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub run_secs: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub python_secs: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git_secs: Option<u64>,
+}
+
+impl TimeoutConfig {
+    /// Limit for an operation such as "build", "test", "run", "python" or "git"
+    pub fn get(&self, operation: &str) -> Option<std::time::Duration> {
+        let secs = match operation {
+            "build" | "check" | "clean" => self.build_secs,
+            "test" => self.test_secs,
+            "run" => self.run_secs,
+            "python" => self.python_secs,
+            "git" => self.git_secs,
+            _ => None,
+        };
+        secs.filter(|&s| s > 0).map(std::time::Duration::from_secs)
+    }
+
+    /// e.g. "build 600s, test 300s", or "none"
+    pub fn describe(&self) -> String {
+        let limits: Vec<String> = [
+            ("build", self.build_secs),
+            ("test", self.test_secs),
+            ("run", self.run_secs),
+            ("python", self.python_secs),
+            ("git", self.git_secs),
+        ].iter()
+            .filter_map(|(name, secs)| secs.filter(|&s| s > 0).map(|s| format!("{} {}s", name, s)))
+            .collect();
+        if limits.is_empty() { "none".to_string() } else { limits.join(", ") }
+    }
 }
 
 impl Default for AppConfig {
@@ -20,6 +201,11 @@ impl Default for AppConfig {
             log_file: "build_tool.log".to_string(),
             verbose_logging: true,
             theme: ThemeConfig::default(),  // Added default theme
+            editor_command: None,
+            timeouts: TimeoutConfig::default(),
+            bench: BenchConfig::default(),
+            logging: LoggingConfig::default(),
+            backup: BackupConfig::default(),
         }
     }
 }
@@ -128,6 +314,9 @@ pub fn apply_theme(theme: &ThemeConfig) {
 
 // Function to reset terminal colors
 pub fn reset_terminal_colors() {
+    if !AnsiTheme::output_enabled() {
+        return;
+    }
     print!("\x1b[0m");
     io::stdout().flush().unwrap();
 }
@@ -219,6 +408,12 @@ impl ConfigManager {
         AnsiTheme::print_themed(&format!("Log File: {}\n", self.config.log_file), theme);
         AnsiTheme::print_themed(&format!("Verbose Logging: {}\n", 
             if self.config.verbose_logging { "Enabled" } else { "Disabled" }), theme);
+        AnsiTheme::print_themed(&format!("Logging: {}\n", self.config.logging.describe()), theme);
+        AnsiTheme::print_themed(&format!("Editor: {}\n",
+            crate::opener::Editor::resolve(&self.config).describe()), theme);
+        AnsiTheme::print_themed(&format!("Timeouts: {}\n", self.config.timeouts.describe()), theme);
+        AnsiTheme::print_themed(&format!("Bench Regression Threshold: {}%\n", self.config.bench.regression_threshold()), theme);
+        AnsiTheme::print_themed(&format!("Self-Update Backups: {}\n", self.config.backup.describe()), theme);
         AnsiTheme::print_themed(&format!("Background Color: {:?}\n", self.config.theme.background_color), theme);
         AnsiTheme::print_themed(&format!("Foreground Color: {:?}\n", self.config.theme.foreground_color), theme);
         AnsiTheme::print_themed(&format!("Logo Color: {:?}\n", self.config.theme.logo_color), theme);
@@ -0,0 +1,251 @@
+// diagnostics.rs
+use serde::{Deserialize, Serialize};
+
+// ---- Raw cargo/rustc JSON (only the fields we use) ----
+
+#[derive(Debug, Deserialize)]
+struct CargoMessage {
+    reason: String,
+    message: Option<CompilerMessage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CompilerMessage {
+    message: String,
+    level: String,
+    code: Option<CompilerCode>,
+    #[serde(default)]
+    spans: Vec<CompilerSpan>,
+    #[serde(default)]
+    children: Vec<CompilerMessage>,
+    rendered: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CompilerCode {
+    code: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct CompilerSpan {
+    file_name: String,
+    line_start: usize,
+    line_end: usize,
+    column_start: usize,
+    column_end: usize,
+    is_primary: bool,
+    label: Option<String>,
+    suggested_replacement: Option<String>,
+}
+
+// ---- Typed diagnostics ----
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiagnosticLevel {
+    Error,
+    Warning,
+    Note,
+    Help,
+}
+
+impl DiagnosticLevel {
+    fn from_rustc(level: &str) -> Option<Self> {
+        match level {
+            "error" | "error: internal compiler error" => Some(DiagnosticLevel::Error),
+            "warning" => Some(DiagnosticLevel::Warning),
+            "note" => Some(DiagnosticLevel::Note),
+            "help" => Some(DiagnosticLevel::Help),
+            // failure-note and friends are summaries, not diagnostics
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DiagnosticLevel::Error => "error",
+            DiagnosticLevel::Warning => "warning",
+            DiagnosticLevel::Note => "note",
+            DiagnosticLevel::Help => "help",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DiagnosticSpan {
+    pub file: String,
+    pub line_start: usize,
+    pub line_end: usize,
+    pub column_start: usize,
+    pub column_end: usize,
+    pub is_primary: bool,
+    pub label: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Suggestion {
+    pub message: String,
+    pub file: String,
+    pub line: usize,
+    pub column: usize,
+    pub replacement: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Diagnostic {
+    pub level: DiagnosticLevel,
+    pub code: Option<String>,
+    pub message: String,
+    pub spans: Vec<DiagnosticSpan>,
+    pub suggestions: Vec<Suggestion>,
+    pub rendered: Option<String>,
+}
+
+impl Diagnostic {
+    pub fn primary_span(&self) -> Option<&DiagnosticSpan> {
+        self.spans.iter().find(|s| s.is_primary).or_else(|| self.spans.first())
+    }
+
+    /// `file:line:column` of the primary span, or an empty string for spanless diagnostics
+    pub fn location(&self) -> String {
+        self.primary_span()
+            .map(|s| format!("{}:{}:{}", s.file, s.line_start, s.column_start))
+            .unwrap_or_default()
+    }
+
+    /// One-line summary such as `warning[unused_variables]: unused variable: `x``
+    pub fn headline(&self) -> String {
+        match &self.code {
+            Some(code) => format!("{}[{}]: {}", self.level.as_str(), code, self.message),
+            None => format!("{}: {}", self.level.as_str(), self.message),
+        }
+    }
+
+    fn from_compiler(message: CompilerMessage) -> Option<Self> {
+        let level = DiagnosticLevel::from_rustc(&message.level)?;
+
+        // rustc's closing "aborting due to..." / "N warnings emitted" lines carry no information
+        if message.spans.is_empty()
+            && (message.message.starts_with("aborting due to") || message.message.ends_with("emitted"))
+        {
+            return None;
+        }
+
+        let suggestions = message.children.iter()
+            .flat_map(|child| child.spans.iter().filter_map(move |span| {
+                span.suggested_replacement.as_ref().map(|replacement| Suggestion {
+                    message: child.message.clone(),
+                    file: span.file_name.clone(),
+                    line: span.line_start,
+                    column: span.column_start,
+                    replacement: replacement.clone(),
+                })
+            }))
+            .collect();
+
+        let spans = message.spans.into_iter()
+            .map(|span| DiagnosticSpan {
+                file: span.file_name,
+                line_start: span.line_start,
+                line_end: span.line_end,
+                column_start: span.column_start,
+                column_end: span.column_end,
+                is_primary: span.is_primary,
+                label: span.label,
+            })
+            .collect();
+
+        Some(Diagnostic {
+            level,
+            code: message.code.map(|c| c.code),
+            message: message.message,
+            spans,
+            suggestions,
+            rendered: message.rendered,
+        })
+    }
+}
+
+/// What a line of `--message-format=json` output should show on the console:
+/// `None` if it is not cargo JSON, `Some(None)` for machine-only messages and
+/// `Some(Some(text))` with rustc's rendered text for compiler messages.
+pub fn console_text(line: &str) -> Option<Option<String>> {
+    if !line.starts_with('{') {
+        return None;
+    }
+    let message: CargoMessage = serde_json::from_str(line).ok()?;
+    Some(match message.message {
+        Some(compiler) if message.reason == "compiler-message" => compiler.rendered
+            .map(|text| text.trim_end().to_string())
+            .filter(|text| !text.is_empty()),
+        _ => None,
+    })
+}
+
+/// Splits cargo's JSON stdout into typed diagnostics and any remaining non-JSON output
+/// (e.g. from build scripts). Duplicate diagnostics, which cargo reports once per target, are dropped.
+pub fn parse_cargo_output(stdout: &str) -> (Vec<Diagnostic>, String) {
+    let mut diagnostics: Vec<Diagnostic> = Vec::new();
+    let mut remaining = String::new();
+
+    for line in stdout.lines() {
+        let parsed = if line.starts_with('{') {
+            serde_json::from_str::<CargoMessage>(line).ok()
+        } else {
+            None
+        };
+
+        match parsed {
+            Some(message) => {
+                if message.reason != "compiler-message" {
+                    continue;
+                }
+                if let Some(diagnostic) = message.message.and_then(Diagnostic::from_compiler) {
+                    if !diagnostics.contains(&diagnostic) {
+                        diagnostics.push(diagnostic);
+                    }
+                }
+            }
+            None => {
+                remaining.push_str(line);
+                remaining.push('\n');
+            }
+        }
+    }
+
+    (diagnostics, remaining)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MISMATCH: &str = r#"{"reason":"compiler-message","package_id":"p","message":{"rendered":"error[E0308]: mismatched types\n --> src/main.rs:3:18\n","children":[],"level":"error","message":"mismatched types","spans":[{"column_end":21,"column_start":18,"file_name":"src/main.rs","is_primary":true,"label":"expected `i32`, found `&str`","line_end":3,"line_start":3,"suggested_replacement":null}],"code":{"code":"E0308","explanation":"..."}}}"#;
+    const UNUSED: &str = r#"{"reason":"compiler-message","package_id":"p","message":{"rendered":"warning: unused variable: `x`\n","children":[{"children":[],"code":null,"level":"help","message":"if this is intentional, prefix it with an underscore","rendered":null,"spans":[{"column_end":10,"column_start":9,"file_name":"src/main.rs","is_primary":true,"label":null,"line_end":2,"line_start":2,"suggested_replacement":"_x"}]}],"level":"warning","message":"unused variable: `x`","spans":[{"column_end":10,"column_start":9,"file_name":"src/main.rs","is_primary":true,"label":null,"line_end":2,"line_start":2,"suggested_replacement":null}],"code":{"code":"unused_variables","explanation":null}}}"#;
+    const ABORTING: &str = r#"{"reason":"compiler-message","package_id":"p","message":{"rendered":"error: aborting due to 1 previous error\n","children":[],"level":"error","message":"aborting due to 1 previous error","spans":[],"code":null}}"#;
+
+    #[test]
+    fn parses_errors_warnings_and_suggestions() {
+        let stdout = [MISMATCH, UNUSED, ABORTING, r#"{"reason":"build-finished","success":false}"#, "build script says hi"].join("\n");
+        let (diagnostics, remaining) = parse_cargo_output(&stdout);
+
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].level, DiagnosticLevel::Error);
+        assert_eq!(diagnostics[0].code.as_deref(), Some("E0308"));
+        assert_eq!(diagnostics[0].location(), "src/main.rs:3:18");
+
+        let warning = &diagnostics[1];
+        assert_eq!(warning.headline(), "warning[unused_variables]: unused variable: `x`");
+        assert_eq!(warning.suggestions[0].replacement, "_x");
+        assert_eq!(warning.suggestions[0].line, 2);
+
+        assert_eq!(remaining, "build script says hi\n");
+    }
+
+    #[test]
+    fn console_text_shows_rendered_messages_only() {
+        assert_eq!(console_text("   Compiling foo"), None);
+        assert_eq!(console_text(r#"{"reason":"build-finished","success":true}"#), Some(None));
+        assert_eq!(console_text(MISMATCH), Some(Some("error[E0308]: mismatched types\n --> src/main.rs:3:18".to_string())));
+    }
+}
@@ -1,11 +1,12 @@
 // file_manager.rs
+use std::cmp::Ordering;
 use std::fs;
 use std::path::{Path, PathBuf};
-use std::process::Command;
 use serde::{Deserialize, Serialize};
 use std::io::{self, Write};
 use crate::ansi_theme::AnsiTheme;
 use crate::config::ThemeConfig;
+use crate::version::{Bump, Channel, Version};
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct CargoToml {
@@ -135,26 +136,19 @@ impl FileManager {
         fs::read_to_string(&full_path).map_err(|e| e.to_string())
     }
 
-    pub fn open_file_in_notepad(&self, project_name: &str, file_path: &str) -> Result<(), String> {
+    pub fn open_file_in_editor(&self, project_name: &str, file_path: &str) -> Result<(), String> {
         let full_path = self.config.get_src_path(project_name).join(file_path);
         
         if !full_path.exists() {
             return Err(format!("File does not exist: {}", full_path.display()));
         }
 
-        AnsiTheme::print_info(&format!(" Opening '{}' in Notepad...\n", file_path), &self.config.theme);
+        let editor = crate::opener::Editor::resolve(&self.config);
+        AnsiTheme::print_info(&format!(" Opening '{}' in {}...\n", file_path, editor.describe()), &self.config.theme);
         
-        let status = Command::new("notepad.exe")
-            .arg(&full_path)
-            .status()
-            .map_err(|e| format!("Failed to open Notepad: {}", e))?;
-        
-        if status.success() {
-            AnsiTheme::print_success(" Notepad closed. File saved.\n", &self.config.theme);
-            Ok(())
-        } else {
-            Err("Notepad exited with an error".to_string())
-        }
+        crate::opener::open_file(&full_path, None, &self.config)?;
+        AnsiTheme::print_success(" Editor closed.\n", &self.config.theme);
+        Ok(())
     }
 
     pub fn read_cargo_toml(&self, project_name: &str) -> Result<CargoToml, String> {
@@ -163,26 +157,19 @@ impl FileManager {
         toml::from_str(&content).map_err(|e| e.to_string())
     }
 
-    pub fn open_cargo_toml_in_notepad(&self, project_name: &str) -> Result<(), String> {
+    pub fn open_cargo_toml_in_editor(&self, project_name: &str) -> Result<(), String> {
         let cargo_path = self.config.get_cargo_toml_path(project_name);
         
         if !cargo_path.exists() {
             return Err("Cargo.toml does not exist".to_string());
         }
 
-        AnsiTheme::print_info(" Opening Cargo.toml in Notepad...\n", &self.config.theme);
-        
-        let status = Command::new("notepad.exe")
-            .arg(&cargo_path)
-            .status()
-            .map_err(|e| format!("Failed to open Notepad: {}", e))?;
+        let editor = crate::opener::Editor::resolve(&self.config);
+        AnsiTheme::print_info(&format!(" Opening Cargo.toml in {}...\n", editor.describe()), &self.config.theme);
         
-        if status.success() {
-            AnsiTheme::print_success(" Notepad closed. Cargo.toml saved.\n", &self.config.theme);
-            Ok(())
-        } else {
-            Err("Notepad exited with an error".to_string())
-        }
+        crate::opener::open_file(&cargo_path, None, &self.config)?;
+        AnsiTheme::print_success(" Editor closed.\n", &self.config.theme);
+        Ok(())
     }
 
     pub fn modify_cargo_toml<F>(&self, project_name: &str, modifier: F) -> Result<(), String> 
@@ -216,78 +203,45 @@ impl FileManager {
 
     // Manual version management functions
     pub fn set_version(&self, project_name: &str, new_version: &str) -> Result<(), String> {
+        let version = Version::parse(new_version)?;
+        let current = self.read_cargo_toml(project_name)?.package.version;
+        if Version::parse(&current).is_ok_and(|current| version.precedence(&current) == Ordering::Less) {
+            AnsiTheme::print_warning(&format!(" {} comes before the current version {}\n", version, current), &self.config.theme);
+        }
         self.modify_cargo_toml(project_name, |cargo_toml| {
-            cargo_toml.package.version = new_version.to_string();
-            AnsiTheme::print_success(&format!(" Version set to: {}\n", new_version), &self.config.theme);
+            cargo_toml.package.version = version.to_string();
+            AnsiTheme::print_success(&format!(" Version set to: {}\n", version), &self.config.theme);
         })
     }
 
-    pub fn increment_patch_version(&self, project_name: &str) -> Result<String, String> {
-        let mut new_version = String::new();
-    
+    /// Moves the project's version on by `bump` and returns the new version
+    pub fn bump_version(&self, project_name: &str, bump: Bump) -> Result<String, String> {
+        let current_version = self.read_cargo_toml(project_name)?.package.version;
+        let new_version = Version::parse(&current_version)?.bump(bump)?.to_string();
         self.modify_cargo_toml(project_name, |cargo_toml| {
-            let current_version = cargo_toml.package.version.clone();
-            let parts: Vec<&str> = current_version.split('.').collect();
-        
-            if parts.len() == 3 {
-                if let Ok(patch) = parts[2].parse::<u32>() {
-                    new_version = format!("{}.{}.{}", parts[0], parts[1], patch + 1);
-                    cargo_toml.package.version = new_version.clone();
-                    AnsiTheme::print_success(&format!(" Version incremented: {} → {}\n", current_version, new_version), &self.config.theme);
-                } else {
-                    AnsiTheme::print_error(&format!(" Failed to parse patch version: {}\n", parts[2]), &self.config.theme);
-                }
-            } else {
-                AnsiTheme::print_error(&format!(" Invalid version format: {}\n", current_version), &self.config.theme);
-            }
+            cargo_toml.package.version = new_version.clone();
+            AnsiTheme::print_success(&format!(" Version updated: {} → {}\n", current_version, new_version), &self.config.theme);
         })?;
-    
         Ok(new_version)
     }
 
+    pub fn increment_patch_version(&self, project_name: &str) -> Result<String, String> {
+        self.bump_version(project_name, Bump::Patch)
+    }
+
     pub fn increment_minor_version(&self, project_name: &str) -> Result<String, String> {
-        let mut new_version = String::new();
-    
-        self.modify_cargo_toml(project_name, |cargo_toml| {
-            let current_version = cargo_toml.package.version.clone();
-            let parts: Vec<&str> = current_version.split('.').collect();
-        
-            if parts.len() == 3 {
-                if let Ok(minor) = parts[1].parse::<u32>() {
-                    new_version = format!("{}.{}.0", parts[0], minor + 1);
-                    cargo_toml.package.version = new_version.clone();
-                    AnsiTheme::print_success(&format!(" Version incremented: {} → {}\n", current_version, new_version), &self.config.theme);
-                } else {
-                    AnsiTheme::print_error(&format!(" Failed to parse minor version: {}\n", parts[1]), &self.config.theme);
-                }
-            } else {
-                AnsiTheme::print_error(&format!(" Invalid version format: {}\n", current_version), &self.config.theme);
-            }
-        })?;
-    
-        Ok(new_version)
+        self.bump_version(project_name, Bump::Minor)
     }
 
     pub fn increment_major_version(&self, project_name: &str) -> Result<String, String> {
-        let mut new_version = String::new();
-    
-        self.modify_cargo_toml(project_name, |cargo_toml| {
-            let current_version = cargo_toml.package.version.clone();
-            let parts: Vec<&str> = current_version.split('.').collect();
-        
-            if parts.len() == 3 {
-                if let Ok(major) = parts[0].parse::<u32>() {
-                    new_version = format!("{}.0.0", major + 1);
-                    cargo_toml.package.version = new_version.clone();
-                    AnsiTheme::print_success(&format!(" Version incremented: {} → {}\n", current_version, new_version), &self.config.theme);
-                } else {
-                    AnsiTheme::print_error(&format!(" Failed to parse major version: {}\n", parts[0]), &self.config.theme);
-                }
-            } else {
-                AnsiTheme::print_error(&format!(" Invalid version format: {}\n", current_version), &self.config.theme);
-            }
-        })?;
-    
+        self.bump_version(project_name, Bump::Major)
+    }
+
+    /// Sets the `+build` part of the project's version; an empty `build` removes it
+    pub fn set_build_metadata(&self, project_name: &str, build: &str) -> Result<String, String> {
+        let current_version = self.read_cargo_toml(project_name)?.package.version;
+        let new_version = Version::parse(&current_version)?.with_build(build)?.to_string();
+        self.set_version(project_name, &new_version)?;
         Ok(new_version)
     }
 
@@ -299,22 +253,34 @@ impl FileManager {
 
     pub fn version_management_menu(&self, project_name: &str, theme: &ThemeConfig) -> Result<(), String> {
         loop {
+            // Bumping a pre-release can release it instead of incrementing, so the labels show
+            // what each bump does to the current version when it can be read
+            let current = self.read_cargo_toml(project_name).ok()
+                .and_then(|cargo_toml| Version::parse(&cargo_toml.package.version).ok());
+            let preview = |bump: Bump, generic: &str| match current.as_ref().map(|version| (version, version.bump(bump))) {
+                Some((version, Ok(next))) => format!("{} → {}", version, next),
+                _ => generic.to_string(),
+            };
+
             AnsiTheme::print_themed("\n", theme);
             AnsiTheme::print_cyan(&format!("Version Management for: {}\n", project_name), theme);
             AnsiTheme::print_themed("1) Show current version\n", theme);
             AnsiTheme::print_themed("2) Set custom version\n", theme);
-            AnsiTheme::print_themed("3) Increment patch version (x.y.Z → x.y.Z+1)\n", theme);
-            AnsiTheme::print_themed("4) Increment minor version (x.Y.z → x.Y+1.0)\n", theme);
-            AnsiTheme::print_themed("5) Increment major version (X.y.z → X+1.0.0)\n", theme);
-            AnsiTheme::print_themed("6) Open Cargo.toml in Notepad\n", theme);
+            AnsiTheme::print_themed(&format!("3) Increment patch version ({})\n", preview(Bump::Patch, "x.y.Z → x.y.Z+1")), theme);
+            AnsiTheme::print_themed(&format!("4) Increment minor version ({})\n", preview(Bump::Minor, "x.Y.z → x.Y+1.0")), theme);
+            AnsiTheme::print_themed(&format!("5) Increment major version ({})\n", preview(Bump::Major, "X.y.z → X+1.0.0")), theme);
+            AnsiTheme::print_themed("6) Next pre-release (alpha/beta/rc, e.g. 1.2.0-beta.1 → 1.2.0-beta.2)\n", theme);
+            AnsiTheme::print_themed("7) Promote pre-release to release (1.2.0-rc.2 → 1.2.0)\n", theme);
+            AnsiTheme::print_themed("8) Set build metadata (x.y.z+BUILD)\n", theme);
+            AnsiTheme::print_themed("9) Open Cargo.toml in Editor\n", theme);
             AnsiTheme::print_themed("0) Back to main menu\n", theme);
             
-            match Self::get_number_input("Select option: ", 0, 6, theme) {
+            match Self::get_number_input("Select option: ", 0, 9, theme) {
                 Some(1) => {
                     self.show_current_version(project_name)?;
                 }
                 Some(2) => {
-                    AnsiTheme::print_themed("Enter new version (format: X.Y.Z): ", theme);
+                    AnsiTheme::print_themed("Enter new version (format: X.Y.Z[-PRE][+BUILD]): ", theme);
                     io::stdout().flush().unwrap();
                     
                     let mut input = String::new();
@@ -322,41 +288,38 @@ impl FileManager {
                     let version = input.trim();
                     
                     if !version.is_empty() {
-                        self.set_version(project_name, version)?;
-                    }
-                }
-                Some(3) => {
-                    match self.increment_patch_version(project_name) {
-                        Ok(new_version) => {
-                            AnsiTheme::print_success(&format!(" Patch version incremented to: {}\n", new_version), theme);
-                        }
-                        Err(e) => {
-                            AnsiTheme::print_error(&format!(" Failed to increment patch version: {}\n", e), theme);
+                        if let Err(e) = self.set_version(project_name, version) {
+                            AnsiTheme::print_error(&format!(" Failed to set version: {}\n", e), theme);
                         }
                     }
                 }
-                Some(4) => {
-                    match self.increment_minor_version(project_name) {
-                        Ok(new_version) => {
-                            AnsiTheme::print_success(&format!(" Minor version incremented to: {}\n", new_version), theme);
-                        }
-                        Err(e) => {
-                            AnsiTheme::print_error(&format!(" Failed to increment minor version: {}\n", e), theme);
-                        }
+                Some(3) => self.bump_version_interactive(project_name, Bump::Patch, "Patch version incremented to", theme),
+                Some(4) => self.bump_version_interactive(project_name, Bump::Minor, "Minor version incremented to", theme),
+                Some(5) => self.bump_version_interactive(project_name, Bump::Major, "Major version incremented to", theme),
+                Some(6) => {
+                    AnsiTheme::print_themed("Channel (alpha, beta, rc): ", theme);
+                    io::stdout().flush().unwrap();
+
+                    let mut input = String::new();
+                    io::stdin().read_line(&mut input).unwrap();
+                    match Channel::parse(input.trim()) {
+                        Some(channel) => self.bump_version_interactive(project_name, Bump::Pre(channel), "Pre-release version set to", theme),
+                        None => AnsiTheme::print_error(&format!(" Unknown channel '{}'\n", input.trim()), theme),
                     }
                 }
-                Some(5) => {
-                    match self.increment_major_version(project_name) {
-                        Ok(new_version) => {
-                            AnsiTheme::print_success(&format!(" Major version incremented to: {}\n", new_version), theme);
-                        }
-                        Err(e) => {
-                            AnsiTheme::print_error(&format!(" Failed to increment major version: {}\n", e), theme);
-                        }
+                Some(7) => self.bump_version_interactive(project_name, Bump::Release, "Promoted to release", theme),
+                Some(8) => {
+                    AnsiTheme::print_themed("Build metadata (e.g. a commit hash or date, empty to remove): ", theme);
+                    io::stdout().flush().unwrap();
+
+                    let mut input = String::new();
+                    io::stdin().read_line(&mut input).unwrap();
+                    if let Err(e) = self.set_build_metadata(project_name, input.trim()) {
+                        AnsiTheme::print_error(&format!(" Failed to set build metadata: {}\n", e), theme);
                     }
                 }
-                Some(6) => {
-                    self.open_cargo_toml_in_notepad(project_name)?;
+                Some(9) => {
+                    self.open_cargo_toml_in_editor(project_name)?;
                 }
                 Some(0) => break,
                 _ => AnsiTheme::print_themed("Invalid selection.\n", theme),
@@ -365,6 +328,17 @@ impl FileManager {
         Ok(())
     }
 
+    fn bump_version_interactive(&self, project_name: &str, bump: Bump, label: &str, theme: &ThemeConfig) {
+        match self.bump_version(project_name, bump) {
+            Ok(new_version) => {
+                AnsiTheme::print_success(&format!(" {}: {}\n", label, new_version), theme);
+            }
+            Err(e) => {
+                AnsiTheme::print_error(&format!(" Failed to bump version: {}\n", e), theme);
+            }
+        }
+    }
+
     pub fn list_project_files(&self, project_name: &str) -> Result<Vec<(usize, PathBuf)>, String> {
         let src_path = self.config.get_src_path(project_name);
         
@@ -506,7 +480,7 @@ impl FileManager {
     }
 
     /// Helper method to clean up empty directories after file deletion
-    fn cleanup_empty_directories(&self, project_name: &str, deleted_file_path: &PathBuf) -> Result<(), String> {
+    fn cleanup_empty_directories(&self, project_name: &str, deleted_file_path: &Path) -> Result<(), String> {
         if let Some(mut current_dir) = deleted_file_path.parent() {
             let src_path = self.config.get_src_path(project_name);
             
@@ -543,7 +517,7 @@ impl FileManager {
             AnsiTheme::print_cyan(&format!("File Operations for: {}\n", project_name), theme);
             AnsiTheme::print_themed("1) List files\n", theme);
             AnsiTheme::print_themed("2) Create file\n", theme);
-            AnsiTheme::print_themed("3) Edit file (Notepad)\n", theme);
+            AnsiTheme::print_themed("3) Edit file (Editor)\n", theme);
             AnsiTheme::print_themed("4) Delete file\n", theme);
             AnsiTheme::print_themed("5) Delete empty directory\n", theme);
             AnsiTheme::print_themed("6) Force delete directory (recursive)\n", theme);
@@ -596,7 +570,7 @@ impl FileManager {
                 }
                 Some(3) => {
                     if let Ok(Some(file_path)) = self.select_file_from_list(project_name) {
-                        if let Err(e) = self.open_file_in_notepad(project_name, &file_path) {
+                        if let Err(e) = self.open_file_in_editor(project_name, &file_path) {
                             AnsiTheme::print_error(&format!(" Failed to open file: {}\n", e), theme);
                         }
                     }
@@ -643,7 +617,7 @@ impl FileManager {
                     let dir_path = dir_input.trim();
                     
                     if !dir_path.is_empty() {
-                        AnsiTheme::print_error(&format!(" Are you ABSOLUTELY sure? This cannot be undone! (type 'YES' to confirm): ",), theme);
+                        AnsiTheme::print_error(" Are you ABSOLUTELY sure? This cannot be undone! (type 'YES' to confirm): ", theme);
                         io::stdout().flush().unwrap();
                         
                         let mut confirm = String::new();
@@ -674,7 +648,7 @@ impl FileManager {
                 
                 if path.is_dir() {
                     Self::walk_dir(&path, files)?;
-                } else if path.extension().map_or(false, |ext| ext == "rs") {
+                } else if path.extension().is_some_and(|ext| ext == "rs") {
                     files.push(path);
                 }
             }
@@ -3,6 +3,8 @@ use std::process::{Command, Stdio};
 use std::fs;
 use crate::ansi_theme::AnsiTheme;
 use crate::config::ThemeConfig;
+use crate::process::SupervisedCommand;
+use serde::Serialize;
 
 pub struct GitManager {
     config: crate::config::AppConfig,
@@ -38,9 +40,10 @@ impl GitManager {
         let output = Command::new("git")
             .current_dir(&project_path)
             .arg("init")
+            .stdin(Stdio::null())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
-            .output()
+            .output_within(self.config.timeouts.get("git"))
             .map_err(|e| format!("Failed to initialize git repository: {}", e))?;
 
         if output.status.success() {
@@ -96,9 +99,10 @@ Thumbs.db"#;
             .current_dir(&project_path)
             .arg("status")
             .arg("--porcelain")
+            .stdin(Stdio::null())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
-            .output()
+            .output_within(self.config.timeouts.get("git"))
             .map_err(|e| format!("Failed to get git status: {}", e))?;
 
         if !output.status.success() {
@@ -128,9 +132,10 @@ Thumbs.db"#;
             .current_dir(&project_path)
             .arg("branch")
             .arg("--show-current")
+            .stdin(Stdio::null())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
-            .output()
+            .output_within(self.config.timeouts.get("git"))
             .map_err(|e| format!("Failed to get current branch: {}", e))?;
 
         let current_branch = if branch_output.status.success() {
@@ -161,9 +166,10 @@ Thumbs.db"#;
         }
 
         let output = command
+            .stdin(Stdio::null())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
-            .output()
+            .output_within(self.config.timeouts.get("git"))
             .map_err(|e| format!("Failed to add files: {}", e))?;
 
         if output.status.success() {
@@ -185,9 +191,10 @@ Thumbs.db"#;
             .current_dir(&project_path)
             .arg("add")
             .arg(".")
+            .stdin(Stdio::null())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
-            .output()
+            .output_within(self.config.timeouts.get("git"))
             .map_err(|e| format!("Failed to add all files: {}", e))?;
 
         if output.status.success() {
@@ -210,9 +217,10 @@ Thumbs.db"#;
             .arg("commit")
             .arg("-m")
             .arg(message)
+            .stdin(Stdio::null())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
-            .output()
+            .output_within(self.config.timeouts.get("git"))
             .map_err(|e| format!("Failed to commit: {}", e))?;
 
         if output.status.success() {
@@ -236,9 +244,10 @@ Thumbs.db"#;
             .arg("-u") // Set upstream
             .arg(remote)
             .arg(branch)
+            .stdin(Stdio::null())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
-            .output()
+            .output_within(self.config.timeouts.get("git"))
             .map_err(|e| format!("Failed to push: {}", e))?;
 
         if output.status.success() {
@@ -264,9 +273,10 @@ Thumbs.db"#;
             .arg("--set-upstream")
             .arg(remote)
             .arg(branch)
+            .stdin(Stdio::null())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
-            .output()
+            .output_within(self.config.timeouts.get("git"))
             .map_err(|e| format!("Failed to push: {}", e))?;
 
         if output.status.success() {
@@ -289,9 +299,10 @@ Thumbs.db"#;
             .arg("pull")
             .arg(remote)
             .arg(branch)
+            .stdin(Stdio::null())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
-            .output()
+            .output_within(self.config.timeouts.get("git"))
             .map_err(|e| format!("Failed to pull: {}", e))?;
 
         if output.status.success() {
@@ -313,9 +324,10 @@ Thumbs.db"#;
             .current_dir(&project_path)
             .arg("remote")
             .arg("-v")
+            .stdin(Stdio::null())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
-            .output()
+            .output_within(self.config.timeouts.get("git"))
             .map_err(|e| format!("Failed to get remotes: {}", e))?;
 
         if !output.status.success() {
@@ -356,9 +368,10 @@ Thumbs.db"#;
             .arg("add")
             .arg(name)
             .arg(url)
+            .stdin(Stdio::null())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
-            .output()
+            .output_within(self.config.timeouts.get("git"))
             .map_err(|e| format!("Failed to add remote: {}", e))?;
 
         if output.status.success() {
@@ -386,9 +399,10 @@ Thumbs.db"#;
         }
 
         let output = command
+            .stdin(Stdio::null())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
-            .output()
+            .output_within(self.config.timeouts.get("git"))
             .map_err(|e| format!("Failed to create tag: {}", e))?;
 
         if output.status.success() {
@@ -411,9 +425,10 @@ Thumbs.db"#;
             .arg("push")
             .arg(remote)
             .arg("--tags")
+            .stdin(Stdio::null())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
-            .output()
+            .output_within(self.config.timeouts.get("git"))
             .map_err(|e| format!("Failed to push tags: {}", e))?;
 
         if output.status.success() {
@@ -436,9 +451,10 @@ Thumbs.db"#;
             .arg("log")
             .arg(format!("--max-count={}", count))
             .arg("--oneline")
+            .stdin(Stdio::null())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
-            .output()
+            .output_within(self.config.timeouts.get("git"))
             .map_err(|e| format!("Failed to get git log: {}", e))?;
 
         if !output.status.success() {
@@ -466,27 +482,27 @@ Thumbs.db"#;
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct GitStatus {
     pub files: Vec<GitFileStatus>,
     pub current_branch: String,
     pub has_changes: bool,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct GitFileStatus {
     pub status: String,
     pub file_path: String,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct GitRemote {
     pub name: String,
     pub url: String,
     pub kind: String,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct GitCommit {
     pub hash: String,
     pub message: String,
@@ -0,0 +1,432 @@
+// jobs.rs
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use chrono::Local;
+use serde::Serialize;
+use crate::ansi_theme::AnsiTheme;
+use crate::config::{AppConfig, ThemeConfig};
+
+const LOG_DIR: &str = "job_logs";
+// How long a stopped job gets to shut down cleanly before it is killed
+const STOP_GRACE: Duration = Duration::from_secs(3);
+
+// PIDs of running jobs, so the Ctrl-C exit path can kill them without access to the manager
+static RUNNING_PIDS: Mutex<Vec<u32>> = Mutex::new(Vec::new());
+
+struct Job {
+    id: usize,
+    name: String,
+    command_line: String,
+    log_path: PathBuf,
+    started: Instant,
+    started_at: String,
+    child: Child,
+    exit_status: Option<ExitStatus>,
+}
+
+impl Job {
+    // Picks up the exit status once the process has finished
+    fn refresh(&mut self) {
+        if self.exit_status.is_none() {
+            if let Ok(Some(status)) = self.child.try_wait() {
+                self.exit_status = Some(status);
+                unregister(self.child.id());
+            }
+        }
+    }
+
+    // The job itself while it is still running
+    fn running(&mut self) -> Option<&mut Self> {
+        self.refresh();
+        self.exit_status.is_none().then_some(self)
+    }
+
+    fn info(&self) -> JobInfo {
+        JobInfo {
+            id: self.id,
+            name: self.name.clone(),
+            command_line: self.command_line.clone(),
+            pid: self.child.id(),
+            started_at: self.started_at.clone(),
+            uptime: self.started.elapsed(),
+            running: self.exit_status.is_none(),
+            exit_code: self.exit_status.and_then(|s| s.code()),
+            log_path: self.log_path.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct JobInfo {
+    pub id: usize,
+    pub name: String,
+    pub command_line: String,
+    pub pid: u32,
+    pub started_at: String,
+    #[serde(rename = "uptime_ms", serialize_with = "crate::output::duration_ms")]
+    pub uptime: Duration,
+    pub running: bool,
+    pub exit_code: Option<i32>,
+    pub log_path: PathBuf,
+}
+
+impl JobInfo {
+    /// "running", "exited (0)" or "killed"
+    pub fn state(&self) -> String {
+        match (self.running, self.exit_code) {
+            (true, _) => "running".to_string(),
+            (false, Some(code)) => format!("exited ({})", code),
+            (false, None) => "killed".to_string(),
+        }
+    }
+}
+
+/// Long-running programs started in the background, with output captured to log files.
+/// Every job still running is stopped when the manager is dropped.
+pub struct JobManager {
+    config: AppConfig,
+    log_dir: PathBuf,
+    jobs: Mutex<Vec<Job>>,
+}
+
+impl JobManager {
+    pub fn new_with_config(config: AppConfig) -> Self {
+        // A fixed per-user directory, wherever the terminal was started from
+        let log_dir = dirs::data_local_dir()
+            .unwrap_or_else(std::env::temp_dir)
+            .join("rust_dev_terminal")
+            .join(LOG_DIR);
+        JobManager { config, log_dir, jobs: Mutex::new(Vec::new()) }
+    }
+
+    /// `cargo run [-- args]` in the project directory
+    pub fn start_cargo_run(&self, project_name: &str, args: &[String]) -> Result<JobInfo, String> {
+        let project_path = self.existing_project_path(project_name)?;
+        let mut command = Command::new("cargo");
+        command.arg("run");
+        if !args.is_empty() {
+            command.arg("--").args(args);
+        }
+        self.start(&format!("cargo run ({})", project_name), command, &project_path)
+    }
+
+    pub fn start_python_script(&self, project_name: &str, script_path: &str, args: &[String]) -> Result<JobInfo, String> {
+        let project_path = self.existing_project_path(project_name)?;
+        if !project_path.join(script_path).exists() {
+            return Err(format!("Python script not found: {}", project_path.join(script_path).display()));
+        }
+        let mut command = Command::new("python");
+        command.arg(script_path).args(args);
+        self.start(&format!("python {} ({})", script_path, project_name), command, &project_path)
+    }
+
+    /// Any command line, run in the project directory or else the workspace
+    pub fn start_command(&self, project_name: Option<&str>, command_line: &str) -> Result<JobInfo, String> {
+        let argv = crate::opener::split_command_line(command_line);
+        if argv.is_empty() {
+            return Err("Command is empty".to_string());
+        }
+        let directory = match project_name {
+            Some(project) => self.existing_project_path(project)?,
+            None => self.config.get_workspace_path(),
+        };
+        let mut command = Command::new(&argv[0]);
+        command.args(&argv[1..]);
+        self.start(command_line, command, &directory)
+    }
+
+    fn existing_project_path(&self, project_name: &str) -> Result<PathBuf, String> {
+        let project_path = self.config.get_project_path(project_name);
+        if project_path.exists() {
+            Ok(project_path)
+        } else {
+            Err(format!("Project '{}' does not exist!", project_name))
+        }
+    }
+
+    fn start(&self, name: &str, mut command: Command, directory: &Path) -> Result<JobInfo, String> {
+        let mut jobs = self.jobs.lock().unwrap();
+        let id = jobs.iter().map(|j| j.id).max().unwrap_or(0) + 1;
+
+        fs::create_dir_all(&self.log_dir)
+            .map_err(|e| format!("Failed to create {}: {}", self.log_dir.display(), e))?;
+        let log_path = self.log_dir.join(format!("job-{}-{}.log", id, Local::now().format("%Y%m%d-%H%M%S")));
+        let command_line = std::iter::once(command.get_program())
+            .chain(command.get_args())
+            .map(|a| a.to_string_lossy())
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let mut log = OpenOptions::new().create(true).append(true).open(&log_path)
+            .map_err(|e| format!("Failed to create {}: {}", log_path.display(), e))?;
+        let _ = writeln!(log, "$ {}  (in {})", command_line, directory.display());
+        let stderr_log = log.try_clone().map_err(|e| e.to_string())?;
+
+        command.current_dir(directory)
+            .stdin(Stdio::null())
+            .stdout(Stdio::from(log))
+            .stderr(Stdio::from(stderr_log));
+        detach_from_terminal(&mut command);
+
+        let child = command.spawn().map_err(|e| format!("Failed to start '{}': {}", command_line, e))?;
+        RUNNING_PIDS.lock().unwrap().push(child.id());
+
+        let job = Job {
+            id,
+            name: name.to_string(),
+            command_line,
+            log_path,
+            started: Instant::now(),
+            started_at: Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+            child,
+            exit_status: None,
+        };
+        let info = job.info();
+        jobs.push(job);
+        Ok(info)
+    }
+
+    pub fn list(&self) -> Vec<JobInfo> {
+        let mut jobs = self.jobs.lock().unwrap();
+        jobs.iter_mut().map(|job| {
+            job.refresh();
+            job.info()
+        }).collect()
+    }
+
+    pub fn running_count(&self) -> usize {
+        self.list().iter().filter(|j| j.running).count()
+    }
+
+    /// The last `lines` lines of the job's captured output
+    pub fn tail(&self, id: usize, lines: usize) -> Result<Vec<String>, String> {
+        let log_path = self.with_job(id, |job| job.log_path.clone())?;
+        let content = fs::read_to_string(&log_path)
+            .map_err(|e| format!("Failed to read {}: {}", log_path.display(), e))?;
+        let all: Vec<String> = content.lines().map(|l| l.to_string()).collect();
+        Ok(all[all.len().saturating_sub(lines)..].to_vec())
+    }
+
+    /// Output appended to the job's log since byte `offset`, and the new offset
+    pub fn read_log_from(&self, id: usize, offset: u64) -> Result<(String, u64), String> {
+        let log_path = self.with_job(id, |job| job.log_path.clone())?;
+        let mut file = File::open(&log_path).map_err(|e| format!("Failed to read {}: {}", log_path.display(), e))?;
+        let len = file.metadata().map(|m| m.len()).unwrap_or(0);
+        if len <= offset {
+            return Ok((String::new(), len));
+        }
+        let mut buffer = Vec::new();
+        file.seek(SeekFrom::Start(offset)).and_then(|_| file.read_to_end(&mut buffer)).map_err(|e| e.to_string())?;
+        Ok((String::from_utf8_lossy(&buffer).to_string(), offset + buffer.len() as u64))
+    }
+
+    /// Asks the job to shut down (SIGTERM / close), killing it if it is still running after a grace period
+    pub fn stop(&self, id: usize) -> Result<(), String> {
+        self.with_job(id, |job| {
+            if job.exit_status.is_some() {
+                return Err(format!("Job {} is not running", job.id));
+            }
+            signal_job(&job.child, false);
+            Ok(())
+        })??;
+        self.wait_or_kill(&[id]);
+        Ok(())
+    }
+
+    /// Kills the job and everything it started immediately
+    pub fn kill(&self, id: usize) -> Result<(), String> {
+        self.with_job(id, |job| {
+            if job.exit_status.is_some() {
+                return Err(format!("Job {} is not running", job.id));
+            }
+            kill_job(job);
+            Ok(())
+        })?
+    }
+
+    /// Forgets finished jobs; their log files are kept
+    pub fn remove_finished(&self) -> usize {
+        let mut jobs = self.jobs.lock().unwrap();
+        jobs.iter_mut().for_each(|job| job.refresh());
+        let before = jobs.len();
+        jobs.retain(|job| job.exit_status.is_none());
+        before - jobs.len()
+    }
+
+    /// Stops every running job, e.g. when the terminal exits. All of them are asked to shut down
+    /// at once and share one grace period.
+    pub fn stop_all(&self, theme: &ThemeConfig) {
+        let running: Vec<usize> = {
+            let mut jobs = self.jobs.lock().unwrap();
+            jobs.iter_mut()
+                .filter_map(|job| job.running())
+                .map(|job| {
+                    signal_job(&job.child, false);
+                    job.id
+                })
+                .collect()
+        };
+        if running.is_empty() {
+            return;
+        }
+        AnsiTheme::print_info(&format!(" Stopping {} background job(s)...\n", running.len()), theme);
+        self.wait_or_kill(&running);
+    }
+
+    // Waits for the jobs in `ids` to exit until the grace period is over, then kills the rest.
+    // The lock is only held while checking, so other calls are not blocked meanwhile.
+    fn wait_or_kill(&self, ids: &[usize]) {
+        let deadline = Instant::now() + STOP_GRACE;
+        loop {
+            let mut jobs = self.jobs.lock().unwrap();
+            let mut remaining = jobs.iter_mut()
+                .filter(|job| ids.contains(&job.id))
+                .filter_map(|job| job.running())
+                .peekable();
+            if remaining.peek().is_none() {
+                return;
+            }
+            if Instant::now() >= deadline {
+                remaining.for_each(kill_job);
+                return;
+            }
+            drop(jobs);
+            std::thread::sleep(Duration::from_millis(100));
+        }
+    }
+
+    fn with_job<T>(&self, id: usize, f: impl FnOnce(&mut Job) -> T) -> Result<T, String> {
+        let mut jobs = self.jobs.lock().unwrap();
+        match jobs.iter_mut().find(|job| job.id == id) {
+            Some(job) => Ok(f(job)),
+            None => Err(format!("No job with id {}", id)),
+        }
+    }
+}
+
+impl Drop for JobManager {
+    fn drop(&mut self) {
+        self.stop_all(&self.config.theme);
+    }
+}
+
+/// Kills every job still running; used when the terminal exits without unwinding (Ctrl-C, self-update)
+pub fn kill_all_jobs() {
+    let pids: Vec<u32> = std::mem::take(&mut *RUNNING_PIDS.lock().unwrap());
+    for pid in pids {
+        signal_pid(pid, true);
+    }
+}
+
+fn unregister(pid: u32) {
+    RUNNING_PIDS.lock().unwrap().retain(|&p| p != pid);
+}
+
+fn kill_job(job: &mut Job) {
+    signal_job(&job.child, true);
+    let _ = job.child.kill();
+    if let Ok(status) = job.child.wait() {
+        job.exit_status = Some(status);
+    }
+    unregister(job.child.id());
+}
+
+fn signal_job(child: &Child, force: bool) {
+    signal_pid(child.id(), force);
+}
+
+// Jobs run in their own process group (Unix) or console process group (Windows), so Ctrl-C
+// meant for a foreground build does not reach them, and stopping one stops all it started
+#[cfg(unix)]
+fn detach_from_terminal(command: &mut Command) {
+    use std::os::unix::process::CommandExt;
+    command.process_group(0);
+}
+
+#[cfg(windows)]
+fn detach_from_terminal(command: &mut Command) {
+    use std::os::windows::process::CommandExt;
+    const CREATE_NEW_PROCESS_GROUP: u32 = 0x0000_0200;
+    command.creation_flags(CREATE_NEW_PROCESS_GROUP);
+}
+
+#[cfg(unix)]
+fn signal_pid(pid: u32, force: bool) {
+    // A negative PID addresses the whole process group
+    let _ = Command::new("kill")
+        .args([if force { "-KILL" } else { "-TERM" }, "--", &format!("-{}", pid)])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status();
+}
+
+#[cfg(windows)]
+fn signal_pid(pid: u32, force: bool) {
+    let mut command = Command::new("taskkill");
+    command.args(["/T", "/PID", &pid.to_string()]);
+    if force {
+        command.arg("/F");
+    }
+    let _ = command.stdout(Stdio::null()).stderr(Stdio::null()).status();
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    fn manager(name: &str) -> (JobManager, TempDir) {
+        let root = TempDir::new(&format!("jobs_{}", name));
+        let mut manager = JobManager::new_with_config(AppConfig {
+            workspace_path: root.display().to_string(),
+            ..AppConfig::default()
+        });
+        manager.log_dir = root.join("logs");
+        (manager, root)
+    }
+
+    #[test]
+    fn captures_output_and_stops_running_jobs() {
+        let (manager, _root) = manager("lifecycle");
+
+        let quick = manager.start_command(None, "sh -c 'echo hello; echo oops >&2'").unwrap();
+        let server = manager.start_command(None, "sh -c 'echo serving; sleep 30'").unwrap();
+        std::thread::sleep(Duration::from_millis(500));
+
+        let jobs = manager.list();
+        assert!(!jobs[0].running);
+        assert_eq!(jobs[0].exit_code, Some(0));
+        assert!(jobs[1].running);
+        assert_eq!(manager.tail(quick.id, 2).unwrap(), vec!["hello", "oops"]);
+
+        manager.stop(server.id).unwrap();
+        assert_eq!(manager.running_count(), 0);
+        assert_eq!(manager.remove_finished(), 2);
+        assert!(manager.tail(server.id, 1).is_err());
+    }
+
+    #[test]
+    fn stops_all_jobs_within_one_grace_period_without_holding_the_lock() {
+        let (manager, _root) = manager("stop_all");
+        // Both ignore SIGTERM, so each has to wait out the grace period before being killed
+        for _ in 0..2 {
+            manager.start_command(None, "sh -c 'trap \"\" TERM; while true; do sleep 1; done'").unwrap();
+        }
+        std::thread::sleep(Duration::from_millis(300));
+
+        let started = Instant::now();
+        std::thread::scope(|scope| {
+            scope.spawn(|| manager.stop_all(&ThemeConfig::default()));
+            std::thread::sleep(Duration::from_millis(500));
+            let listed = Instant::now();
+            assert_eq!(manager.list().len(), 2);
+            assert!(listed.elapsed() < Duration::from_secs(1));
+        });
+        assert!(started.elapsed() < STOP_GRACE * 2);
+        assert_eq!(manager.running_count(), 0);
+    }
+}
@@ -0,0 +1,386 @@
+// logger.rs
+use std::cell::RefCell;
+use std::collections::hash_map::DefaultHasher;
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::hash::{Hash, Hasher};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::SystemTime;
+use std::backtrace::{Backtrace, BacktraceStatus};
+use chrono::{DateTime, Local, Utc};
+use serde::{Deserialize, Serialize};
+use crate::ansi_theme::AnsiTheme;
+use crate::config::{AppConfig, LoggingConfig, ThemeConfig};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl LogLevel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LogLevel::Debug => "DEBUG",
+            LogLevel::Info => "INFO",
+            LogLevel::Warn => "WARN",
+            LogLevel::Error => "ERROR",
+        }
+    }
+
+    pub fn parse(text: &str) -> Option<Self> {
+        match text.trim().to_uppercase().as_str() {
+            "DEBUG" => Some(LogLevel::Debug),
+            "INFO" => Some(LogLevel::Info),
+            "WARN" | "WARNING" => Some(LogLevel::Warn),
+            "ERROR" => Some(LogLevel::Error),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for LogLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// How lines are written to the log file; the console always gets text
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    #[default]
+    Text,
+    /// One JSON object per line
+    Json,
+}
+
+/// When the log file is moved aside and a new one started
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogRotation {
+    Never,
+    /// Once the file reaches `max_size_mb`
+    #[default]
+    Size,
+    /// On the first write of a new day
+    Daily,
+}
+
+/// One log line as written in the JSON-lines format
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LogRecord {
+    pub timestamp: String,
+    pub level: LogLevel,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context: Option<String>,
+    /// Shared by every line of one build, test or run
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub operation: Option<String>,
+    pub message: String,
+}
+
+impl LogRecord {
+    // e.g. "2024-05-01 12:00:00.000 INFO [build:app] (3f9a1c2e): Build completed"
+    pub fn to_text(&self) -> String {
+        let context = self.context.as_ref().map(|c| format!(" [{}]", c)).unwrap_or_default();
+        let operation = self.operation.as_ref().map(|id| format!(" ({})", id)).unwrap_or_default();
+        format!("{} {}{}{}: {}", self.timestamp, self.level, context, operation, self.message)
+    }
+
+    /// Reads a line written in either format; None for continuation lines such as backtraces
+    pub fn parse(line: &str) -> Option<Self> {
+        if line.starts_with('{') {
+            return serde_json::from_str(line).ok();
+        }
+
+        let (date, rest) = line.split_once(' ')?;
+        let (time, rest) = rest.split_once(' ')?;
+        let timestamp = format!("{} {}", date, time);
+        chrono::NaiveDateTime::parse_from_str(&timestamp, "%Y-%m-%d %H:%M:%S%.3f").ok()?;
+
+        let level_end = rest.find([' ', ':'])?;
+        let level = LogLevel::parse(&rest[..level_end])?;
+        let mut rest = &rest[level_end..];
+
+        // Contexts can contain brackets themselves, e.g. "build[release]:app"
+        let mut context = None;
+        if let Some(inner) = rest.strip_prefix(" [") {
+            let end = [inner.find("]: "), inner.find("] (")].into_iter().flatten().min()?;
+            context = Some(inner[..end].to_string());
+            rest = &inner[end + 1..];
+        }
+        let mut operation = None;
+        if let Some(inner) = rest.strip_prefix(" (") {
+            let end = inner.find("): ")?;
+            operation = Some(inner[..end].to_string());
+            rest = &inner[end + 1..];
+        }
+        let message = rest.strip_prefix(": ").or_else(|| rest.strip_prefix(':'))?;
+
+        Some(LogRecord { timestamp, level, context, operation, message: message.to_string() })
+    }
+}
+
+thread_local! {
+    static OPERATION: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+static OPERATION_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Gives every line logged on this thread the same correlation ID until it is dropped.
+/// Operations started inside another one (e.g. build info while packaging) keep the outer ID.
+pub struct Operation {
+    owner: bool,
+}
+
+impl Operation {
+    pub fn id() -> Option<String> {
+        OPERATION.with(|current| current.borrow().clone())
+    }
+}
+
+impl Drop for Operation {
+    fn drop(&mut self) {
+        if self.owner {
+            OPERATION.with(|current| *current.borrow_mut() = None);
+        }
+    }
+}
+
+pub fn begin_operation() -> Operation {
+    OPERATION.with(|current| {
+        let mut current = current.borrow_mut();
+        if current.is_some() {
+            return Operation { owner: false };
+        }
+        let mut hasher = DefaultHasher::new();
+        SystemTime::now().hash(&mut hasher);
+        std::process::id().hash(&mut hasher);
+        OPERATION_COUNTER.fetch_add(1, Ordering::Relaxed).hash(&mut hasher);
+        *current = Some(format!("{:08x}", hasher.finish() as u32));
+        Operation { owner: true }
+    })
+}
+
+#[derive(Debug, Clone)]
+pub struct BuildLogger {
+    log_file: Option<PathBuf>,
+    settings: LoggingConfig,
+    console_level: LogLevel,
+}
+
+impl BuildLogger {
+    pub fn new(log_file: Option<String>, verbose: bool, settings: LoggingConfig) -> Self {
+        let console_level = settings.console_level
+            .unwrap_or(if verbose { LogLevel::Debug } else { LogLevel::Warn });
+        BuildLogger { log_file: log_file.map(PathBuf::from), settings, console_level }
+    }
+
+    pub fn from_config(config: &AppConfig) -> Self {
+        Self::new(Some(config.log_file.clone()), config.verbose_logging, config.logging.clone())
+    }
+
+    pub fn log_file(&self) -> Option<&Path> {
+        self.log_file.as_deref()
+    }
+
+    pub fn log(&self, level: LogLevel, message: &str, context: Option<&str>, theme: &ThemeConfig) {
+        if level < self.settings.min_level() {
+            return;
+        }
+        let record = record(level, message, context);
+
+        if level >= self.console_level {
+            let line = format!("{}\n", record.to_text());
+            match level {
+                LogLevel::Error => AnsiTheme::print_error(&line, theme),
+                LogLevel::Warn => AnsiTheme::print_warning(&line, theme),
+                LogLevel::Info => AnsiTheme::print_info(&line, theme),
+                LogLevel::Debug => AnsiTheme::print_themed(&line, theme),
+            }
+        }
+
+        self.write_to_file(&record);
+    }
+
+    // Records a message in the log file only, e.g. child output that is already on screen
+    pub fn log_to_file(&self, level: LogLevel, message: &str, context: Option<&str>) {
+        if level >= self.settings.min_level() {
+            self.write_to_file(&record(level, message, context));
+        }
+    }
+
+    fn write_to_file(&self, record: &LogRecord) {
+        let Some(log_path) = &self.log_file else { return };
+        let line = match self.settings.format {
+            LogFormat::Text => record.to_text(),
+            LogFormat::Json => match serde_json::to_string(record) {
+                Ok(json) => json,
+                Err(_) => return,
+            },
+        };
+
+        self.rotate_if_needed(log_path);
+        if let Ok(mut file) = OpenOptions::new().create(true).append(true).open(log_path) {
+            let _ = writeln!(file, "{}", line);
+        }
+    }
+
+    // Moves the log file to `<file>.<date or timestamp>` when the rotation policy says so,
+    // then deletes the oldest rotated files beyond `keep_files`
+    fn rotate_if_needed(&self, log_path: &Path) {
+        let Ok(metadata) = fs::metadata(log_path) else { return };
+        let suffix = match self.settings.rotation {
+            LogRotation::Never => return,
+            LogRotation::Size => {
+                if metadata.len() < self.settings.max_size_bytes() {
+                    return;
+                }
+                Local::now().format("%Y%m%d-%H%M%S").to_string()
+            }
+            LogRotation::Daily => {
+                let Ok(modified) = metadata.modified() else { return };
+                let modified: DateTime<Local> = modified.into();
+                if modified.date_naive() >= Local::now().date_naive() {
+                    return;
+                }
+                modified.format("%Y-%m-%d").to_string()
+            }
+        };
+
+        let file_name = log_path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        let mut rotated = log_path.with_file_name(format!("{}.{}", file_name, suffix));
+        let mut counter = 1;
+        while rotated.exists() {
+            rotated = log_path.with_file_name(format!("{}.{}-{}", file_name, suffix, counter));
+            counter += 1;
+        }
+        if fs::rename(log_path, &rotated).is_err() {
+            return;
+        }
+
+        let files = rotated_files(log_path);
+        let excess = files.len().saturating_sub(self.settings.keep_files());
+        for old in &files[..excess] {
+            let _ = fs::remove_file(old);
+        }
+    }
+
+    pub fn log_error_with_backtrace(&self, error: &str, context: Option<&str>, theme: &ThemeConfig) {
+        let backtrace = Backtrace::capture();
+        self.log(LogLevel::Error, error, context, theme);
+
+        if backtrace.status() == BacktraceStatus::Captured {
+            self.log(LogLevel::Debug, &format!("Backtrace:\n{}", backtrace), context, theme);
+        }
+    }
+
+    pub fn log_command(&self, command: &str, args: &[String], context: Option<&str>, theme: &ThemeConfig) {
+        let full_command = if args.is_empty() {
+            command.to_string()
+        } else {
+            format!("{} {}", command, args.join(" "))
+        };
+        self.log(LogLevel::Debug, &format!("Executing: {}", full_command), context, theme);
+    }
+}
+
+fn record(level: LogLevel, message: &str, context: Option<&str>) -> LogRecord {
+    LogRecord {
+        timestamp: Utc::now().format("%Y-%m-%d %H:%M:%S%.3f").to_string(),
+        level,
+        context: context.map(|c| c.to_string()),
+        operation: Operation::id(),
+        message: message.to_string(),
+    }
+}
+
+/// Rotated copies of `log_path` (`build_tool.log.2024-05-01`, ...), oldest first
+pub fn rotated_files(log_path: &Path) -> Vec<PathBuf> {
+    let Some(dir) = log_path.parent().map(|d| if d.as_os_str().is_empty() { Path::new(".") } else { d }) else {
+        return Vec::new();
+    };
+    let prefix = format!("{}.", log_path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default());
+    let mut files: Vec<(SystemTime, PathBuf)> = fs::read_dir(dir)
+        .map(|entries| entries
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_name().to_string_lossy().starts_with(&prefix))
+            .filter_map(|entry| Some((entry.metadata().ok()?.modified().ok()?, entry.path())))
+            .collect())
+        .unwrap_or_default();
+    files.sort();
+    files.into_iter().map(|(_, path)| path).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    fn logger(path: &Path, settings: LoggingConfig) -> BuildLogger {
+        BuildLogger::new(Some(path.display().to_string()), false, LoggingConfig { console_level: Some(LogLevel::Error), ..settings })
+    }
+
+    #[test]
+    fn filters_levels_and_groups_lines_by_operation() {
+        let dir = TempDir::new("logger");
+        let path = dir.join("build_tool.log");
+        let logger = logger(&path, LoggingConfig { level: Some(LogLevel::Info), format: LogFormat::Json, ..LoggingConfig::default() });
+        let theme = ThemeConfig::default();
+
+        {
+            let _operation = begin_operation();
+            let _nested = begin_operation();
+            logger.log(LogLevel::Debug, "dropped", Some("build:app"), &theme);
+            logger.log(LogLevel::Info, "started", Some("build:app"), &theme);
+            logger.log_to_file(LogLevel::Warn, "finished", Some("build:app"));
+        }
+        logger.log(LogLevel::Info, "outside", None, &theme);
+
+        let records: Vec<LogRecord> = fs::read_to_string(&path).unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        let messages: Vec<&str> = records.iter().map(|r| r.message.as_str()).collect();
+        assert_eq!(messages, vec!["started", "finished", "outside"]);
+        assert!(records[0].operation.is_some());
+        assert_eq!(records[0].operation, records[1].operation);
+        assert_eq!(records[2].operation, None);
+        assert_eq!(records[1].level, LogLevel::Warn);
+    }
+
+    #[test]
+    fn parses_text_and_json_lines() {
+        let record = LogRecord::parse("2024-05-01 12:00:00.250 WARN [build[release]:app] (3f9a1c2e): Slow: 12s").unwrap();
+        assert_eq!(record.level, LogLevel::Warn);
+        assert_eq!(record.context.as_deref(), Some("build[release]:app"));
+        assert_eq!(record.operation.as_deref(), Some("3f9a1c2e"));
+        assert_eq!(record.message, "Slow: 12s");
+        assert_eq!(LogRecord::parse(&record.to_text()), Some(record.clone()));
+        assert_eq!(LogRecord::parse(&serde_json::to_string(&record).unwrap()), Some(record));
+
+        let plain = LogRecord::parse("2024-05-01 12:00:00.000 INFO: no context").unwrap();
+        assert_eq!((plain.context, plain.operation, plain.message.as_str()), (None, None, "no context"));
+        assert_eq!(LogRecord::parse("   0: std::backtrace::Backtrace::capture"), None);
+    }
+
+    #[test]
+    fn rotates_by_size_and_keeps_the_newest_files() {
+        let dir = TempDir::new("rotation");
+        let path = dir.join("build_tool.log");
+        let logger = logger(&path, LoggingConfig { max_size_mb: Some(1), keep_files: Some(2), ..LoggingConfig::default() });
+
+        for _ in 0..3 {
+            fs::write(&path, vec![b'x'; 1024 * 1024]).unwrap();
+            logger.log_to_file(LogLevel::Info, "after rotation", None);
+            assert_eq!(fs::read_to_string(&path).unwrap().lines().count(), 1);
+        }
+        assert_eq!(rotated_files(&path).len(), 2);
+    }
+}
@@ -1,4 +1,7 @@
 // main.rs
+// Several manager helpers are only reachable from some front ends (menu or CLI)
+#![allow(dead_code)]
+
 mod menu;
 mod projects;
 mod analyzer;
@@ -6,19 +9,53 @@ mod file_manager;
 mod project_creator;
 mod build_system;
 mod self_update;
+mod backup;
+mod version;
 mod git_manager;
 mod config;
 mod python_manager;
 mod ansi_theme;  
+mod cli;
+mod output;
+mod opener;
+mod diagnostics;
+mod watcher;
+mod project_settings;
+mod test_report;
+mod bench_report;
+mod build_history;
+mod process;
+mod jobs;
+mod archive;
+mod packager;
+mod size_report;
+mod timings_report;
+mod logger;
+#[cfg(test)]
+mod test_support;
 
 fn main() {
     // ENABLE ANSI SUPPORT FIRST
     ansi_theme::AnsiTheme::enable_ansi_support();
 
+    // Global flags (e.g. --json) must be applied before anything is printed
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    cli::apply_global_flags(&mut args);
+
+    // Ctrl-C stops a running build/test/program rather than the whole terminal
+    process::install_interrupt_handler();
+
     // Initialize configuration first
     let config_manager = config::ConfigManager::new();
     let config = config_manager.get_config().clone();
 
+    // Any command-line arguments select the non-interactive front end
+    if !args.is_empty() || output::is_json() {
+        let exit_code = cli::run(&args, config);
+        config::reset_terminal_colors();
+        std::process::exit(exit_code);
+    }
+
     // APPLY THEME AT STARTUP - This sets the persistent background and foreground
     ansi_theme::AnsiTheme::apply_theme(&config.theme);
 
@@ -8,34 +8,43 @@ use crate::build_system::BuildSystem;
 use crate::self_update::SelfUpdater;
 use crate::git_manager::GitManager;
 use crate::ansi_theme::AnsiTheme;
-pub use editor::open_file_in_editor;
+use crate::jobs::JobManager;
 
 // Import the new menu modules
 mod editor;
+mod log_viewer;
 mod menu_build_run;
+mod menu_diagnostics;
+mod menu_build_configurations;
+mod menu_run_configurations;
+mod menu_tests;
+mod menu_bench;
 mod menu_project_creator;
 mod menu_file_management;
 mod menu_cargo_management;
 mod menu_git_management;
 mod menu_python_management;
+mod menu_jobs;
+mod menu_self_update;
 
 // Re-export the menu functions
+pub use editor::open_file_in_editor_with_cursor;
 pub use menu_build_run::build_run_menu;
 pub use menu_project_creator::create_project_menu;
 pub use menu_file_management::file_management_menu;
 pub use menu_cargo_management::cargo_management_menu;
 pub use menu_git_management::git_management_menu;
 pub use menu_python_management::python_management_menu;
+pub use menu_jobs::jobs_menu;
+pub use menu_self_update::self_update_menu;
 
 pub fn main_menu_with_config(config: crate::config::AppConfig) {
     let file_manager = FileManager::new_with_config(config.clone());
     let project_creator = ProjectCreator::new_with_config(config.clone()); 
-    let build_system = BuildSystem::new(
-        Some(config.log_file.clone()), 
-        config.verbose_logging
-    );
+    let build_system = BuildSystem::new_with_config(config.clone());
     let git_manager = GitManager::new_with_config(config.clone());
     let python_manager = crate::python_manager::PythonManager::new_with_config(config.clone());
+    let job_manager = JobManager::new_with_config(config.clone());
     let config_manager = crate::config::ConfigManager::new();
     let mut current_project: Option<String> = None;
 
@@ -60,14 +69,15 @@ pub fn main_menu_with_config(config: crate::config::AppConfig) {
         AnsiTheme::print_themed("4) Cargo.toml Management\n", &config.theme);
         AnsiTheme::print_themed("5) Create New Rust Project\n", &config.theme);
         AnsiTheme::print_themed("6) Build & Run Projects\n", &config.theme);
-        AnsiTheme::print_themed("7) Self-Update Terminal\n", &config.theme);
+        AnsiTheme::print_themed("7) Self-Update Terminal (update, backups & rollback)\n", &config.theme);
         AnsiTheme::print_themed("8) Git Management\n", &config.theme);
         AnsiTheme::print_themed("9) Version Management\n", &config.theme);
         AnsiTheme::print_themed("10) Select Current Project\n", &config.theme);
-        AnsiTheme::print_themed("11) Open Build Log File\n", &config.theme);
+        AnsiTheme::print_themed("11) View Build Log (filter, search, follow)\n", &config.theme);
         AnsiTheme::print_themed("12) Python Management\n", &config.theme);
         AnsiTheme::print_themed("13) Configuration Settings\n", &config.theme);
         AnsiTheme::print_themed("14) Open File in Text Editor\n", &config.theme);
+        AnsiTheme::print_themed(&format!("15) Background Jobs ({} running)\n", job_manager.running_count()), &config.theme);
         AnsiTheme::print_themed("Q) Quit\n", &config.theme);
 
         AnsiTheme::print_themed("Enter choice: ", &config.theme);
@@ -96,12 +106,9 @@ pub fn main_menu_with_config(config: crate::config::AppConfig) {
                         None => continue,
                     },
                 };
-                build_run_menu(&build_system, &project, &config.theme);
-            }
-            "7" => {
-                let updater = SelfUpdater::new();
-                updater.check_for_updates(&config.theme);
+                build_run_menu(&build_system, &python_manager, &job_manager, &project, &config.theme);
             }
+            "7" => self_update_menu(&SelfUpdater::new_with_config(config.clone()), &config.theme),
             "8" => git_management_menu(&git_manager, &current_project, &config.theme),
             "9" => {
                 if let Some(project) = &current_project {
@@ -117,16 +124,7 @@ pub fn main_menu_with_config(config: crate::config::AppConfig) {
                     current_project = Some(project);
                 }
             }
-            "11" => {
-                let build_system = BuildSystem::new(
-                    Some(config.log_file.clone()), 
-                    config.verbose_logging
-                );
-                 match build_system.open_log_file(&config.theme) {
-                    Ok(()) => AnsiTheme::print_success(" Log file opened\n", &config.theme),
-                    Err(e) => AnsiTheme::print_error(&format!("Error: {}\n", e), &config.theme),
-                }
-            }
+            "11" => view_build_log(&build_system, None, &config.theme),
             "12" => python_management_menu(&python_manager, &current_project, &config.theme),
             "13" => {
                 config_menu(&config_manager, &config.theme);
@@ -144,6 +142,7 @@ pub fn main_menu_with_config(config: crate::config::AppConfig) {
 	            AnsiTheme::print_error(" No project selected.\n", &config.theme);
 	        }
 	    }
+            "15" => jobs_menu(&job_manager, &python_manager, &current_project, &config.theme),
             "Q" | "q" => {
                 AnsiTheme::print_themed("Goodbye!\n", &config.theme);
                 break;
@@ -153,6 +152,21 @@ pub fn main_menu_with_config(config: crate::config::AppConfig) {
     }
 }
 
+/// Opens the build log in the log viewer, optionally filtered to a context such as a project name.
+/// Pressing `e` in the viewer hands the file to the configured external program instead.
+fn view_build_log(build_system: &BuildSystem, context: Option<&str>, theme: &crate::config::ThemeConfig) {
+    let filter = log_viewer::LogFilter { context: context.map(str::to_string), ..Default::default() };
+    match log_viewer::view_log_file(build_system.get_log_file_path(), filter, theme.clone()) {
+        Ok(log_viewer::ViewerExit::Quit) => {}
+        Ok(log_viewer::ViewerExit::OpenExternally) => {
+            if let Err(e) = build_system.open_log_file(theme) {
+                AnsiTheme::print_error(&format!("Error: {}\n", e), theme);
+            }
+        }
+        Err(e) => AnsiTheme::print_error(&format!("Log viewer error: {}\n", e), theme),
+    }
+}
+
 // Updated config menu with theme support
 fn config_menu(config_manager: &crate::config::ConfigManager, theme: &crate::config::ThemeConfig) {
     use std::io::{self, Write};
@@ -189,23 +203,12 @@ fn config_menu(config_manager: &crate::config::ConfigManager, theme: &crate::con
                 config_manager.show_config(theme);
             }
             "3" => {
-                // Open config file in default editor
+                // Open config file in the configured editor
                 let config_path = crate::config::ConfigManager::get_config_path();
                 if config_path.exists() {
-                    #[cfg(target_os = "windows")]
-                    let _ = std::process::Command::new("notepad.exe")
-                        .arg(&config_path)
-                        .status();
-                    
-                    #[cfg(target_family = "unix")]
-                    let _ = std::process::Command::new("xdg-open")
-                        .arg(&config_path)
-                        .status();
-                    
-                    #[cfg(target_os = "macos")]
-                    let _ = std::process::Command::new("open")
-                        .arg(&config_path)
-                        .status();
+                    if let Err(e) = crate::opener::open_file(&config_path, None, config_manager.get_config()) {
+                        AnsiTheme::print_error(&format!("Error: {}\n", e), theme);
+                    }
                 } else {
                     AnsiTheme::print_warning(" Config file not found\n", theme);
                 }
@@ -72,8 +72,8 @@ pub fn paste_from_clipboard(editor: &mut TextEditor) {
             
             editor.content[editor.cursor_position.1] = format!("{}{}", prefix, lines[0]);
             
-            for i in 1..lines.len() - 1 {
-                editor.content.insert(editor.cursor_position.1 + i, lines[i].to_string());
+            for (i, middle_line) in lines.iter().enumerate().take(lines.len() - 1).skip(1) {
+                editor.content.insert(editor.cursor_position.1 + i, middle_line.to_string());
             }
             
             let last_line_index = editor.cursor_position.1 + lines.len() - 1;
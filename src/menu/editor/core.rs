@@ -5,9 +5,9 @@ use crossterm::{
 };
 use std::io::{stdout};
 use std::path::PathBuf;
-use crate::ansi_theme::AnsiTheme;
 use crate::config::ThemeConfig;
 
+use super::cursor;
 use super::input;
 use super::render;
 
@@ -53,6 +53,12 @@ impl TextEditor {
             self.content.push(String::new());
         }
         
+        // Keep a preset cursor (e.g. from open_file_in_editor_with_cursor) inside the loaded content
+        let row = self.cursor_position.1.min(self.content.len() - 1);
+        let col = self.cursor_position.0.min(self.content[row].chars().count());
+        self.cursor_position = (col, row);
+        cursor::adjust_viewport_to_cursor_smooth(self);
+
         self.modified = false;
         self.desired_column = self.cursor_position.0;
         self.clear_selection();
@@ -116,6 +122,13 @@ impl TextEditor {
 }
 
 pub fn open_file_in_editor(file_path: PathBuf, theme: ThemeConfig) -> Result<(), String> {
+    open_file_in_editor_with_cursor(file_path, (0, 0), theme)
+}
+
+/// Opens the editor with an initial zero-based `cursor_position` of (column, row).
+/// Positions past the end of a line or of the file are clamped once it is loaded.
+pub fn open_file_in_editor_with_cursor(file_path: PathBuf, cursor_position: (usize, usize), theme: ThemeConfig) -> Result<(), String> {
     let mut editor = TextEditor::new(file_path, theme);
+    editor.cursor_position = cursor_position;
     editor.run_editor()
 }
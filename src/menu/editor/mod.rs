@@ -4,5 +4,4 @@ mod render;
 mod clipboard;
 mod cursor;
 
-pub use core::TextEditor;
-pub use core::open_file_in_editor;
+pub use core::{open_file_in_editor, open_file_in_editor_with_cursor};
@@ -0,0 +1,322 @@
+use crossterm::{
+    event::{self, Event},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use std::fs::File;
+use std::io::{stdout, Read, Seek, SeekFrom};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use crate::config::ThemeConfig;
+
+use super::filter::{self, LogFilter, LogLine};
+use super::input;
+use super::render;
+
+// How often follow mode checks the file for new lines
+const FOLLOW_INTERVAL: Duration = Duration::from_millis(250);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Prompt {
+    Search,
+    Context,
+    TimeRange,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ViewerExit {
+    Quit,
+    /// The user asked for the log in the configured external editor
+    OpenExternally,
+}
+
+pub struct LogViewer {
+    pub file_path: PathBuf,
+    pub lines: Vec<LogLine>,
+    /// Indices into `lines` that pass the filter
+    pub visible: Vec<usize>,
+    pub filter: LogFilter,
+    pub search: String,
+    pub prompt: Option<Prompt>,
+    pub prompt_input: String,
+    /// Selection and search to restore when an incremental search is cancelled
+    pub search_origin: (usize, String),
+    /// Position in `visible`
+    pub selected: usize,
+    pub top: usize,
+    pub left: usize,
+    pub follow: bool,
+    pub message: Option<String>,
+    pub theme: ThemeConfig,
+    pub exit: Option<ViewerExit>,
+    pub needs_redraw: bool,
+    pub previous_terminal_size: (u16, u16),
+    read_offset: u64,
+    partial_line: String,
+}
+
+impl LogViewer {
+    pub fn new(file_path: PathBuf, filter: LogFilter, theme: ThemeConfig) -> Self {
+        Self {
+            file_path,
+            lines: Vec::new(),
+            visible: Vec::new(),
+            filter,
+            search: String::new(),
+            prompt: None,
+            prompt_input: String::new(),
+            search_origin: (0, String::new()),
+            selected: 0,
+            top: 0,
+            left: 0,
+            follow: false,
+            message: None,
+            theme,
+            exit: None,
+            needs_redraw: true,
+            previous_terminal_size: (0, 0),
+            read_offset: 0,
+            partial_line: String::new(),
+        }
+    }
+
+    /// Reads the whole file again, e.g. after it was rotated
+    pub fn reload(&mut self) -> Result<(), String> {
+        let current = self.visible.get(self.selected).copied().unwrap_or(0);
+        self.lines.clear();
+        self.visible.clear();
+        self.read_offset = 0;
+        self.partial_line.clear();
+        self.read_new_lines()?;
+        self.select_line(current);
+        Ok(())
+    }
+
+    /// Appends lines written since the last read. Returns true when there were any.
+    /// A missing file reads as empty so follow mode can wait for it to be created.
+    pub fn read_new_lines(&mut self) -> Result<bool, String> {
+        let mut file = match File::open(&self.file_path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(false),
+            Err(e) => return Err(format!("Failed to open {}: {}", self.file_path.display(), e)),
+        };
+        let length = file.metadata().map(|m| m.len()).unwrap_or(0);
+        if length < self.read_offset {
+            // Truncated or rotated: start over from the new file
+            self.lines.clear();
+            self.visible.clear();
+            self.selected = 0;
+            self.top = 0;
+            self.read_offset = 0;
+            self.partial_line.clear();
+        }
+        if length == self.read_offset {
+            return Ok(false);
+        }
+
+        file.seek(SeekFrom::Start(self.read_offset))
+            .map_err(|e| format!("Failed to read {}: {}", self.file_path.display(), e))?;
+        let mut bytes = Vec::new();
+        file.read_to_end(&mut bytes)
+            .map_err(|e| format!("Failed to read {}: {}", self.file_path.display(), e))?;
+        self.read_offset += bytes.len() as u64;
+
+        // Keep an unfinished last line until the logger writes its newline
+        let mut text = std::mem::take(&mut self.partial_line);
+        text.push_str(&String::from_utf8_lossy(&bytes));
+        let complete = match text.rfind('\n') {
+            Some(end) => {
+                self.partial_line = text[end + 1..].to_string();
+                &text[..end]
+            }
+            None => {
+                self.partial_line = text;
+                return Ok(false);
+            }
+        };
+
+        let new_lines = filter::parse_lines(complete.lines(), self.lines.last());
+        let first_new = self.lines.len();
+        self.lines.extend(new_lines);
+        for index in first_new..self.lines.len() {
+            if self.filter.matches(&self.lines[index]) {
+                self.visible.push(index);
+            }
+        }
+        Ok(self.lines.len() > first_new)
+    }
+
+    /// Recomputes the visible lines, keeping the selection on the same line where possible
+    pub fn apply_filter(&mut self) {
+        let current = self.visible.get(self.selected).copied().unwrap_or(0);
+        self.visible = (0..self.lines.len()).filter(|&i| self.filter.matches(&self.lines[i])).collect();
+        self.select_line(current);
+    }
+
+    // Selects the first visible line at or after `index` in the file (the last line when following)
+    fn select_line(&mut self, index: usize) {
+        self.selected = if self.follow {
+            self.last_position()
+        } else {
+            self.visible.iter().position(|&i| i >= index).unwrap_or_else(|| self.last_position())
+        };
+    }
+
+    pub fn last_position(&self) -> usize {
+        self.visible.len().saturating_sub(1)
+    }
+
+    pub fn selected_line(&self) -> Option<&LogLine> {
+        self.visible.get(self.selected).map(|&i| &self.lines[i])
+    }
+
+    pub fn move_selection(&mut self, delta: isize) {
+        let target = self.selected as isize + delta;
+        self.selected = target.clamp(0, self.last_position() as isize) as usize;
+        // Moving away from the end stops following, like scrolling up in `less +F`
+        if self.selected < self.last_position() {
+            self.follow = false;
+        }
+    }
+
+    /// Selects the next line (or previous, when `forward` is false) containing the search text,
+    /// starting with `from` itself and wrapping around the end
+    pub fn find_match(&mut self, from: usize, forward: bool) -> bool {
+        let count = self.visible.len();
+        if self.search.is_empty() || count == 0 {
+            return false;
+        }
+        for step in 0..count {
+            let position = if forward { (from + step) % count } else { (from + count - step % count) % count };
+            if !match_ranges(&self.lines[self.visible[position]].text, &self.search).is_empty() {
+                self.selected = position;
+                self.follow = false;
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Keeps the selected line on screen
+    pub fn scroll_to_selection(&mut self, page_height: usize) {
+        if self.selected < self.top {
+            self.top = self.selected;
+        } else if page_height > 0 && self.selected >= self.top + page_height {
+            self.top = self.selected + 1 - page_height;
+        }
+    }
+
+    pub fn run_viewer(&mut self) -> Result<ViewerExit, String> {
+        self.reload()?;
+        // Newest entries are the interesting ones
+        self.selected = self.last_position();
+
+        enable_raw_mode().map_err(|e| format!("Failed to enable raw mode: {}", e))?;
+        execute!(stdout(), EnterAlternateScreen).map_err(|e| format!("Failed to enter alternate screen: {}", e))?;
+
+        let result = self.viewer_loop();
+
+        disable_raw_mode().map_err(|e| format!("Failed to disable raw mode: {}", e))?;
+        execute!(stdout(), crossterm::cursor::Show, LeaveAlternateScreen).map_err(|e| format!("Failed to leave alternate screen: {}", e))?;
+
+        result
+    }
+
+    fn viewer_loop(&mut self) -> Result<ViewerExit, String> {
+        let mut last_follow_check = Instant::now();
+        loop {
+            let current_size = crossterm::terminal::size()
+                .map_err(|e| format!("Failed to get terminal size: {}", e))?;
+
+            if self.follow && last_follow_check.elapsed() >= FOLLOW_INTERVAL {
+                last_follow_check = Instant::now();
+                if self.read_new_lines()? {
+                    self.selected = self.last_position();
+                    self.needs_redraw = true;
+                }
+            }
+
+            if self.needs_redraw || current_size != self.previous_terminal_size {
+                self.scroll_to_selection(render::page_height(current_size));
+                render::draw_interface(self, current_size)?;
+                self.previous_terminal_size = current_size;
+                self.needs_redraw = false;
+            }
+
+            if event::poll(Duration::from_millis(16))
+                .map_err(|e| format!("Failed to poll event: {}", e))?
+            {
+                if let Event::Key(key_event) = event::read()
+                    .map_err(|e| format!("Failed to read event: {}", e))?
+                {
+                    input::handle_key_event(self, key_event);
+                }
+            }
+
+            if let Some(exit) = self.exit {
+                return Ok(exit);
+            }
+        }
+    }
+}
+
+/// Character ranges in `text` matching `query`, ignoring case
+pub fn match_ranges(text: &str, query: &str) -> Vec<(usize, usize)> {
+    let fold = |c: char| c.to_lowercase().next().unwrap_or(c);
+    let text: Vec<char> = text.chars().map(fold).collect();
+    let query: Vec<char> = query.chars().map(fold).collect();
+    if query.is_empty() || query.len() > text.len() {
+        return Vec::new();
+    }
+
+    let mut ranges = Vec::new();
+    let mut start = 0;
+    while start + query.len() <= text.len() {
+        if text[start..start + query.len()] == query[..] {
+            ranges.push((start, start + query.len()));
+            start += query.len();
+        } else {
+            start += 1;
+        }
+    }
+    ranges
+}
+
+/// Opens the log viewer on `file_path` with an initial filter, e.g. a project's context
+pub fn view_log_file(file_path: PathBuf, filter: LogFilter, theme: ThemeConfig) -> Result<ViewerExit, String> {
+    let mut viewer = LogViewer::new(file_path, filter, theme);
+    viewer.run_viewer()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+    use std::io::Write;
+
+    #[test]
+    fn follows_appended_lines_and_rotation() {
+        let dir = TempDir::new("viewer");
+        let path = dir.join("build_tool.log");
+        std::fs::write(&path, "2024-05-01 12:00:00.000 INFO [build:app]: Starting build\n2024-05-01 12:00:01.000 ERR").unwrap();
+
+        let mut viewer = LogViewer::new(path.clone(), LogFilter::default(), ThemeConfig::default());
+        viewer.reload().unwrap();
+        assert_eq!(viewer.lines.len(), 1);
+
+        let mut file = std::fs::OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(b"OR [build:app]: Build failed\n  at main.rs:3\n").unwrap();
+        assert!(viewer.read_new_lines().unwrap());
+        assert_eq!(viewer.visible, vec![0, 1, 2]);
+        assert_eq!(viewer.lines[2].level, Some(crate::logger::LogLevel::Error));
+
+        viewer.search = "FAILED".to_string();
+        assert!(viewer.find_match(0, true));
+        assert_eq!(viewer.selected, 1);
+        assert_eq!(match_ranges("Build failed, failed again", "failed"), vec![(6, 12), (14, 20)]);
+
+        std::fs::write(&path, "2024-05-01 13:00:00.000 WARN: Rotated\n").unwrap();
+        assert!(viewer.read_new_lines().unwrap());
+        assert_eq!(viewer.lines.len(), 1);
+    }
+}
@@ -0,0 +1,193 @@
+use chrono::{DateTime, Duration, NaiveDateTime, Utc};
+use crate::logger::{LogLevel, LogRecord};
+
+// Log timestamps are UTC and sort as text, e.g. "2024-05-01 12:00:00.250"
+const TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S%.3f";
+
+/// One row of the viewer. Lines that are not log records themselves (backtraces, multi-line
+/// messages) take the timestamp, level, context and operation of the record above them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogLine {
+    pub timestamp: String,
+    pub level: Option<LogLevel>,
+    pub context: Option<String>,
+    pub operation: Option<String>,
+    pub text: String,
+}
+
+/// Splits raw log file lines into viewer rows
+pub fn parse_lines<'a>(lines: impl Iterator<Item = &'a str>, previous: Option<&LogLine>) -> Vec<LogLine> {
+    let mut parsed: Vec<LogLine> = Vec::new();
+    for raw in lines {
+        match LogRecord::parse(raw) {
+            Some(record) => {
+                let mut message_lines = record.message.lines();
+                let first = LogRecord { message: message_lines.next().unwrap_or_default().to_string(), ..record.clone() };
+                parsed.push(LogLine {
+                    timestamp: record.timestamp.clone(),
+                    level: Some(record.level),
+                    context: record.context.clone(),
+                    operation: record.operation.clone(),
+                    text: first.to_text(),
+                });
+                for continuation in message_lines {
+                    parsed.push(LogLine { text: format!("    {}", continuation), ..parsed[parsed.len() - 1].clone() });
+                }
+            }
+            None => {
+                let line = match parsed.last().or(previous) {
+                    Some(above) => LogLine { text: raw.to_string(), ..above.clone() },
+                    None => LogLine { timestamp: String::new(), level: None, context: None, operation: None, text: raw.to_string() },
+                };
+                parsed.push(line);
+            }
+        }
+    }
+    parsed
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LogFilter {
+    pub min_level: Option<LogLevel>,
+    /// Case-insensitive part of the context, e.g. a project name or "build:"
+    pub context: Option<String>,
+    pub operation: Option<String>,
+    pub since: Option<String>,
+    pub until: Option<String>,
+}
+
+impl LogFilter {
+    pub fn matches(&self, line: &LogLine) -> bool {
+        if let Some(min_level) = self.min_level {
+            if line.level.is_none_or(|level| level < min_level) {
+                return false;
+            }
+        }
+        if let Some(context) = &self.context {
+            let matched = line.context.as_ref().is_some_and(|c| c.to_lowercase().contains(&context.to_lowercase()));
+            if !matched {
+                return false;
+            }
+        }
+        if self.operation.is_some() && line.operation != self.operation {
+            return false;
+        }
+        if self.since.as_ref().is_some_and(|since| line.timestamp.as_str() < since.as_str()) {
+            return false;
+        }
+        if self.until.as_ref().is_some_and(|until| line.timestamp.as_str() > until.as_str()) {
+            return false;
+        }
+        true
+    }
+
+    /// Lowest level shown, in the order the `l` key steps through them
+    pub fn next_level(&mut self) {
+        self.min_level = match self.min_level {
+            None => Some(LogLevel::Info),
+            Some(LogLevel::Debug) | Some(LogLevel::Info) => Some(LogLevel::Warn),
+            Some(LogLevel::Warn) => Some(LogLevel::Error),
+            Some(LogLevel::Error) => None,
+        };
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == LogFilter::default()
+    }
+
+    /// e.g. "level>=WARN context~app 12:00.." for the status bar
+    pub fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(level) = self.min_level {
+            parts.push(format!("level>={}", level));
+        }
+        if let Some(context) = &self.context {
+            parts.push(format!("context~{}", context));
+        }
+        if let Some(operation) = &self.operation {
+            parts.push(format!("op={}", operation));
+        }
+        if self.since.is_some() || self.until.is_some() {
+            parts.push(format!("time {}..{}", self.since.as_deref().unwrap_or(""), self.until.as_deref().unwrap_or("")));
+        }
+        if parts.is_empty() { "no filters".to_string() } else { parts.join(" ") }
+    }
+}
+
+/// Parses "FROM..TO", "FROM.." or "..TO" (a single value means FROM). Each end is either
+/// relative to `now` ("30m", "2h", "1d") or a UTC timestamp prefix ("2024-05-01 12:00").
+pub fn parse_time_range(input: &str, now: DateTime<Utc>) -> Result<(Option<String>, Option<String>), String> {
+    let (from, to) = input.split_once("..").unwrap_or((input, ""));
+    Ok((parse_time(from.trim(), now, false)?, parse_time(to.trim(), now, true)?))
+}
+
+fn parse_time(text: &str, now: DateTime<Utc>, upper: bool) -> Result<Option<String>, String> {
+    if text.is_empty() {
+        return Ok(None);
+    }
+
+    let relative = text.char_indices().last().and_then(|(i, unit)| {
+        let amount: i64 = text[..i].parse().ok()?;
+        match unit {
+            's' => Some(Duration::seconds(amount)),
+            'm' => Some(Duration::minutes(amount)),
+            'h' => Some(Duration::hours(amount)),
+            'd' => Some(Duration::days(amount)),
+            _ => None,
+        }
+    });
+    if let Some(ago) = relative {
+        return Ok(Some((now - ago).format(TIMESTAMP_FORMAT).to_string()));
+    }
+
+    // Bare times are for today
+    let text = if text.len() <= 8 && text.contains(':') {
+        format!("{} {}", now.format("%Y-%m-%d"), text)
+    } else {
+        text.to_string()
+    };
+
+    // Fill in the rest of a partial timestamp so "12:00" as an upper bound includes 12:00:59.
+    // Bounds are only compared as text, so the upper fill need not be a real date.
+    const LOWER: &str = "0000-01-01 00:00:00.000";
+    const UPPER: &str = "0000-12-31 23:59:59.999";
+    let invalid = || format!("Invalid time '{}' (use 30m, 2h, 1d, 14:30 or 2024-05-01 14:30)", text);
+    if text.len() > LOWER.len() || !text.is_char_boundary(text.len()) {
+        return Err(invalid());
+    }
+    NaiveDateTime::parse_from_str(&format!("{}{}", text, &LOWER[text.len()..]), TIMESTAMP_FORMAT)
+        .map_err(|_| invalid())?;
+    let template = if upper { UPPER } else { LOWER };
+    Ok(Some(format!("{}{}", text, &template[text.len()..])))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filters_by_level_context_operation_and_time() {
+        let lines = parse_lines([
+            "2024-05-01 12:00:00.000 INFO [build:app] (aaaa0001): Starting build",
+            "2024-05-01 12:00:05.000 ERROR [build:app] (aaaa0001): Build failed",
+            "   0: backtrace frame",
+            "2024-05-01 13:00:00.000 WARN [test:other] (bbbb0002): Slow test",
+        ].into_iter(), None);
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[2].level, Some(LogLevel::Error));
+        assert_eq!(lines[2].operation.as_deref(), Some("aaaa0001"));
+
+        let count = |filter: &LogFilter| lines.iter().filter(|line| filter.matches(line)).count();
+        assert_eq!(count(&LogFilter { min_level: Some(LogLevel::Warn), ..LogFilter::default() }), 3);
+        assert_eq!(count(&LogFilter { context: Some("APP".to_string()), ..LogFilter::default() }), 3);
+        assert_eq!(count(&LogFilter { operation: Some("bbbb0002".to_string()), ..LogFilter::default() }), 1);
+
+        let now = NaiveDateTime::parse_from_str("2024-05-01 13:30:00.000", TIMESTAMP_FORMAT).unwrap().and_utc();
+        let (since, until) = parse_time_range("12:00..12:00", now).unwrap();
+        assert_eq!(until.as_deref(), Some("2024-05-01 12:00:59.999"));
+        assert_eq!(count(&LogFilter { since, until, ..LogFilter::default() }), 3);
+        let (since, until) = parse_time_range("1h", now).unwrap();
+        assert_eq!((since.as_deref(), until), (Some("2024-05-01 12:30:00.000"), None));
+        assert!(parse_time_range("yesterday", now).is_err());
+    }
+}
@@ -0,0 +1,159 @@
+use crossterm::event::{KeyCode, KeyModifiers, KeyEventKind};
+use super::core::{LogViewer, Prompt, ViewerExit};
+use super::filter;
+
+pub fn handle_key_event(viewer: &mut LogViewer, key_event: crossterm::event::KeyEvent) {
+    if key_event.kind != KeyEventKind::Press {
+        return;
+    }
+
+    viewer.needs_redraw = true;
+
+    if viewer.prompt.is_some() {
+        handle_prompt_key(viewer, key_event);
+        return;
+    }
+    viewer.message = None;
+
+    let page = super::render::page_height(viewer.previous_terminal_size).max(1) as isize;
+    match (key_event.code, key_event.modifiers) {
+        (KeyCode::Char('q'), _) | (KeyCode::Esc, _) => viewer.exit = Some(ViewerExit::Quit),
+        (KeyCode::Char('c'), KeyModifiers::CONTROL) => viewer.exit = Some(ViewerExit::Quit),
+        (KeyCode::Up, _) | (KeyCode::Char('k'), _) => viewer.move_selection(-1),
+        (KeyCode::Down, _) | (KeyCode::Char('j'), _) => viewer.move_selection(1),
+        (KeyCode::PageUp, _) | (KeyCode::Char('b'), _) => viewer.move_selection(-page),
+        (KeyCode::PageDown, _) | (KeyCode::Char(' '), _) => viewer.move_selection(page),
+        (KeyCode::Home, _) | (KeyCode::Char('g'), _) => viewer.move_selection(isize::MIN / 2),
+        (KeyCode::End, _) | (KeyCode::Char('G'), _) => viewer.move_selection(isize::MAX / 2),
+        (KeyCode::Left, _) => viewer.left = viewer.left.saturating_sub(8),
+        (KeyCode::Right, _) => viewer.left += 8,
+        (KeyCode::Char('/'), _) => {
+            viewer.search_origin = (viewer.selected, viewer.search.clone());
+            viewer.prompt_input.clear();
+            viewer.prompt = Some(Prompt::Search);
+        }
+        (KeyCode::Char('n'), _) => search_again(viewer, true),
+        (KeyCode::Char('N'), _) => search_again(viewer, false),
+        (KeyCode::Char('l'), _) => {
+            viewer.filter.next_level();
+            viewer.apply_filter();
+        }
+        (KeyCode::Char('c'), _) => {
+            viewer.prompt_input = viewer.filter.context.clone().unwrap_or_default();
+            viewer.prompt = Some(Prompt::Context);
+        }
+        (KeyCode::Char('t'), _) => {
+            viewer.prompt_input.clear();
+            viewer.prompt = Some(Prompt::TimeRange);
+        }
+        (KeyCode::Char('o'), _) => {
+            if viewer.filter.operation.is_some() {
+                viewer.filter.operation = None;
+            } else {
+                match viewer.selected_line().and_then(|line| line.operation.clone()) {
+                    Some(operation) => viewer.filter.operation = Some(operation),
+                    None => viewer.message = Some("Selected line has no operation ID".to_string()),
+                }
+            }
+            viewer.apply_filter();
+        }
+        (KeyCode::Char('x'), _) => {
+            viewer.filter = filter::LogFilter::default();
+            viewer.apply_filter();
+        }
+        (KeyCode::Char('f'), _) => {
+            viewer.follow = !viewer.follow;
+            if viewer.follow {
+                if let Err(e) = viewer.read_new_lines() {
+                    viewer.message = Some(e);
+                }
+                viewer.selected = viewer.last_position();
+            }
+        }
+        (KeyCode::Char('r'), _) => {
+            if let Err(e) = viewer.reload() {
+                viewer.message = Some(e);
+            }
+        }
+        (KeyCode::Char('e'), _) => viewer.exit = Some(ViewerExit::OpenExternally),
+        _ => {
+            viewer.needs_redraw = false;
+        }
+    }
+}
+
+fn handle_prompt_key(viewer: &mut LogViewer, key_event: crossterm::event::KeyEvent) {
+    let prompt = match viewer.prompt {
+        Some(prompt) => prompt,
+        None => return,
+    };
+
+    match key_event.code {
+        KeyCode::Esc => {
+            if prompt == Prompt::Search {
+                let (selected, search) = viewer.search_origin.clone();
+                viewer.selected = selected.min(viewer.last_position());
+                viewer.search = search;
+            }
+            viewer.prompt = None;
+        }
+        KeyCode::Enter => {
+            viewer.prompt = None;
+            let text = viewer.prompt_input.trim().to_string();
+            match prompt {
+                Prompt::Search => {
+                    if !text.is_empty() && !viewer.find_match(viewer.selected, true) {
+                        viewer.message = Some(format!("Pattern not found: {}", text));
+                    }
+                }
+                Prompt::Context => {
+                    viewer.filter.context = if text.is_empty() { None } else { Some(text) };
+                    viewer.apply_filter();
+                }
+                Prompt::TimeRange => match filter::parse_time_range(&text, chrono::Utc::now()) {
+                    Ok((since, until)) => {
+                        viewer.filter.since = since;
+                        viewer.filter.until = until;
+                        viewer.apply_filter();
+                    }
+                    Err(e) => viewer.message = Some(e),
+                },
+            }
+        }
+        KeyCode::Backspace => {
+            viewer.prompt_input.pop();
+            update_incremental_search(viewer, prompt);
+        }
+        KeyCode::Char(c) if !key_event.modifiers.contains(KeyModifiers::CONTROL) => {
+            viewer.prompt_input.push(c);
+            update_incremental_search(viewer, prompt);
+        }
+        _ => {
+            viewer.needs_redraw = false;
+        }
+    }
+}
+
+// Jumps to the first match from where the search started as the query is typed
+fn update_incremental_search(viewer: &mut LogViewer, prompt: Prompt) {
+    if prompt != Prompt::Search {
+        return;
+    }
+    viewer.search = viewer.prompt_input.clone();
+    let origin = viewer.search_origin.0.min(viewer.last_position());
+    if !viewer.find_match(origin, true) {
+        viewer.selected = origin;
+    }
+}
+
+fn search_again(viewer: &mut LogViewer, forward: bool) {
+    if viewer.search.is_empty() {
+        viewer.message = Some("No search - press / to search".to_string());
+        return;
+    }
+    let count = viewer.visible.len().max(1);
+    let from = if forward { viewer.selected + 1 } else { viewer.selected + count - 1 };
+    if !viewer.find_match(from % count, forward) {
+        viewer.message = Some(format!("Pattern not found: {}", viewer.search));
+    }
+}
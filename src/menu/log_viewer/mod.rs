@@ -0,0 +1,7 @@
+mod core;
+mod filter;
+mod input;
+mod render;
+
+pub use core::{view_log_file, ViewerExit};
+pub use filter::LogFilter;
@@ -0,0 +1,146 @@
+use std::io::{stdout, Write};
+use crossterm::{execute, style::{Attribute, SetAttribute}, terminal};
+use crate::ansi_theme::AnsiTheme;
+use crate::logger::LogLevel;
+use super::core::{match_ranges, LogViewer, Prompt};
+use super::filter::LogLine;
+
+const HELP: &str = "q quit  / search  n/N next/prev  l level  c context  t time  o operation  x clear  f follow  r reload  e external";
+const MATCH_STYLE: &str = "\x1b[30;43m";
+
+/// Rows available for log lines; the last two hold the status bar and the help/prompt line
+pub fn page_height(terminal_size: (u16, u16)) -> usize {
+    (terminal_size.1 as usize).saturating_sub(2)
+}
+
+pub fn draw_interface(viewer: &LogViewer, terminal_size: (u16, u16)) -> Result<(), String> {
+    execute!(stdout(), terminal::Clear(terminal::ClearType::Purge))
+        .map_err(|e| format!("Failed to clear screen: {}", e))?;
+
+    draw_content(viewer, terminal_size)?;
+    draw_status_bar(viewer, terminal_size)?;
+    draw_prompt_line(viewer, terminal_size)?;
+
+    stdout().flush().map_err(|e| format!("Failed to flush stdout: {}", e))?;
+    Ok(())
+}
+
+pub fn draw_content(viewer: &LogViewer, terminal_size: (u16, u16)) -> Result<(), String> {
+    let width = terminal_size.0 as usize;
+
+    for row in 0..page_height(terminal_size) {
+        execute!(stdout(), crossterm::cursor::MoveTo(0, row as u16))
+            .map_err(|e| format!("Failed to move cursor for line {}: {}", row, e))?;
+
+        let position = viewer.top + row;
+        match viewer.visible.get(position) {
+            Some(&index) => {
+                let selected = position == viewer.selected;
+                if selected {
+                    execute!(stdout(), SetAttribute(Attribute::Reverse))
+                        .map_err(|e| format!("Failed to set reverse attribute: {}", e))?;
+                }
+                draw_line(viewer, &viewer.lines[index], width);
+                if selected {
+                    execute!(stdout(), SetAttribute(Attribute::NoReverse))
+                        .map_err(|e| format!("Failed to reset attribute: {}", e))?;
+                }
+            }
+            None => AnsiTheme::print_themed(&" ".repeat(width), &viewer.theme),
+        }
+    }
+    Ok(())
+}
+
+// Prints the horizontally scrolled part of a line in its level's color, with search matches highlighted
+fn draw_line(viewer: &LogViewer, line: &LogLine, width: usize) {
+    let color = match line.level {
+        Some(LogLevel::Error) => "\x1b[91m",
+        Some(LogLevel::Warn) => "\x1b[93m",
+        Some(LogLevel::Debug) => "\x1b[90m",
+        _ => viewer.theme.foreground_color.to_ansi_fg_code(),
+    };
+    let chars: Vec<char> = line.text.chars().collect();
+    let start = viewer.left.min(chars.len());
+    let end = (start + width).min(chars.len());
+    let matches = match_ranges(&line.text, &viewer.search);
+
+    let mut segment_start = start;
+    while segment_start < end {
+        let highlighted = matches.iter().any(|&(from, to)| segment_start >= from && segment_start < to);
+        let mut segment_end = segment_start + 1;
+        while segment_end < end
+            && matches.iter().any(|&(from, to)| segment_end >= from && segment_end < to) == highlighted
+        {
+            segment_end += 1;
+        }
+        let text: String = chars[segment_start..segment_end].iter().collect();
+        AnsiTheme::print_styled(&text, if highlighted { MATCH_STYLE } else { color }, &viewer.theme);
+        segment_start = segment_end;
+    }
+    AnsiTheme::print_themed(&" ".repeat(width - (end - start)), &viewer.theme);
+}
+
+pub fn draw_status_bar(viewer: &LogViewer, terminal_size: (u16, u16)) -> Result<(), String> {
+    let position = if viewer.visible.is_empty() { 0 } else { viewer.selected + 1 };
+    let mut status = format!(
+        "{} | {}/{} shown of {} | {}",
+        viewer.file_path.display(),
+        position,
+        viewer.visible.len(),
+        viewer.lines.len(),
+        viewer.filter.describe()
+    );
+    if !viewer.search.is_empty() {
+        status.push_str(&format!(" | search: {}", viewer.search));
+    }
+    if viewer.follow {
+        status.push_str(" | FOLLOWING");
+    }
+
+    move_to_row(terminal_size.1.saturating_sub(2))?;
+    AnsiTheme::print_info(&fit(&status, terminal_size.0 as usize), &viewer.theme);
+    Ok(())
+}
+
+pub fn draw_prompt_line(viewer: &LogViewer, terminal_size: (u16, u16)) -> Result<(), String> {
+    let width = terminal_size.0 as usize;
+    move_to_row(terminal_size.1.saturating_sub(1))?;
+
+    match (viewer.prompt, &viewer.message) {
+        (Some(prompt), _) => {
+            let label = match prompt {
+                Prompt::Search => "/",
+                Prompt::Context => "Context contains (empty clears): ",
+                Prompt::TimeRange => "Time range (30m, 2h, 1d, 14:00..15:30, empty clears): ",
+            };
+            let text = format!("{}{}", label, viewer.prompt_input);
+            AnsiTheme::print_themed(&fit(&text, width), &viewer.theme);
+            let column = text.chars().count().min(width.saturating_sub(1));
+            execute!(stdout(), crossterm::cursor::MoveTo(column as u16, terminal_size.1.saturating_sub(1)), crossterm::cursor::Show)
+                .map_err(|e| format!("Failed to move cursor to prompt: {}", e))?;
+        }
+        (None, Some(message)) => {
+            AnsiTheme::print_warning(&fit(message, width), &viewer.theme);
+            execute!(stdout(), crossterm::cursor::Hide).map_err(|e| format!("Failed to hide cursor: {}", e))?;
+        }
+        (None, None) => {
+            AnsiTheme::print_themed(&fit(HELP, width), &viewer.theme);
+            execute!(stdout(), crossterm::cursor::Hide).map_err(|e| format!("Failed to hide cursor: {}", e))?;
+        }
+    }
+    Ok(())
+}
+
+fn move_to_row(row: u16) -> Result<(), String> {
+    execute!(stdout(), crossterm::cursor::MoveTo(0, row))
+        .map_err(|e| format!("Failed to move cursor to row {}: {}", row, e))
+}
+
+// Cuts or pads `text` to exactly `width` characters so it overwrites the whole row
+fn fit(text: &str, width: usize) -> String {
+    let mut fitted: String = text.chars().take(width).collect();
+    let length = fitted.chars().count();
+    fitted.push_str(&" ".repeat(width - length));
+    fitted
+}
@@ -0,0 +1,166 @@
+// menu_bench.rs
+use std::io::{self, Write};
+use crate::ansi_theme::AnsiTheme;
+use crate::bench_report::{self, BaselineReport, BenchBaselines, Benchmark};
+use crate::build_history;
+use crate::build_system::BuildSystem;
+use crate::config::ThemeConfig;
+
+/// Runs `cargo bench`, compares the results with a saved baseline and manages baselines
+pub fn bench_menu(build_system: &BuildSystem, project: &str, theme: &ThemeConfig) {
+    let project_path = build_system.get_project_path(project);
+    let threshold_percent = build_system.bench_regression_threshold();
+    let mut selected = BenchBaselines::load(&project_path).latest().map(|name| name.to_string());
+    let mut last_benchmarks: Vec<Benchmark> = Vec::new();
+
+    loop {
+        AnsiTheme::print_themed("\n", theme);
+        AnsiTheme::print_yellow(&format!("Benchmarks - {}\n", project), theme);
+        AnsiTheme::print_themed(&format!("Comparing against: {} (regression above {}%)\n",
+            selected.as_deref().unwrap_or("no baseline"), threshold_percent), theme);
+        AnsiTheme::print_themed("1) Run all benchmarks\n", theme);
+        AnsiTheme::print_themed("2) Run benchmarks matching filter\n", theme);
+        AnsiTheme::print_themed("3) Save last run as baseline\n", theme);
+        AnsiTheme::print_themed("4) Choose baseline to compare against\n", theme);
+        AnsiTheme::print_themed("5) List baselines\n", theme);
+        AnsiTheme::print_themed("6) Delete baseline\n", theme);
+        AnsiTheme::print_themed("B) Back\n", theme);
+        AnsiTheme::print_themed("Enter choice: ", theme);
+        io::stdout().flush().unwrap();
+
+        match read_line().as_str() {
+            "1" => {
+                if let Some(benchmarks) = run_benchmarks(build_system, project, &[], selected.as_deref(), threshold_percent, theme) {
+                    last_benchmarks = benchmarks;
+                }
+            }
+            "2" => {
+                AnsiTheme::print_themed("Filter (substring of the benchmark name, empty to cancel): ", theme);
+                io::stdout().flush().unwrap();
+                let filter = read_line();
+                if filter.is_empty() {
+                    AnsiTheme::print_themed("Cancelled.\n", theme);
+                    continue;
+                }
+                if let Some(benchmarks) = run_benchmarks(build_system, project, &[filter], selected.as_deref(), threshold_percent, theme) {
+                    last_benchmarks = benchmarks;
+                }
+            }
+            "3" => {
+                if last_benchmarks.is_empty() {
+                    AnsiTheme::print_themed("No benchmark results yet - run the benchmarks first.\n", theme);
+                    continue;
+                }
+                AnsiTheme::print_themed("Baseline name (e.g. main, before-refactor): ", theme);
+                io::stdout().flush().unwrap();
+                let name = read_line();
+                if name.is_empty() {
+                    AnsiTheme::print_themed("Cancelled.\n", theme);
+                    continue;
+                }
+                let mut baselines = BenchBaselines::load(&project_path);
+                baselines.insert(&name, &last_benchmarks, build_history::git_commit(&project_path));
+                match baselines.save(&project_path) {
+                    Ok(()) => {
+                        AnsiTheme::print_success(&format!(" Saved {} benchmarks as baseline '{}'\n", last_benchmarks.len(), name), theme);
+                        selected = Some(name);
+                    }
+                    Err(e) => AnsiTheme::print_error(&format!("Error: {}\n", e), theme),
+                }
+            }
+            "4" => {
+                if let Some(name) = pick_baseline(&BenchBaselines::load(&project_path), theme) {
+                    selected = Some(name);
+                }
+            }
+            "5" => print_baselines(&BenchBaselines::load(&project_path), theme),
+            "6" => {
+                let mut baselines = BenchBaselines::load(&project_path);
+                if let Some(name) = pick_baseline(&baselines, theme) {
+                    baselines.baselines.remove(&name);
+                    match baselines.save(&project_path) {
+                        Ok(()) => {
+                            AnsiTheme::print_success(&format!(" Deleted baseline '{}'\n", name), theme);
+                            if selected.as_deref() == Some(name.as_str()) {
+                                selected = baselines.latest().map(|name| name.to_string());
+                            }
+                        }
+                        Err(e) => AnsiTheme::print_error(&format!("Error: {}\n", e), theme),
+                    }
+                }
+            }
+            "B" | "b" => break,
+            _ => AnsiTheme::print_themed("Invalid choice.\n", theme),
+        }
+    }
+}
+
+fn run_benchmarks(build_system: &BuildSystem, project: &str, args: &[String], baseline: Option<&str>, threshold_percent: f64, theme: &ThemeConfig) -> Option<Vec<Benchmark>> {
+    let result = match build_system.bench(project, args, theme) {
+        Ok(result) => result,
+        Err(e) => {
+            AnsiTheme::print_error(&format!("Error: {}\n", e), theme);
+            return None;
+        }
+    };
+
+    if let Some(reason) = result.termination.interruption() {
+        AnsiTheme::print_error(&format!(" Benchmarks {}! ({:.2?})\n", reason, result.duration), theme);
+        return None;
+    }
+    if !result.success {
+        AnsiTheme::print_error(&format!(" Benchmarks failed! ({:.2?})\n", result.duration), theme);
+        return None;
+    }
+    if result.benchmarks.is_empty() {
+        AnsiTheme::print_warning(" No benchmark results found in the output\n", theme);
+        return None;
+    }
+
+    let baselines = BenchBaselines::load(&build_system.get_project_path(project));
+    match baseline.and_then(|name| baselines.baselines.get(name).map(|b| (name, b))) {
+        Some((name, baseline)) => BaselineReport::new(&result.benchmarks, name, baseline, threshold_percent).print(theme),
+        None => bench_report::print_benchmarks(&result.benchmarks, theme),
+    }
+    AnsiTheme::print_success(&format!(" {} benchmarks finished ({:.2?})\n", result.benchmarks.len(), result.duration), theme);
+    Some(result.benchmarks)
+}
+
+fn print_baselines(baselines: &BenchBaselines, theme: &ThemeConfig) {
+    if baselines.baselines.is_empty() {
+        AnsiTheme::print_themed("No baselines saved yet.\n", theme);
+        return;
+    }
+    AnsiTheme::print_themed("\n", theme);
+    for (name, baseline) in &baselines.baselines {
+        let commit = baseline.commit.as_ref().map(|c| format!(" @ {}", c)).unwrap_or_default();
+        AnsiTheme::print_themed(&format!(" {:<20} {} {} benchmarks{}\n", name, baseline.timestamp, baseline.benchmarks.len(), commit), theme);
+    }
+}
+
+fn pick_baseline(baselines: &BenchBaselines, theme: &ThemeConfig) -> Option<String> {
+    if baselines.baselines.is_empty() {
+        AnsiTheme::print_themed("No baselines saved yet.\n", theme);
+        return None;
+    }
+    let names: Vec<&String> = baselines.baselines.keys().collect();
+    for (i, name) in names.iter().enumerate() {
+        AnsiTheme::print_themed(&format!("{}) {}\n", i + 1, name), theme);
+    }
+    AnsiTheme::print_themed("Baseline number: ", theme);
+    io::stdout().flush().unwrap();
+
+    match read_line().parse::<usize>() {
+        Ok(n) if n >= 1 && n <= names.len() => Some(names[n - 1].clone()),
+        _ => {
+            AnsiTheme::print_themed("Invalid choice.\n", theme);
+            None
+        }
+    }
+}
+
+fn read_line() -> String {
+    let mut input = String::new();
+    io::stdin().read_line(&mut input).unwrap();
+    input.trim().to_string()
+}
@@ -0,0 +1,173 @@
+// menu_build_configurations.rs
+use std::io::{self, Write};
+use crate::ansi_theme::AnsiTheme;
+use crate::build_system::{BuildResult, BuildSystem};
+use crate::config::ThemeConfig;
+use crate::project_settings::{BuildConfiguration, ProjectSettings};
+
+/// Lists the project's saved build configurations and builds with the chosen one.
+/// Returns the result of the last build run from this menu, if any.
+pub fn build_configurations_menu(build_system: &BuildSystem, project: &str, theme: &ThemeConfig) -> Option<BuildResult> {
+    let project_path = build_system.get_project_path(project);
+    let mut last_result = None;
+
+    loop {
+        let mut settings = match ProjectSettings::load(&project_path) {
+            Ok(settings) => settings,
+            Err(e) => {
+                AnsiTheme::print_error(&format!("Error: {}\n", e), theme);
+                return last_result;
+            }
+        };
+
+        AnsiTheme::print_themed("\n", theme);
+        AnsiTheme::print_yellow(&format!("Build Configurations - {}\n", project), theme);
+        if settings.build_configurations.is_empty() {
+            AnsiTheme::print_themed("No build configurations saved yet.\n", theme);
+        }
+        for (i, configuration) in settings.build_configurations.iter().enumerate() {
+            AnsiTheme::print_themed(&format!("{}) {:<16} {}\n", i + 1, configuration.name, configuration.summary()), theme);
+        }
+        AnsiTheme::print_themed("N) New configuration\n", theme);
+        AnsiTheme::print_themed("E) Edit configuration\n", theme);
+        AnsiTheme::print_themed("D) Delete configuration\n", theme);
+        AnsiTheme::print_themed("B) Back\n", theme);
+        AnsiTheme::print_themed("Enter a number to build, or a choice: ", theme);
+        io::stdout().flush().unwrap();
+
+        let choice = read_line();
+        match choice.as_str() {
+            "N" | "n" => {
+                if let Some(configuration) = prompt_configuration(&BuildConfiguration::default(), theme) {
+                    save_configuration(&mut settings, configuration, &project_path, theme);
+                }
+            }
+            "E" | "e" => {
+                if let Some(index) = pick_configuration(&settings, "edit", theme) {
+                    let existing = settings.build_configurations[index].clone();
+                    if let Some(configuration) = prompt_configuration(&existing, theme) {
+                        // A rename replaces the old entry rather than duplicating it
+                        settings.remove_build_configuration(&existing.name);
+                        save_configuration(&mut settings, configuration, &project_path, theme);
+                    }
+                }
+            }
+            "D" | "d" => {
+                if let Some(index) = pick_configuration(&settings, "delete", theme) {
+                    let name = settings.build_configurations[index].name.clone();
+                    settings.remove_build_configuration(&name);
+                    match settings.save(&project_path) {
+                        Ok(()) => AnsiTheme::print_success(&format!(" Deleted '{}'\n", name), theme),
+                        Err(e) => AnsiTheme::print_error(&format!("Error: {}\n", e), theme),
+                    }
+                }
+            }
+            "B" | "b" => return last_result,
+            _ => match choice.parse::<usize>() {
+                Ok(n) if n >= 1 && n <= settings.build_configurations.len() => {
+                    let configuration = &settings.build_configurations[n - 1];
+                    match build_system.build_with_configuration(project, configuration, theme) {
+                        Ok(result) => {
+                            if let Some(reason) = result.termination.interruption() {
+                                AnsiTheme::print_error(&format!(" Build '{}' {}! ({:.2?})\n", configuration.name, reason, result.duration), theme);
+                            } else if result.success {
+                                AnsiTheme::print_success(&format!(" Build '{}' successful! ({:.2?})\n", configuration.name, result.duration), theme);
+                            } else {
+                                AnsiTheme::print_error(&format!(" Build '{}' failed! ({:.2?})\n", configuration.name, result.duration), theme);
+                            }
+                            last_result = Some(result);
+                        }
+                        Err(e) => AnsiTheme::print_error(&format!("Error: {}\n", e), theme),
+                    }
+                }
+                _ => AnsiTheme::print_themed("Invalid choice.\n", theme),
+            },
+        }
+    }
+}
+
+fn save_configuration(settings: &mut ProjectSettings, configuration: BuildConfiguration, project_path: &std::path::Path, theme: &ThemeConfig) {
+    let name = configuration.name.clone();
+    settings.set_build_configuration(configuration);
+    match settings.save(project_path) {
+        Ok(()) => AnsiTheme::print_success(&format!(" Saved build configuration '{}'\n", name), theme),
+        Err(e) => AnsiTheme::print_error(&format!("Error: {}\n", e), theme),
+    }
+}
+
+fn pick_configuration(settings: &ProjectSettings, verb: &str, theme: &ThemeConfig) -> Option<usize> {
+    if settings.build_configurations.is_empty() {
+        AnsiTheme::print_themed("No build configurations saved yet.\n", theme);
+        return None;
+    }
+    AnsiTheme::print_themed(&format!("Number of the configuration to {}: ", verb), theme);
+    io::stdout().flush().unwrap();
+
+    match read_line().parse::<usize>() {
+        Ok(n) if n >= 1 && n <= settings.build_configurations.len() => Some(n - 1),
+        _ => {
+            AnsiTheme::print_themed("Invalid choice.\n", theme);
+            None
+        }
+    }
+}
+
+/// Asks for each field, showing `current` values that Enter keeps. `-` clears a field.
+fn prompt_configuration(current: &BuildConfiguration, theme: &ThemeConfig) -> Option<BuildConfiguration> {
+    AnsiTheme::print_themed("\n", theme);
+    AnsiTheme::print_info("Press Enter to keep the value in brackets, '-' to clear it.\n", theme);
+
+    let name = ask("Name", Some(current.name.clone()).filter(|n| !n.is_empty()), theme)?;
+    let configuration = BuildConfiguration {
+        name,
+        profile: ask("Profile (dev, release or custom)", current.profile.clone(), theme),
+        features: ask("Features (comma-separated)", Some(current.features.join(",")).filter(|f| !f.is_empty()), theme)
+            .map(|f| f.split(',').map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect())
+            .unwrap_or_default(),
+        all_features: ask_bool("All features", current.all_features, theme),
+        no_default_features: ask_bool("No default features", current.no_default_features, theme),
+        target: ask("Target triple", current.target.clone(), theme),
+        bin: ask("Binary (--bin)", current.bin.clone(), theme),
+        example: ask("Example (--example)", current.example.clone(), theme),
+        extra_args: ask("Extra cargo arguments", Some(current.extra_args.join(" ")).filter(|a| !a.is_empty()), theme)
+            .map(|a| a.split_whitespace().map(|s| s.to_string()).collect())
+            .unwrap_or_default(),
+    };
+
+    match configuration.validate() {
+        Ok(()) => Some(configuration),
+        Err(e) => {
+            AnsiTheme::print_error(&format!("Error: {}\n", e), theme);
+            None
+        }
+    }
+}
+
+fn ask(label: &str, current: Option<String>, theme: &ThemeConfig) -> Option<String> {
+    match &current {
+        Some(value) => AnsiTheme::print_themed(&format!("{} [{}]: ", label, value), theme),
+        None => AnsiTheme::print_themed(&format!("{}: ", label), theme),
+    }
+    io::stdout().flush().unwrap();
+
+    match read_line().as_str() {
+        "" => current,
+        "-" => None,
+        value => Some(value.to_string()),
+    }
+}
+
+fn ask_bool(label: &str, current: bool, theme: &ThemeConfig) -> bool {
+    let shown = if current { "y" } else { "n" };
+    match ask(&format!("{} (y/n)", label), Some(shown.to_string()), theme).as_deref() {
+        Some("y") | Some("Y") => true,
+        Some("n") | Some("N") | None => false,
+        Some(_) => current,
+    }
+}
+
+fn read_line() -> String {
+    let mut input = String::new();
+    io::stdin().read_line(&mut input).unwrap();
+    input.trim().to_string()
+}
@@ -1,22 +1,52 @@
 // menu_build_run.rs (UPDATED)
 use std::io::{self, Write};
-use crate::build_system::BuildSystem;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use crate::build_history::HistoryReport;
+use crate::build_system::{BuildResult, BuildSystem};
 use crate::ansi_theme::AnsiTheme;
+use crate::archive::ArchiveFormat;
 use crate::config::ThemeConfig;
+use crate::diagnostics::{Diagnostic, DiagnosticLevel};
+use crate::jobs::JobManager;
+use crate::project_settings::ProjectSettings;
+use crate::python_manager::PythonManager;
+use crate::size_report;
+use crate::watcher::{self, WatchAction};
+use super::menu_bench::bench_menu;
+use super::menu_build_configurations::build_configurations_menu;
+use super::menu_diagnostics::diagnostics_menu;
+use super::menu_jobs::report_started;
+use super::menu_run_configurations::run_configurations_menu;
+use super::menu_tests::tests_menu;
+use super::view_build_log;
+
+pub fn build_run_menu(build_system: &BuildSystem, python_manager: &PythonManager, job_manager: &JobManager, project: &str, theme: &ThemeConfig) {
+    // Diagnostics from the most recent build or check, for the D option
+    let mut last_diagnostics: Vec<Diagnostic> = Vec::new();
 
-pub fn build_run_menu(build_system: &BuildSystem, project: &str, theme: &ThemeConfig) {
     loop {
         AnsiTheme::print_themed("\n", theme);
         AnsiTheme::print_yellow(&format!("Build & Run - {}\n", project), theme);
         AnsiTheme::print_themed("1) Build Project (debug)\n", theme);
         AnsiTheme::print_themed("2) Build Project (release)\n", theme);
         AnsiTheme::print_themed("3) Check Project (fast compile check)\n", theme);
+        AnsiTheme::print_themed("C) Build with Configuration (profiles, features, targets)\n", theme);
+        AnsiTheme::print_themed("T) Build with Compile Timings (slowest crates, critical path)\n", theme);
         AnsiTheme::print_themed("4) Run Project\n", theme);
         AnsiTheme::print_themed("5) Run Project with Arguments\n", theme);
-        AnsiTheme::print_themed("6) Run Tests\n", theme);
+        AnsiTheme::print_themed("R) Run Configurations (args, env, stdin, working dir)\n", theme);
+        AnsiTheme::print_themed("J) Run Project in Background\n", theme);
+        AnsiTheme::print_themed("6) Run Tests (per-test results, rerun failed)\n", theme);
+        AnsiTheme::print_themed("M) Benchmarks (cargo bench, baseline comparison)\n", theme);
+        AnsiTheme::print_themed("P) Package Release (zip/tar.gz + SHA-256)\n", theme);
         AnsiTheme::print_themed("7) Clean Project\n", theme);
         AnsiTheme::print_themed("8) Show Build Info\n", theme);
-        AnsiTheme::print_themed("9) Show Recent Logs\n", theme);
+        AnsiTheme::print_themed("S) Binary Size Report (sections, crates, symbols)\n", theme);
+        AnsiTheme::print_themed("9) View Project Logs (filter, search, follow)\n", theme);
+        AnsiTheme::print_themed("H) Build History & Trends\n", theme);
+        AnsiTheme::print_themed("W) Watch (re-run on source changes)\n", theme);
+        AnsiTheme::print_themed(&format!("D) Browse Diagnostics ({} from last build/check)\n", last_diagnostics.len()), theme);
         AnsiTheme::print_themed("B) Back to main menu\n", theme);
 
         AnsiTheme::print_themed("Enter choice: ", theme);
@@ -27,22 +57,40 @@ pub fn build_run_menu(build_system: &BuildSystem, project: &str, theme: &ThemeCo
         let choice = input.trim();
 
         match choice {
-            "1" => build_project(build_system, project, false, theme),
-            "2" => build_project(build_system, project, true, theme),
-            "3" => check_project(build_system, project, theme),
+            "1" => last_diagnostics = build_project(build_system, project, false, theme),
+            "2" => last_diagnostics = build_project(build_system, project, true, theme),
+            "3" => last_diagnostics = check_project(build_system, project, theme),
+            "C" | "c" => {
+                if let Some(result) = build_configurations_menu(build_system, project, theme) {
+                    last_diagnostics = result.diagnostics;
+                }
+            }
+            "T" | "t" => {
+                if let Some(diagnostics) = timings_build(build_system, project, theme) {
+                    last_diagnostics = diagnostics;
+                }
+            }
             "4" => run_project(build_system, project, &[], theme),
             "5" => run_with_args_menu(build_system, project, theme),
-            "6" => test_project(build_system, project, theme),
+            "R" | "r" => run_configurations_menu(build_system, python_manager, project, theme),
+            "J" | "j" => report_started(job_manager.start_cargo_run(project, &[]), theme),
+            "6" => tests_menu(build_system, project, theme),
+            "M" | "m" => bench_menu(build_system, project, theme),
+            "P" | "p" => package_release_menu(build_system, project, theme),
             "7" => clean_project(build_system, project, theme),
             "8" => show_build_info(build_system, project, theme),
-            "9" => show_recent_logs(theme),
+            "S" | "s" => show_size_report(build_system, project, theme),
+            "9" => view_build_log(build_system, Some(project), theme),
+            "H" | "h" => show_build_history(build_system, project, theme),
+            "W" | "w" => watch_menu(build_system, project, theme),
+            "D" | "d" => diagnostics_menu(&last_diagnostics, &build_system.get_project_path(project), theme),
             "B" | "b" => break,
             _ => AnsiTheme::print_themed("Invalid choice.\n", theme),
         }
     }
 }
 
-fn build_project(build_system: &BuildSystem, project: &str, release: bool, theme: &ThemeConfig) {
+fn build_project(build_system: &BuildSystem, project: &str, release: bool, theme: &ThemeConfig) -> Vec<Diagnostic> {
     let result = if release {
         build_system.build_release(project, theme)
     } else {
@@ -51,46 +99,86 @@ fn build_project(build_system: &BuildSystem, project: &str, release: bool, theme
 
     match result {
         Ok(build_result) => {
-            if build_result.success {
+            if let Some(reason) = build_result.termination.interruption() {
+                AnsiTheme::print_error(&format!(" Build {}! ({:.2?})\n", reason, build_result.duration), theme);
+            } else if build_result.success {
                 AnsiTheme::print_success(&format!(" Build successful! ({:.2?})\n", build_result.duration), theme);
             } else {
                 AnsiTheme::print_error(&format!(" Build failed! ({:.2?})\n", build_result.duration), theme);
             }
-            
-            if !build_result.stderr.is_empty() {
-                AnsiTheme::print_themed("\n", theme);
-                AnsiTheme::print_yellow("Build output:\n", theme);
-                AnsiTheme::print_themed(&build_result.stderr, theme);
+            print_diagnostic_counts(&build_result, theme);
+            build_result.diagnostics
+        }
+        Err(e) => {
+            AnsiTheme::print_error(&format!("Error: {}\n", e), theme);
+            Vec::new()
+        }
+    }
+}
+
+fn timings_build(build_system: &BuildSystem, project: &str, theme: &ThemeConfig) -> Option<Vec<Diagnostic>> {
+    AnsiTheme::print_themed("Profile: 1) debug  2) release [1]: ", theme);
+    io::stdout().flush().unwrap();
+    let mut input = String::new();
+    io::stdin().read_line(&mut input).unwrap();
+    let release = match input.trim() {
+        "" | "1" => false,
+        "2" => true,
+        _ => {
+            AnsiTheme::print_themed("Invalid choice.\n", theme);
+            return None;
+        }
+    };
+    AnsiTheme::print_info(" Only crates that need rebuilding are timed - clean the project first for a full picture\n", theme);
+
+    match build_system.build_with_timings(project, release, theme) {
+        Ok(result) => {
+            let build = &result.build;
+            if let Some(reason) = build.termination.interruption() {
+                AnsiTheme::print_error(&format!(" Build {}! ({:.2?})\n", reason, build.duration), theme);
+            } else if build.success {
+                AnsiTheme::print_success(&format!(" Build successful! ({:.2?})\n", build.duration), theme);
+            } else {
+                AnsiTheme::print_error(&format!(" Build failed! ({:.2?})\n", build.duration), theme);
             }
-            
-            if !build_result.stdout.is_empty() {
-                AnsiTheme::print_themed(&build_result.stdout, theme);
+            print_diagnostic_counts(build, theme);
+            match &result.timings {
+                Some(timings) => timings.print(15, theme),
+                None => AnsiTheme::print_warning(" Cargo did not write a timings report\n", theme),
             }
+            Some(result.build.diagnostics)
+        }
+        Err(e) => {
+            AnsiTheme::print_error(&format!("Error: {}\n", e), theme);
+            None
         }
-        Err(e) => AnsiTheme::print_error(&format!("Error: {}\n", e), theme),
     }
 }
 
-fn check_project(build_system: &BuildSystem, project: &str, theme: &ThemeConfig) {
+fn check_project(build_system: &BuildSystem, project: &str, theme: &ThemeConfig) -> Vec<Diagnostic> {
     match build_system.check_project(project, theme) {
         Ok(check_result) => {
-            if check_result.success {
+            if let Some(reason) = check_result.termination.interruption() {
+                AnsiTheme::print_error(&format!(" Check {}! ({:.2?})\n", reason, check_result.duration), theme);
+            } else if check_result.success {
                 AnsiTheme::print_success(&format!(" Check passed! ({:.2?})\n", check_result.duration), theme);
             } else {
                 AnsiTheme::print_error(&format!(" Check failed! ({:.2?})\n", check_result.duration), theme);
             }
-            
-            if !check_result.stderr.is_empty() {
-                AnsiTheme::print_themed("\n", theme);
-                AnsiTheme::print_yellow("Check output:\n", theme);
-                AnsiTheme::print_themed(&check_result.stderr, theme);
-            }
-            
-            if !check_result.stdout.is_empty() {
-                AnsiTheme::print_themed(&check_result.stdout, theme);
-            }
+            print_diagnostic_counts(&check_result, theme);
+            check_result.diagnostics
+        }
+        Err(e) => {
+            AnsiTheme::print_error(&format!("Error: {}\n", e), theme);
+            Vec::new()
         }
-        Err(e) => AnsiTheme::print_error(&format!("Error: {}\n", e), theme),
+    }
+}
+
+fn print_diagnostic_counts(result: &BuildResult, theme: &ThemeConfig) {
+    if !result.diagnostics.is_empty() {
+        AnsiTheme::print_info(&format!(" {} errors, {} warnings - choose D to browse them\n",
+            result.count(DiagnosticLevel::Error), result.count(DiagnosticLevel::Warning)), theme);
     }
 }
 
@@ -104,15 +192,10 @@ fn run_project(build_system: &BuildSystem, project: &str, args: &[String], theme
     match result {
         Ok(run_result) => {
             AnsiTheme::print_themed("\n", theme);
-            AnsiTheme::print_green("Program output:\n", theme);
-            AnsiTheme::print_themed(&run_result.stdout, theme);
-            
-            if !run_result.stderr.is_empty() {
-                AnsiTheme::print_error("Program errors:\n", theme);
-                AnsiTheme::print_themed(&run_result.stderr, theme);
+            match run_result.termination.interruption() {
+                Some(reason) => AnsiTheme::print_error(&format!(" Run {}! (Duration: {:.2?})\n", reason, run_result.duration), theme),
+                None => AnsiTheme::print_blue(&format!(" Exit code: {} (Duration: {:.2?})\n", run_result.exit_code, run_result.duration), theme),
             }
-            
-            AnsiTheme::print_blue(&format!(" Exit code: {} (Duration: {:.2?})\n", run_result.exit_code, run_result.duration), theme);
         }
         Err(e) => AnsiTheme::print_error(&format!("Error: {}\n", e), theme),
     }
@@ -137,27 +220,140 @@ fn run_with_args_menu(build_system: &BuildSystem, project: &str, theme: &ThemeCo
     run_project(build_system, project, &args, theme);
 }
 
-fn test_project(build_system: &BuildSystem, project: &str, theme: &ThemeConfig) {
-    match build_system.test_project(project, theme) {
-        Ok(test_result) => {
-            if test_result.success {
-                AnsiTheme::print_success(&format!(" Tests passed! ({:.2?})\n", test_result.duration), theme);
-            } else {
-                AnsiTheme::print_error(&format!(" Tests failed! ({:.2?})\n", test_result.duration), theme);
+fn package_release_menu(build_system: &BuildSystem, project: &str, theme: &ThemeConfig) {
+    let project_path = build_system.get_project_path(project);
+    let mut settings = match ProjectSettings::load(&project_path) {
+        Ok(settings) => settings,
+        Err(e) => {
+            AnsiTheme::print_error(&format!("Error: {}\n", e), theme);
+            return;
+        }
+    };
+    let default_format = settings.package.format.unwrap_or_else(ArchiveFormat::platform_default);
+
+    AnsiTheme::print_themed("\n", theme);
+    AnsiTheme::print_yellow(&format!("Package Release - {}\n", project), theme);
+    AnsiTheme::print_themed(&format!("Format (zip or tar.gz) [{}]: ", default_format.extension()), theme);
+    io::stdout().flush().unwrap();
+    let mut input = String::new();
+    io::stdin().read_line(&mut input).unwrap();
+    let format = match input.trim() {
+        "" => default_format,
+        text => match ArchiveFormat::parse(text) {
+            Ok(format) => format,
+            Err(e) => {
+                AnsiTheme::print_error(&format!("Error: {}\n", e), theme);
+                return;
             }
-            
+        },
+    };
+
+    // Extra files are remembered in the project settings for the next package
+    let current = settings.package.extra_files.join(", ");
+    AnsiTheme::print_themed(&format!("Extra files/directories, comma-separated [{}] ('-' clears): ", current), theme);
+    io::stdout().flush().unwrap();
+    let mut input = String::new();
+    io::stdin().read_line(&mut input).unwrap();
+    let extra_files: Vec<String> = match input.trim() {
+        "" => settings.package.extra_files.clone(),
+        "-" => Vec::new(),
+        text => text.split(',').map(|f| f.trim().to_string()).filter(|f| !f.is_empty()).collect(),
+    };
+    if extra_files != settings.package.extra_files {
+        settings.package.extra_files = extra_files;
+        if let Err(e) = settings.save(&project_path) {
+            AnsiTheme::print_error(&format!("Error: {}\n", e), theme);
+        }
+    }
+
+    let has_release = build_system.get_build_info(project, theme)
+        .map(|info| !info.release_binaries.is_empty())
+        .unwrap_or(false);
+    if !has_release {
+        AnsiTheme::print_themed("No release build found. Build release now? (y/N): ", theme);
+        io::stdout().flush().unwrap();
+        let mut confirm = String::new();
+        io::stdin().read_line(&mut confirm).unwrap();
+        if !confirm.trim().eq_ignore_ascii_case("y") {
+            AnsiTheme::print_themed("Cancelled.\n", theme);
+            return;
+        }
+        match build_system.build_release(project, theme) {
+            Ok(result) if result.success => {}
+            Ok(_) => {
+                AnsiTheme::print_error(" Release build failed - nothing packaged\n", theme);
+                return;
+            }
+            Err(e) => {
+                AnsiTheme::print_error(&format!("Error: {}\n", e), theme);
+                return;
+            }
+        }
+    }
+
+    match build_system.package_release(project, Some(format), theme) {
+        Ok(result) => {
             AnsiTheme::print_themed("\n", theme);
-            AnsiTheme::print_cyan("Test output:\n", theme);
-            AnsiTheme::print_themed(&test_result.stdout, theme);
-            
-            if !test_result.stderr.is_empty() {
-                AnsiTheme::print_themed(&test_result.stderr, theme);
+            for file in &result.files {
+                AnsiTheme::print_themed(&format!("  {}\n", file), theme);
             }
+            AnsiTheme::print_success(&format!(" Packaged {} ({} bytes)\n", result.archive_path.display(), result.size_bytes), theme);
+            AnsiTheme::print_info(&format!(" SHA-256: {} ({})\n", result.sha256, result.checksum_path.display()), theme);
         }
         Err(e) => AnsiTheme::print_error(&format!("Error: {}\n", e), theme),
     }
 }
 
+fn watch_menu(build_system: &BuildSystem, project: &str, theme: &ThemeConfig) {
+    AnsiTheme::print_themed("\n", theme);
+    AnsiTheme::print_yellow("Watch - choose what to run on each change:\n", theme);
+    AnsiTheme::print_themed("1) Check\n", theme);
+    AnsiTheme::print_themed("2) Build\n", theme);
+    AnsiTheme::print_themed("3) Test\n", theme);
+    AnsiTheme::print_themed("4) Run\n", theme);
+    AnsiTheme::print_themed("Enter choice (empty to cancel): ", theme);
+    io::stdout().flush().unwrap();
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input).unwrap();
+    let action = match input.trim() {
+        "1" => WatchAction::Check,
+        "2" => WatchAction::Build,
+        "3" => WatchAction::Test,
+        "4" => WatchAction::Run,
+        _ => {
+            AnsiTheme::print_themed("Cancelled.\n", theme);
+            return;
+        }
+    };
+
+    let stop = Arc::new(AtomicBool::new(false));
+    if action == WatchAction::Run {
+        // The program keeps stdin, so Ctrl-C ends this watch instead of Enter
+        AnsiTheme::print_info(" Watching src/, tests/, examples/ and Cargo.toml - restarting the program on changes. Press Ctrl-C to stop.\n", theme);
+    } else {
+        AnsiTheme::print_info(&format!(" Watching src/, tests/, examples/ and Cargo.toml - running {} on changes. Press Enter to stop.\n", action.name()), theme);
+
+        // Enter on stdin ends the watch; the watcher checks the flag between polls
+        let stop_signal = Arc::clone(&stop);
+        std::thread::spawn(move || {
+            let mut line = String::new();
+            let _ = io::stdin().read_line(&mut line);
+            stop_signal.store(true, Ordering::Relaxed);
+        });
+    }
+
+    watcher::watch_project(build_system, project, action, &stop, theme, |action, result| {
+        match result {
+            Ok(true) => AnsiTheme::print_success(&format!(" {} passed - waiting for changes...\n", action.name()), theme),
+            Ok(false) => AnsiTheme::print_error(&format!(" {} failed - waiting for changes...\n", action.name()), theme),
+            Err(e) => AnsiTheme::print_error(&format!("Error: {}\n", e), theme),
+        }
+    });
+
+    AnsiTheme::print_themed("Stopped watching.\n", theme);
+}
+
 fn clean_project(build_system: &BuildSystem, project: &str, theme: &ThemeConfig) {
     match build_system.clean_project(project, theme) {
         Ok(()) => AnsiTheme::print_success(" Project cleaned successfully\n", theme),
@@ -176,39 +372,27 @@ fn show_build_info(build_system: &BuildSystem, project: &str, theme: &ThemeConfi
                 if build_info.release_exists { "✓ Available" } else { "✗ Not built" }), theme);
             
             if build_info.debug_exists {
-                AnsiTheme::print_themed(&format!("Debug binary: {}\n", build_info.debug_path.display()), theme);
+                AnsiTheme::print_themed(&format!("Debug binary: {} ({})\n", build_info.debug_path.display(), file_size(&build_info.debug_path)), theme);
             }
             if build_info.release_exists {
-                AnsiTheme::print_themed(&format!("Release binary: {}\n", build_info.release_path.display()), theme);
+                AnsiTheme::print_themed(&format!("Release binary: {} ({})\n", build_info.release_path.display(), file_size(&build_info.release_path)), theme);
             }
         }
         Err(e) => AnsiTheme::print_error(&format!("Error: {}\n", e), theme),
     }
 }
 
-fn show_recent_logs(theme: &ThemeConfig) {
-    AnsiTheme::print_themed("\n", theme);
-    AnsiTheme::print_cyan("Recent Logs:\n", theme);
-    if let Ok(content) = std::fs::read_to_string("build_tool.log") {
-        let lines: Vec<&str> = content.lines().collect();
-        let recent_lines = if lines.len() > 20 {
-            &lines[lines.len() - 20..]
-        } else {
-            &lines
-        };
-        
-        for line in recent_lines {
-            if line.contains("ERROR") {
-                AnsiTheme::print_error(&format!("{}\n", line), theme);
-            } else if line.contains("WARN") {
-                AnsiTheme::print_warning(&format!("{}\n", line), theme);
-            } else if line.contains("INFO") {
-                AnsiTheme::print_info(&format!("{}\n", line), theme);
-            } else {
-                AnsiTheme::print_themed(&format!("{}\n", line), theme);
-            }
-        }
-    } else {
-        AnsiTheme::print_themed("No log file found.\n", theme);
+fn file_size(path: &std::path::Path) -> String {
+    std::fs::metadata(path).map(|m| size_report::format_bytes(m.len())).unwrap_or_default()
+}
+
+fn show_size_report(build_system: &BuildSystem, project: &str, theme: &ThemeConfig) {
+    match build_system.binary_size_report(project, 10, theme) {
+        Ok(report) => report.print(10, theme),
+        Err(e) => AnsiTheme::print_error(&format!("Error: {}\n", e), theme),
     }
-}
\ No newline at end of file
+}
+
+fn show_build_history(build_system: &BuildSystem, project: &str, theme: &ThemeConfig) {
+    HistoryReport::new(project, &build_system.history_entries(project), 10).print(theme);
+}
@@ -18,7 +18,7 @@ pub fn cargo_management_menu(file_manager: &FileManager, current_project: &Optio
         AnsiTheme::print_themed("\n", theme);
         AnsiTheme::print_green(&format!("Cargo.toml Management - {}\n", project), theme);
         AnsiTheme::print_themed("1) View Cargo.toml info\n", theme);
-        AnsiTheme::print_themed("2) Open Cargo.toml in Notepad (View/Edit)\n", theme);
+        AnsiTheme::print_themed("2) Open Cargo.toml in Editor (View/Edit)\n", theme);
         AnsiTheme::print_themed("3) Add dependency (quick)\n", theme);
         AnsiTheme::print_themed("4) Update package version (quick)\n", theme);
         AnsiTheme::print_themed("B) Back to main menu\n", theme);
@@ -53,7 +53,7 @@ pub fn cargo_management_menu(file_manager: &FileManager, current_project: &Optio
                 }
             }
             "2" => {
-                match file_manager.open_cargo_toml_in_notepad(&project) {
+                match file_manager.open_cargo_toml_in_editor(&project) {
                     Ok(()) => AnsiTheme::print_success(" Cargo.toml editing complete.\n", theme),
                     Err(e) => AnsiTheme::print_error(&format!("Error: {}\n", e), theme),
                 }
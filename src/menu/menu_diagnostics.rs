@@ -0,0 +1,152 @@
+// menu_diagnostics.rs
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+use std::path::Path;
+use crate::ansi_theme::AnsiTheme;
+use crate::config::ThemeConfig;
+use crate::diagnostics::{Diagnostic, DiagnosticLevel};
+use super::editor::open_file_in_editor_with_cursor;
+
+#[derive(Clone, Copy, PartialEq)]
+enum Filter {
+    All,
+    Errors,
+    Warnings,
+}
+
+impl Filter {
+    fn matches(&self, diagnostic: &Diagnostic) -> bool {
+        match self {
+            Filter::All => true,
+            Filter::Errors => diagnostic.level == DiagnosticLevel::Error,
+            Filter::Warnings => diagnostic.level == DiagnosticLevel::Warning,
+        }
+    }
+}
+
+/// Browses diagnostics; file paths are resolved against `project_path`, the directory cargo ran in
+pub fn diagnostics_menu(diagnostics: &[Diagnostic], project_path: &Path, theme: &ThemeConfig) {
+    if diagnostics.is_empty() {
+        AnsiTheme::print_success(" No diagnostics from the last build or check.\n", theme);
+        return;
+    }
+
+    let mut filter = Filter::All;
+
+    loop {
+        // Errors first, then warnings, each grouped by file in line order
+        let mut visible: Vec<&Diagnostic> = diagnostics.iter().filter(|d| filter.matches(d)).collect();
+        visible.sort_by_key(|d| {
+            let span = d.primary_span();
+            (d.level, span.map(|s| s.file.clone()), span.map(|s| (s.line_start, s.column_start)))
+        });
+
+        print_summary(diagnostics, theme);
+        print_grouped(&visible, theme);
+
+        AnsiTheme::print_themed("\nEnter a number for details, E) Errors only, W) Warnings only, A) All, B) Back: ", theme);
+        io::stdout().flush().unwrap();
+
+        let mut input = String::new();
+        io::stdin().read_line(&mut input).unwrap();
+        let choice = input.trim();
+
+        match choice {
+            "E" | "e" => filter = Filter::Errors,
+            "W" | "w" => filter = Filter::Warnings,
+            "A" | "a" => filter = Filter::All,
+            "B" | "b" => break,
+            _ => match choice.parse::<usize>() {
+                Ok(n) if n >= 1 && n <= visible.len() => {
+                    show_details(visible[n - 1], theme);
+                    offer_to_open(visible[n - 1], project_path, theme);
+                }
+                _ => AnsiTheme::print_themed("Invalid choice.\n", theme),
+            },
+        }
+    }
+}
+
+fn print_summary(diagnostics: &[Diagnostic], theme: &ThemeConfig) {
+    let errors = diagnostics.iter().filter(|d| d.level == DiagnosticLevel::Error).count();
+    let warnings = diagnostics.iter().filter(|d| d.level == DiagnosticLevel::Warning).count();
+
+    AnsiTheme::print_themed("\n", theme);
+    AnsiTheme::print_yellow(&format!("Diagnostics - {} errors, {} warnings\n", errors, warnings), theme);
+
+    // The most frequent lint codes make bulk warnings easier to triage
+    let mut by_code: BTreeMap<&str, usize> = BTreeMap::new();
+    for diagnostic in diagnostics {
+        if let Some(code) = &diagnostic.code {
+            *by_code.entry(code.as_str()).or_default() += 1;
+        }
+    }
+    let mut counts: Vec<(&str, usize)> = by_code.into_iter().collect();
+    counts.sort_by_key(|(_, n)| std::cmp::Reverse(*n));
+    if !counts.is_empty() {
+        let top: Vec<String> = counts.iter().take(5).map(|(code, n)| format!("{} x{}", code, n)).collect();
+        AnsiTheme::print_themed(&format!("Most frequent: {}\n", top.join(", ")), theme);
+    }
+}
+
+fn print_grouped(visible: &[&Diagnostic], theme: &ThemeConfig) {
+    let mut current_group: Option<(DiagnosticLevel, String)> = None;
+
+    for (i, diagnostic) in visible.iter().enumerate() {
+        let file = diagnostic.primary_span().map(|s| s.file.clone()).unwrap_or_else(|| "(no file)".to_string());
+        let group = (diagnostic.level, file.clone());
+
+        if current_group.as_ref() != Some(&group) {
+            let header = format!("\n{} - {}\n", diagnostic.level.as_str().to_uppercase(), file);
+            match diagnostic.level {
+                DiagnosticLevel::Error => AnsiTheme::print_error(&header, theme),
+                DiagnosticLevel::Warning => AnsiTheme::print_warning(&header, theme),
+                _ => AnsiTheme::print_info(&header, theme),
+            }
+            current_group = Some(group);
+        }
+
+        let position = diagnostic.primary_span()
+            .map(|s| format!("{}:{}", s.line_start, s.column_start))
+            .unwrap_or_default();
+        AnsiTheme::print_themed(&format!("{:>3}) {:<8} {}\n", i + 1, position, diagnostic.headline()), theme);
+    }
+}
+
+fn show_details(diagnostic: &Diagnostic, theme: &ThemeConfig) {
+    AnsiTheme::print_themed("\n", theme);
+    match &diagnostic.rendered {
+        Some(rendered) => AnsiTheme::print_themed(rendered, theme),
+        None => AnsiTheme::print_themed(&format!("{}\n --> {}\n", diagnostic.headline(), diagnostic.location()), theme),
+    }
+
+    if !diagnostic.suggestions.is_empty() {
+        AnsiTheme::print_cyan("Suggested fixes:\n", theme);
+        for suggestion in &diagnostic.suggestions {
+            AnsiTheme::print_themed(&format!(" {}:{}:{} {} -> `{}`\n",
+                suggestion.file, suggestion.line, suggestion.column, suggestion.message, suggestion.replacement), theme);
+        }
+    }
+}
+
+fn offer_to_open(diagnostic: &Diagnostic, project_path: &Path, theme: &ThemeConfig) {
+    let span = match diagnostic.primary_span() {
+        Some(span) => span,
+        None => return,
+    };
+
+    AnsiTheme::print_themed(&format!("Open {} in the editor? (y/N): ", diagnostic.location()), theme);
+    io::stdout().flush().unwrap();
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input).unwrap();
+    if !input.trim().eq_ignore_ascii_case("y") {
+        return;
+    }
+
+    // rustc reports 1-based line/column; the editor cursor is a zero-based (column, row)
+    let cursor_position = (span.column_start.saturating_sub(1), span.line_start.saturating_sub(1));
+    if let Err(e) = open_file_in_editor_with_cursor(project_path.join(&span.file), cursor_position, theme.clone()) {
+        AnsiTheme::print_error(&format!("Editor error: {}\n", e), theme);
+    }
+}
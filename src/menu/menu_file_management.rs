@@ -19,7 +19,7 @@ pub fn file_management_menu(file_manager: &FileManager, current_project: &Option
         AnsiTheme::print_green(&format!("File Management - {}\n", project), theme);
         AnsiTheme::print_themed("1) List Rust files in project\n", theme);
         AnsiTheme::print_themed("2) Create new Rust file\n", theme);
-        AnsiTheme::print_themed("3) Open Rust file in Notepad (View/Edit)\n", theme);
+        AnsiTheme::print_themed("3) Open Rust file in Editor (View/Edit)\n", theme);
         AnsiTheme::print_themed("4) Delete Rust file\n", theme);
         AnsiTheme::print_themed("5) Advanced File Operations\n", theme);
         AnsiTheme::print_themed("B) Back to main menu\n", theme);
@@ -92,14 +92,14 @@ fn create_rust_file_menu(file_manager: &FileManager, project: &str, theme: &Them
         Ok(()) => {
             AnsiTheme::print_success(" File created successfully!\n", theme);
             
-            AnsiTheme::print_themed("Open file in Notepad? (y/N): ", theme);
+            AnsiTheme::print_themed("Open file in editor? (y/N): ", theme);
             io::stdout().flush().unwrap();
             
             let mut open_choice = String::new();
             io::stdin().read_line(&mut open_choice).unwrap();
             
             if open_choice.trim().eq_ignore_ascii_case("y") {
-                if let Err(e) = file_manager.open_file_in_notepad(project, file_path) {
+                if let Err(e) = file_manager.open_file_in_editor(project, file_path) {
                     AnsiTheme::print_error(&format!("Error opening file: {}\n", e), theme);
                 }
             }
@@ -110,7 +110,7 @@ fn create_rust_file_menu(file_manager: &FileManager, project: &str, theme: &Them
 
 fn open_rust_file_menu(file_manager: &FileManager, project: &str, theme: &ThemeConfig) {
     AnsiTheme::print_themed("\n", theme);
-    AnsiTheme::print_blue("Open Rust file in Notepad:\n", theme);
+    AnsiTheme::print_blue("Open Rust file in editor:\n", theme);
     
     let file_path = match file_manager.select_file_from_list(project) {
         Ok(Some(path)) => path,
@@ -121,7 +121,7 @@ fn open_rust_file_menu(file_manager: &FileManager, project: &str, theme: &ThemeC
         }
     };
 
-    match file_manager.open_file_in_notepad(project, &file_path) {
+    match file_manager.open_file_in_editor(project, &file_path) {
         Ok(()) => AnsiTheme::print_success(" File editing complete.\n", theme),
         Err(e) => AnsiTheme::print_error(&format!("Error: {}\n", e), theme),
     }
@@ -142,7 +142,7 @@ fn add_specific_files_menu(git_manager: &GitManager, project: &str, theme: &Them
             if let Ok(num) = choice.parse::<usize>() {
                 if num > 0 && num <= status.files.len() {
                     let selected_file = &status.files[num - 1];
-                    match git_manager.add_files(project, &[selected_file.file_path.clone()]) {
+                    match git_manager.add_files(project, std::slice::from_ref(&selected_file.file_path)) {
                         Ok(()) => AnsiTheme::print_success(&format!(" File '{}' added\n", selected_file.file_path), theme),
                         Err(e) => AnsiTheme::print_error(&format!("Error: {}\n", e), theme),
                     }
@@ -0,0 +1,196 @@
+// menu_jobs.rs
+use std::io::{self, Write};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use crate::ansi_theme::AnsiTheme;
+use crate::config::ThemeConfig;
+use crate::jobs::{JobInfo, JobManager};
+use crate::python_manager::PythonManager;
+
+pub fn jobs_menu(job_manager: &JobManager, python_manager: &PythonManager, current_project: &Option<String>, theme: &ThemeConfig) {
+    loop {
+        AnsiTheme::print_themed("\n", theme);
+        AnsiTheme::print_yellow(&format!("Background Jobs ({} running)\n", job_manager.running_count()), theme);
+        AnsiTheme::print_themed("1) Start cargo run\n", theme);
+        AnsiTheme::print_themed("2) Start Python script\n", theme);
+        AnsiTheme::print_themed("3) Start custom command\n", theme);
+        AnsiTheme::print_themed("4) List jobs\n", theme);
+        AnsiTheme::print_themed("5) Tail job output\n", theme);
+        AnsiTheme::print_themed("6) Stop job\n", theme);
+        AnsiTheme::print_themed("7) Kill job\n", theme);
+        AnsiTheme::print_themed("8) Remove finished jobs\n", theme);
+        AnsiTheme::print_themed("B) Back to main menu\n", theme);
+
+        AnsiTheme::print_themed("Enter choice: ", theme);
+        io::stdout().flush().unwrap();
+
+        match read_line().as_str() {
+            "1" => match current_project {
+                Some(project) => {
+                    let args = prompt("Arguments (space-separated, empty for none): ", theme);
+                    let args: Vec<String> = args.split_whitespace().map(|s| s.to_string()).collect();
+                    report_started(job_manager.start_cargo_run(project, &args), theme);
+                }
+                None => AnsiTheme::print_error(" No project selected.\n", theme),
+            },
+            "2" => match current_project {
+                Some(project) => match python_manager.select_file_from_list(project, theme) {
+                    Ok(Some(script)) => {
+                        let args = prompt("Arguments (space-separated, empty for none): ", theme);
+                        let args: Vec<String> = args.split_whitespace().map(|s| s.to_string()).collect();
+                        report_started(job_manager.start_python_script(project, &script, &args), theme);
+                    }
+                    Ok(None) => {}
+                    Err(e) => AnsiTheme::print_error(&format!("Error: {}\n", e), theme),
+                },
+                None => AnsiTheme::print_error(" No project selected.\n", theme),
+            },
+            "3" => {
+                let command = prompt("Command (empty to cancel): ", theme);
+                if command.is_empty() {
+                    AnsiTheme::print_themed("Cancelled.\n", theme);
+                    continue;
+                }
+                report_started(job_manager.start_command(current_project.as_deref(), &command), theme);
+            }
+            "4" => print_jobs(&job_manager.list(), theme),
+            "5" => {
+                if let Some(id) = pick_job(job_manager, theme) {
+                    follow_output(job_manager, id, theme);
+                }
+            }
+            "6" => {
+                if let Some(id) = pick_job(job_manager, theme) {
+                    AnsiTheme::print_themed("Stopping...\n", theme);
+                    match job_manager.stop(id) {
+                        Ok(()) => AnsiTheme::print_success(&format!(" Job {} stopped\n", id), theme),
+                        Err(e) => AnsiTheme::print_error(&format!("Error: {}\n", e), theme),
+                    }
+                }
+            }
+            "7" => {
+                if let Some(id) = pick_job(job_manager, theme) {
+                    match job_manager.kill(id) {
+                        Ok(()) => AnsiTheme::print_success(&format!(" Job {} killed\n", id), theme),
+                        Err(e) => AnsiTheme::print_error(&format!("Error: {}\n", e), theme),
+                    }
+                }
+            }
+            "8" => {
+                let removed = job_manager.remove_finished();
+                AnsiTheme::print_success(&format!(" Removed {} finished job(s)\n", removed), theme);
+            }
+            "B" | "b" => break,
+            _ => AnsiTheme::print_themed("Invalid choice.\n", theme),
+        }
+    }
+}
+
+pub fn report_started(result: Result<JobInfo, String>, theme: &ThemeConfig) {
+    match result {
+        Ok(job) => {
+            AnsiTheme::print_success(&format!(" Started job {}: {} (PID {})\n", job.id, job.name, job.pid), theme);
+            AnsiTheme::print_info(&format!(" Output: {}\n", job.log_path.display()), theme);
+        }
+        Err(e) => AnsiTheme::print_error(&format!("Error: {}\n", e), theme),
+    }
+}
+
+fn print_jobs(jobs: &[JobInfo], theme: &ThemeConfig) {
+    if jobs.is_empty() {
+        AnsiTheme::print_themed("No background jobs.\n", theme);
+        return;
+    }
+
+    AnsiTheme::print_themed("\n", theme);
+    AnsiTheme::print_blue(&format!(" {:<4} {:<8} {:<12} {:<10} {}\n", "ID", "PID", "STATE", "UPTIME", "JOB"), theme);
+    for job in jobs {
+        let uptime = format_uptime(job.uptime);
+        let line = format!(" {:<4} {:<8} {:<12} {:<10} {}\n", job.id, job.pid, job.state(), uptime, job.name);
+        if job.running {
+            AnsiTheme::print_themed(&line, theme);
+        } else {
+            AnsiTheme::print_cyan(&line, theme);
+        }
+    }
+}
+
+// e.g. "1h02m", "3m07s", "12s"
+fn format_uptime(uptime: Duration) -> String {
+    let secs = uptime.as_secs();
+    match secs {
+        0..=59 => format!("{}s", secs),
+        60..=3599 => format!("{}m{:02}s", secs / 60, secs % 60),
+        _ => format!("{}h{:02}m", secs / 3600, secs % 3600 / 60),
+    }
+}
+
+fn pick_job(job_manager: &JobManager, theme: &ThemeConfig) -> Option<usize> {
+    let jobs = job_manager.list();
+    if jobs.is_empty() {
+        AnsiTheme::print_themed("No background jobs.\n", theme);
+        return None;
+    }
+    print_jobs(&jobs, theme);
+
+    match prompt("Job ID: ", theme).parse::<usize>() {
+        Ok(id) if jobs.iter().any(|j| j.id == id) => Some(id),
+        _ => {
+            AnsiTheme::print_themed("Invalid choice.\n", theme);
+            None
+        }
+    }
+}
+
+// Shows the last lines of the log, then new output as it arrives until Enter is pressed
+fn follow_output(job_manager: &JobManager, id: usize, theme: &ThemeConfig) {
+    match job_manager.tail(id, 20) {
+        Ok(lines) => {
+            AnsiTheme::print_themed("\n", theme);
+            for line in lines {
+                AnsiTheme::print_themed(&format!("{}\n", line), theme);
+            }
+        }
+        Err(e) => {
+            AnsiTheme::print_error(&format!("Error: {}\n", e), theme);
+            return;
+        }
+    }
+    let mut offset = match job_manager.read_log_from(id, 0) {
+        Ok((_, end)) => end,
+        Err(_) => return,
+    };
+
+    AnsiTheme::print_info(" Following output - press Enter to stop.\n", theme);
+    let stop = Arc::new(AtomicBool::new(false));
+    let stop_signal = Arc::clone(&stop);
+    std::thread::spawn(move || {
+        let mut line = String::new();
+        let _ = io::stdin().read_line(&mut line);
+        stop_signal.store(true, Ordering::Relaxed);
+    });
+
+    while !stop.load(Ordering::Relaxed) {
+        if let Ok((text, end)) = job_manager.read_log_from(id, offset) {
+            if !text.is_empty() {
+                AnsiTheme::print_themed(&text, theme);
+                io::stdout().flush().unwrap();
+            }
+            offset = end;
+        }
+        std::thread::sleep(Duration::from_millis(250));
+    }
+}
+
+fn prompt(label: &str, theme: &ThemeConfig) -> String {
+    AnsiTheme::print_themed(label, theme);
+    io::stdout().flush().unwrap();
+    read_line()
+}
+
+fn read_line() -> String {
+    let mut input = String::new();
+    io::stdin().read_line(&mut input).unwrap();
+    input.trim().to_string()
+}
@@ -97,7 +97,7 @@ fn run_python_script_menu(python_manager: &PythonManager, project: &str, theme:
 
     let mut args_input = String::new();
     io::stdin().read_line(&mut args_input).unwrap();
-    let args: Vec<String> = args_input.trim().split_whitespace().map(|s| s.to_string()).collect();
+    let args: Vec<String> = args_input.split_whitespace().map(|s| s.to_string()).collect();
 
     match python_manager.run_python_script(project, &file_path, &args, theme) {
         Ok(result) => {
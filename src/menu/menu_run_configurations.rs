@@ -0,0 +1,195 @@
+// menu_run_configurations.rs
+use std::io::{self, Write};
+use std::path::Path;
+use crate::ansi_theme::AnsiTheme;
+use crate::build_system::BuildSystem;
+use crate::config::ThemeConfig;
+use crate::project_settings::{ProjectSettings, RunConfiguration};
+use crate::python_manager::PythonManager;
+
+/// Lists the project's saved run configurations and runs the chosen one
+pub fn run_configurations_menu(build_system: &BuildSystem, python_manager: &PythonManager, project: &str, theme: &ThemeConfig) {
+    let project_path = build_system.get_project_path(project);
+
+    loop {
+        let mut settings = match ProjectSettings::load(&project_path) {
+            Ok(settings) => settings,
+            Err(e) => {
+                AnsiTheme::print_error(&format!("Error: {}\n", e), theme);
+                return;
+            }
+        };
+
+        AnsiTheme::print_themed("\n", theme);
+        AnsiTheme::print_yellow(&format!("Run Configurations - {}\n", project), theme);
+        if settings.run_configurations.is_empty() {
+            AnsiTheme::print_themed("No run configurations saved yet.\n", theme);
+        }
+        for (i, configuration) in settings.run_configurations.iter().enumerate() {
+            AnsiTheme::print_themed(&format!("{}) {:<16} {}\n", i + 1, configuration.name, configuration.summary()), theme);
+        }
+        AnsiTheme::print_themed("N) New configuration\n", theme);
+        AnsiTheme::print_themed("E) Edit configuration\n", theme);
+        AnsiTheme::print_themed("D) Delete configuration\n", theme);
+        AnsiTheme::print_themed("B) Back\n", theme);
+        AnsiTheme::print_themed("Enter a number to run, or a choice: ", theme);
+        io::stdout().flush().unwrap();
+
+        let choice = read_line();
+        match choice.as_str() {
+            "N" | "n" => {
+                if let Some(configuration) = prompt_configuration(&RunConfiguration::default(), theme) {
+                    save_configuration(&mut settings, configuration, &project_path, theme);
+                }
+            }
+            "E" | "e" => {
+                if let Some(index) = pick_configuration(&settings, "edit", theme) {
+                    let existing = settings.run_configurations[index].clone();
+                    if let Some(configuration) = prompt_configuration(&existing, theme) {
+                        // A rename replaces the old entry rather than duplicating it
+                        settings.remove_run_configuration(&existing.name);
+                        save_configuration(&mut settings, configuration, &project_path, theme);
+                    }
+                }
+            }
+            "D" | "d" => {
+                if let Some(index) = pick_configuration(&settings, "delete", theme) {
+                    let name = settings.run_configurations[index].name.clone();
+                    settings.remove_run_configuration(&name);
+                    match settings.save(&project_path) {
+                        Ok(()) => AnsiTheme::print_success(&format!(" Deleted '{}'\n", name), theme),
+                        Err(e) => AnsiTheme::print_error(&format!("Error: {}\n", e), theme),
+                    }
+                }
+            }
+            "B" | "b" => return,
+            _ => match choice.parse::<usize>() {
+                Ok(n) if n >= 1 && n <= settings.run_configurations.len() => {
+                    run_configuration(build_system, python_manager, project, &settings.run_configurations[n - 1], theme);
+                }
+                _ => AnsiTheme::print_themed("Invalid choice.\n", theme),
+            },
+        }
+    }
+}
+
+fn run_configuration(build_system: &BuildSystem, python_manager: &PythonManager, project: &str, configuration: &RunConfiguration, theme: &ThemeConfig) {
+    if configuration.is_python() {
+        match python_manager.run_with_configuration(project, configuration, &[], theme) {
+            Ok(result) => {
+                AnsiTheme::print_themed("\n", theme);
+                AnsiTheme::print_green("Python Output:\n", theme);
+                if !result.stdout.is_empty() {
+                    AnsiTheme::print_themed(&result.stdout, theme);
+                }
+                if !result.stderr.is_empty() {
+                    AnsiTheme::print_error("Python Errors:\n", theme);
+                    AnsiTheme::print_themed(&result.stderr, theme);
+                }
+                AnsiTheme::print_blue(&format!(" Exit code: {}\n", result.exit_code), theme);
+            }
+            Err(e) => AnsiTheme::print_error(&format!("Error: {}\n", e), theme),
+        }
+        return;
+    }
+
+    match build_system.run_with_configuration(project, configuration, &[], theme) {
+        Ok(result) => {
+            AnsiTheme::print_themed("\n", theme);
+            match result.termination.interruption() {
+                Some(reason) => AnsiTheme::print_error(&format!(" Run '{}' {}! (Duration: {:.2?})\n", configuration.name, reason, result.duration), theme),
+                None => AnsiTheme::print_blue(&format!(" Exit code: {} (Duration: {:.2?})\n", result.exit_code, result.duration), theme),
+            }
+        }
+        Err(e) => AnsiTheme::print_error(&format!("Error: {}\n", e), theme),
+    }
+}
+
+fn save_configuration(settings: &mut ProjectSettings, configuration: RunConfiguration, project_path: &Path, theme: &ThemeConfig) {
+    let name = configuration.name.clone();
+    settings.set_run_configuration(configuration);
+    match settings.save(project_path) {
+        Ok(()) => AnsiTheme::print_success(&format!(" Saved run configuration '{}'\n", name), theme),
+        Err(e) => AnsiTheme::print_error(&format!("Error: {}\n", e), theme),
+    }
+}
+
+fn pick_configuration(settings: &ProjectSettings, verb: &str, theme: &ThemeConfig) -> Option<usize> {
+    if settings.run_configurations.is_empty() {
+        AnsiTheme::print_themed("No run configurations saved yet.\n", theme);
+        return None;
+    }
+    AnsiTheme::print_themed(&format!("Number of the configuration to {}: ", verb), theme);
+    io::stdout().flush().unwrap();
+
+    match read_line().parse::<usize>() {
+        Ok(n) if n >= 1 && n <= settings.run_configurations.len() => Some(n - 1),
+        _ => {
+            AnsiTheme::print_themed("Invalid choice.\n", theme);
+            None
+        }
+    }
+}
+
+/// Asks for each field, showing `current` values that Enter keeps. `-` clears a field.
+fn prompt_configuration(current: &RunConfiguration, theme: &ThemeConfig) -> Option<RunConfiguration> {
+    AnsiTheme::print_themed("\n", theme);
+    AnsiTheme::print_info("Press Enter to keep the value in brackets, '-' to clear it. Paths are relative to the project.\n", theme);
+
+    let name = ask("Name", Some(current.name.clone()).filter(|n| !n.is_empty()), theme)?;
+    let script = ask("Python script (empty for cargo run)", current.script.clone(), theme);
+    let args = ask("Program arguments", Some(current.args.join(" ")).filter(|a| !a.is_empty()), theme)
+        .map(|a| a.split_whitespace().map(|s| s.to_string()).collect())
+        .unwrap_or_default();
+    let env_file = ask(".env file", current.env_file.clone(), theme);
+    let env = ask("Environment (KEY=VALUE, space-separated)", Some(format_env(current)).filter(|e| !e.is_empty()), theme)
+        .map(|e| e.split_whitespace()
+            .map(|pair| match pair.split_once('=') {
+                Some((key, value)) => (key.to_string(), value.to_string()),
+                None => (pair.to_string(), String::new()),
+            })
+            .collect())
+        .unwrap_or_default();
+
+    let configuration = RunConfiguration {
+        name,
+        script,
+        args,
+        env_file,
+        env,
+        stdin_file: ask("Stdin input file", current.stdin_file.clone(), theme),
+        working_dir: ask("Working directory", current.working_dir.clone(), theme),
+    };
+
+    match configuration.validate() {
+        Ok(()) => Some(configuration),
+        Err(e) => {
+            AnsiTheme::print_error(&format!("Error: {}\n", e), theme);
+            None
+        }
+    }
+}
+
+fn format_env(configuration: &RunConfiguration) -> String {
+    configuration.env.iter().map(|(k, v)| format!("{}={}", k, v)).collect::<Vec<_>>().join(" ")
+}
+
+fn ask(label: &str, current: Option<String>, theme: &ThemeConfig) -> Option<String> {
+    match &current {
+        Some(value) => AnsiTheme::print_themed(&format!("{} [{}]: ", label, value), theme),
+        None => AnsiTheme::print_themed(&format!("{}: ", label), theme),
+    }
+    io::stdout().flush().unwrap();
+
+    match read_line().as_str() {
+        "" => current,
+        "-" => None,
+        value => Some(value.to_string()),
+    }
+}
+
+fn read_line() -> String {
+    let mut input = String::new();
+    io::stdin().read_line(&mut input).unwrap();
+    input.trim().to_string()
+}
@@ -0,0 +1,203 @@
+// menu_self_update.rs
+use std::io::{self, Write};
+use std::path::PathBuf;
+use crate::ansi_theme::AnsiTheme;
+use crate::config::ThemeConfig;
+use crate::backup::{BackupInfo, BackupKind};
+use crate::self_update::{RestoreTarget, SelfUpdater, UpdateOptions, UpdateSource};
+use crate::size_report::format_bytes;
+
+pub fn self_update_menu(updater: &SelfUpdater, theme: &ThemeConfig) {
+    loop {
+        AnsiTheme::print_themed("\n", theme);
+        AnsiTheme::print_yellow("Self-Update\n", theme);
+        AnsiTheme::print_themed("1) Update from Working Tree (bump patch version, rebuild, restart)\n", theme);
+        AnsiTheme::print_themed("2) Update from Git Tag, Branch or Commit\n", theme);
+        AnsiTheme::print_themed("3) Update from Local Directory\n", theme);
+        AnsiTheme::print_themed("4) Dry Run (show version, backup location and build output only)\n", theme);
+        AnsiTheme::print_themed("5) Backups & Rollback\n", theme);
+        AnsiTheme::print_themed("6) Restore Previous Executable (swap with the one kept by the last update)\n", theme);
+        AnsiTheme::print_themed("B) Back to main menu\n", theme);
+
+        AnsiTheme::print_themed("Enter choice: ", theme);
+        io::stdout().flush().unwrap();
+
+        let choice = read_line();
+        match choice.as_str() {
+            "1" => updater.check_for_updates(theme),
+            "2" | "3" => {
+                if let Some(source) = prompt_source(choice.as_str(), theme) {
+                    updater.run_update(&UpdateOptions { source, ..Default::default() }, theme);
+                }
+            }
+            "4" => {
+                AnsiTheme::print_themed("Source - 1) working tree, 2) git ref, 3) directory [1]: ", theme);
+                io::stdout().flush().unwrap();
+                let kind = read_line();
+                if let Some(source) = prompt_source(if kind.is_empty() { "1" } else { kind.as_str() }, theme) {
+                    updater.run_update(&UpdateOptions { source, dry_run: true, ..Default::default() }, theme);
+                }
+            }
+            "5" => backups_menu(updater, theme),
+            "6" => match updater.rollback_executable(theme) {
+                Ok(executable) => AnsiTheme::print_success(&format!(" Previous executable restored: restart {} to use it\n", executable.display()), theme),
+                Err(e) => AnsiTheme::print_error(&format!("Error: {}\n", e), theme),
+            },
+            "B" | "b" => break,
+            _ => AnsiTheme::print_themed("Invalid choice.\n", theme),
+        }
+    }
+}
+
+// "1" is the working tree, "2" asks for a git ref and "3" for a directory
+fn prompt_source(kind: &str, theme: &ThemeConfig) -> Option<UpdateSource> {
+    let (prompt, make): (&str, fn(String) -> UpdateSource) = match kind {
+        "1" => return Some(UpdateSource::WorkingTree),
+        "2" => ("Tag, branch or commit: ", UpdateSource::GitRef),
+        "3" => ("Source directory: ", |input| UpdateSource::Directory(PathBuf::from(input))),
+        _ => {
+            AnsiTheme::print_themed("Invalid choice.\n", theme);
+            return None;
+        }
+    };
+    AnsiTheme::print_themed(prompt, theme);
+    io::stdout().flush().unwrap();
+    let input = read_line();
+    if input.is_empty() {
+        AnsiTheme::print_themed("Cancelled.\n", theme);
+        return None;
+    }
+    Some(make(input))
+}
+
+fn backups_menu(updater: &SelfUpdater, theme: &ThemeConfig) {
+    loop {
+        let backups = match updater.list_backups() {
+            Ok(backups) => backups,
+            Err(e) => {
+                AnsiTheme::print_error(&format!("Error: {}\n", e), theme);
+                return;
+            }
+        };
+
+        AnsiTheme::print_themed("\n", theme);
+        AnsiTheme::print_yellow(&format!("Self-Update Backups ({})\n", backups.len()), theme);
+        if backups.is_empty() {
+            AnsiTheme::print_themed("No backups yet - one is made before every self-update.\n", theme);
+            return;
+        }
+        for (i, backup) in backups.iter().enumerate() {
+            let kind = match backup.kind {
+                BackupKind::Full => "zip",
+                BackupKind::Incremental => "incremental",
+            };
+            AnsiTheme::print_themed(&format!("{}) v{:<12} {}  {:>10}  {:<11}  {}\n",
+                i + 1,
+                backup.version,
+                backup.created_display(),
+                format_bytes(backup.size_bytes),
+                kind,
+                backup.path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default()), theme);
+        }
+        AnsiTheme::print_themed("B) Back\n", theme);
+
+        AnsiTheme::print_themed("Select a backup to restore: ", theme);
+        io::stdout().flush().unwrap();
+
+        let choice = read_line();
+        if choice.eq_ignore_ascii_case("b") {
+            break;
+        }
+        match choice.parse::<usize>().ok().and_then(|n| n.checked_sub(1)).and_then(|i| backups.get(i)) {
+            Some(backup) => {
+                if restore_backup_menu(updater, backup, theme) {
+                    break;
+                }
+            }
+            None => AnsiTheme::print_themed("Invalid choice.\n", theme),
+        }
+    }
+}
+
+// Returns true once the restored version has replaced the running executable
+fn restore_backup_menu(updater: &SelfUpdater, backup: &BackupInfo, theme: &ThemeConfig) -> bool {
+    let project_root = updater.project_root();
+    AnsiTheme::print_themed("\n", theme);
+    AnsiTheme::print_yellow(&format!("Restore v{} from {}\n", backup.version, backup.created_display()), theme);
+    AnsiTheme::print_themed("1) Into a new directory\n", theme);
+    match &project_root {
+        Some(root) => AnsiTheme::print_themed(&format!("2) Over the current project ({})\n", root.display()), theme),
+        None => AnsiTheme::print_themed("2) Over the current project (not found from this directory)\n", theme),
+    }
+    AnsiTheme::print_themed("Enter choice: ", theme);
+    io::stdout().flush().unwrap();
+
+    let target = match read_line().as_str() {
+        "1" => {
+            let default = default_restore_directory(project_root.as_ref(), backup);
+            AnsiTheme::print_themed(&format!("Directory [{}]: ", default.display()), theme);
+            io::stdout().flush().unwrap();
+            let input = read_line();
+            RestoreTarget::Directory(if input.is_empty() { default } else { PathBuf::from(input) })
+        }
+        "2" => {
+            let Some(root) = &project_root else {
+                AnsiTheme::print_error(" Could not find the current project.\n", theme);
+                return false;
+            };
+            AnsiTheme::print_warning(&format!("This overwrites the files in {} with the backup.\n", root.display()), theme);
+            AnsiTheme::print_themed("The current state is backed up first. Continue? (y/N): ", theme);
+            io::stdout().flush().unwrap();
+            if !read_line().eq_ignore_ascii_case("y") {
+                AnsiTheme::print_themed("Cancelled.\n", theme);
+                return false;
+            }
+            RestoreTarget::CurrentProject
+        }
+        _ => {
+            AnsiTheme::print_themed("Invalid choice.\n", theme);
+            return false;
+        }
+    };
+
+    let restored = match updater.restore_backup(backup, target, theme) {
+        Ok(restored) => restored,
+        Err(e) => {
+            AnsiTheme::print_error(&format!("Error: {}\n", e), theme);
+            return false;
+        }
+    };
+
+    AnsiTheme::print_themed("Rebuild it and replace the current executable? (y/N): ", theme);
+    io::stdout().flush().unwrap();
+    if !read_line().eq_ignore_ascii_case("y") {
+        AnsiTheme::print_themed(&format!("Source restored to {}\n", restored.display()), theme);
+        return false;
+    }
+    match updater.rebuild_and_install(&restored, theme) {
+        Ok(executable) => {
+            AnsiTheme::print_success(&format!(" Rolled back to v{}: restart {} to use it\n", backup.version, executable.display()), theme);
+            true
+        }
+        Err(e) => {
+            AnsiTheme::print_error(&format!("Error: {}\n", e), theme);
+            false
+        }
+    }
+}
+
+// Next to the project, e.g. "rust_dev_terminal-restored-v1.1.2-1714550000"
+fn default_restore_directory(project_root: Option<&PathBuf>, backup: &BackupInfo) -> PathBuf {
+    let name = project_root
+        .and_then(|root| root.file_name())
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "project".to_string());
+    let parent = project_root.and_then(|root| root.parent()).map(PathBuf::from).unwrap_or_default();
+    parent.join(format!("{}-restored-v{}-{}", name, backup.version, backup.created))
+}
+
+fn read_line() -> String {
+    let mut input = String::new();
+    io::stdin().read_line(&mut input).unwrap();
+    input.trim().to_string()
+}
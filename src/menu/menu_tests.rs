@@ -0,0 +1,130 @@
+// menu_tests.rs
+use std::io::{self, Write};
+use crate::ansi_theme::AnsiTheme;
+use crate::build_system::{BuildSystem, TestResult};
+use crate::config::ThemeConfig;
+use crate::test_report::{TestHistory, TestStatus};
+
+/// Runs the project's tests, shows a per-test summary and offers reruns of failed or filtered tests
+pub fn tests_menu(build_system: &BuildSystem, project: &str, theme: &ThemeConfig) {
+    let mut last_result = run_tests(build_system, project, &[], theme);
+
+    loop {
+        AnsiTheme::print_themed("\n", theme);
+        AnsiTheme::print_yellow(&format!("Tests - {}\n", project), theme);
+        AnsiTheme::print_themed("1) Run all tests\n", theme);
+        AnsiTheme::print_themed("2) Rerun only failed tests\n", theme);
+        AnsiTheme::print_themed("3) Run tests matching filter\n", theme);
+        AnsiTheme::print_themed("4) Show failure output\n", theme);
+        AnsiTheme::print_themed("5) Show summary table\n", theme);
+        AnsiTheme::print_themed("B) Back\n", theme);
+        AnsiTheme::print_themed("Enter choice: ", theme);
+        io::stdout().flush().unwrap();
+
+        let mut input = String::new();
+        io::stdin().read_line(&mut input).unwrap();
+
+        match input.trim() {
+            "1" => last_result = run_tests(build_system, project, &[], theme).or(last_result),
+            "2" => {
+                let failed = TestHistory::load(&build_system.get_project_path(project)).last_failed;
+                if failed.is_empty() {
+                    AnsiTheme::print_themed("No failed tests in the last run.\n", theme);
+                    continue;
+                }
+                let mut args = vec!["--exact".to_string()];
+                args.extend(failed);
+                last_result = run_tests(build_system, project, &args, theme).or(last_result);
+            }
+            "3" => {
+                AnsiTheme::print_themed("Filter (substring of the test name, empty to cancel): ", theme);
+                io::stdout().flush().unwrap();
+                let mut filter = String::new();
+                io::stdin().read_line(&mut filter).unwrap();
+                let filter = filter.trim();
+                if filter.is_empty() {
+                    AnsiTheme::print_themed("Cancelled.\n", theme);
+                    continue;
+                }
+                last_result = run_tests(build_system, project, &[filter.to_string()], theme).or(last_result);
+            }
+            "4" => match &last_result {
+                Some(result) => print_failures(result, theme),
+                None => AnsiTheme::print_themed("No test results yet.\n", theme),
+            },
+            "5" => match &last_result {
+                Some(result) => print_summary(result, theme),
+                None => AnsiTheme::print_themed("No test results yet.\n", theme),
+            },
+            "B" | "b" => break,
+            _ => AnsiTheme::print_themed("Invalid choice.\n", theme),
+        }
+    }
+}
+
+fn run_tests(build_system: &BuildSystem, project: &str, args: &[String], theme: &ThemeConfig) -> Option<TestResult> {
+    match build_system.test_with_args(project, args, theme) {
+        Ok(result) => {
+            print_summary(&result, theme);
+            if let Some(reason) = result.termination.interruption() {
+                AnsiTheme::print_error(&format!(" Tests {}! ({:.2?})\n", reason, result.duration), theme);
+            } else if result.success {
+                AnsiTheme::print_success(&format!(" Tests passed! ({:.2?})\n", result.duration), theme);
+            } else {
+                AnsiTheme::print_error(&format!(" Tests failed! ({:.2?})\n", result.duration), theme);
+            }
+            Some(result)
+        }
+        Err(e) => {
+            AnsiTheme::print_error(&format!("Error: {}\n", e), theme);
+            None
+        }
+    }
+}
+
+/// One row per test, failures first, with flaky tests marked
+pub fn print_summary(result: &TestResult, theme: &ThemeConfig) {
+    if result.cases.is_empty() {
+        AnsiTheme::print_themed("No test results found in the output.\n", theme);
+        return;
+    }
+
+    let mut cases: Vec<_> = result.cases.iter().collect();
+    cases.sort_by(|a, b| a.status.cmp(&b.status).then_with(|| a.name.cmp(&b.name)));
+
+    AnsiTheme::print_themed("\n", theme);
+    AnsiTheme::print_blue(&format!(" {:<8} {:<10} {}\n", "STATUS", "TIME", "TEST"), theme);
+    for case in cases {
+        let time = case.duration.map(|d| format!("{:.2?}", d)).unwrap_or_else(|| "-".to_string());
+        let flaky = if result.flaky.contains(&case.name) { "  [flaky]" } else { "" };
+        let line = format!(" {:<8} {:<10} {}{}\n", case.status.as_str(), time, case.name, flaky);
+        match case.status {
+            TestStatus::Ok => AnsiTheme::print_themed(&line, theme),
+            TestStatus::Failed => AnsiTheme::print_error(&line, theme),
+            TestStatus::Ignored => AnsiTheme::print_warning(&line, theme),
+        }
+    }
+
+    AnsiTheme::print_info(&format!(" {} passed, {} failed, {} ignored\n",
+        result.count(TestStatus::Ok), result.count(TestStatus::Failed), result.count(TestStatus::Ignored)), theme);
+    if !result.flaky.is_empty() {
+        AnsiTheme::print_warning(&format!(" {} flaky test(s): results changed between recent runs\n", result.flaky.len()), theme);
+    }
+}
+
+fn print_failures(result: &TestResult, theme: &ThemeConfig) {
+    let failed: Vec<_> = result.cases.iter().filter(|c| c.status == TestStatus::Failed).collect();
+    if failed.is_empty() {
+        AnsiTheme::print_themed("No failed tests.\n", theme);
+        return;
+    }
+
+    for case in failed {
+        AnsiTheme::print_themed("\n", theme);
+        AnsiTheme::print_error(&format!("---- {} ----\n", case.name), theme);
+        match &case.failure_output {
+            Some(output) => AnsiTheme::print_themed(&format!("{}\n", output), theme),
+            None => AnsiTheme::print_themed("(no captured output)\n", theme),
+        }
+    }
+}
@@ -0,0 +1,156 @@
+// opener.rs
+use std::path::Path;
+use std::process::Command;
+use crate::ansi_theme::AnsiTheme;
+use crate::config::AppConfig;
+
+/// Where a file will be opened, in order of preference:
+/// `editor_command` from the config, then `$VISUAL`, then `$EDITOR`, then the built-in editor.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Editor {
+    External(String),
+    BuiltIn,
+}
+
+impl Editor {
+    pub fn resolve(config: &AppConfig) -> Self {
+        let configured = config.editor_command.clone().filter(|c| !c.trim().is_empty());
+        let from_env = || {
+            ["VISUAL", "EDITOR"].iter()
+                .filter_map(|var| std::env::var(var).ok())
+                .find(|value| !value.trim().is_empty())
+        };
+
+        match configured.or_else(from_env) {
+            Some(command) => Editor::External(command),
+            None => Editor::BuiltIn,
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            Editor::External(command) => command.clone(),
+            Editor::BuiltIn => "built-in editor".to_string(),
+        }
+    }
+}
+
+/// Opens `path` in the user's editor, optionally positioned at a 1-based (line, column)
+/// `location`, and waits for it to exit.
+pub fn open_file(path: &Path, location: Option<(usize, usize)>, config: &AppConfig) -> Result<(), String> {
+    if !path.exists() {
+        return Err(format!("File does not exist: {}", path.display()));
+    }
+
+    match Editor::resolve(config) {
+        Editor::External(template) => {
+            let argv = build_command(&template, path, location)?;
+            let status = crate::process::run_interactive(Command::new(&argv[0]).args(&argv[1..]))
+                .map_err(|e| format!("Failed to start editor '{}': {}", argv[0], e))?;
+
+            if status.success() {
+                Ok(())
+            } else {
+                Err(format!("Editor '{}' exited with an error", argv[0]))
+            }
+        }
+        Editor::BuiltIn => {
+            AnsiTheme::print_info(" No editor configured ($VISUAL/$EDITOR unset), using the built-in editor\n", &config.theme);
+            let (line, column) = location.unwrap_or((1, 1));
+            let cursor_position = (column.saturating_sub(1), line.saturating_sub(1));
+            crate::menu::open_file_in_editor_with_cursor(path.to_path_buf(), cursor_position, config.theme.clone())
+        }
+    }
+}
+
+/// Expands an editor command template into program and arguments.
+/// `{file}`, `{line}` and `{column}` are substituted per argument so paths containing spaces stay intact;
+/// when the template has no `{file}` the path is appended as the last argument.
+pub fn build_command(template: &str, path: &Path, location: Option<(usize, usize)>) -> Result<Vec<String>, String> {
+    let words = split_command_line(template);
+    if words.is_empty() {
+        return Err("Editor command is empty".to_string());
+    }
+
+    let file = path.display().to_string();
+    let (line, column) = location.unwrap_or((1, 1));
+    let mut argv: Vec<String> = words.iter()
+        .map(|word| word.replace("{file}", &file)
+            .replace("{line}", &line.to_string())
+            .replace("{column}", &column.to_string()))
+        .collect();
+
+    if !template.contains("{file}") {
+        argv.push(file);
+    }
+
+    Ok(argv)
+}
+
+/// Splits a command line on whitespace, keeping single- or double-quoted sections together
+pub fn split_command_line(command: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut in_word = false;
+    let mut quote: Option<char> = None;
+
+    for c in command.chars() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => current.push(c),
+            None if c == '"' || c == '\'' => {
+                quote = Some(c);
+                in_word = true;
+            }
+            None if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut current));
+                    in_word = false;
+                }
+            }
+            None => {
+                current.push(c);
+                in_word = true;
+            }
+        }
+    }
+
+    if in_word {
+        words.push(current);
+    }
+
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn substitutes_file_and_line_placeholders() {
+        let argv = build_command("code --goto {file}:{line}:{column}", &PathBuf::from("src/main.rs"), Some((42, 7))).unwrap();
+        assert_eq!(argv, vec!["code", "--goto", "src/main.rs:42:7"]);
+    }
+
+    #[test]
+    fn appends_file_when_template_has_no_placeholder() {
+        let argv = build_command("vim", &PathBuf::from("my file.rs"), None).unwrap();
+        assert_eq!(argv, vec!["vim", "my file.rs"]);
+    }
+
+    #[test]
+    fn keeps_quoted_program_paths_together() {
+        let argv = build_command("\"C:\\Program Files\\Notepad++\\notepad++.exe\" -n{line} {file}", &PathBuf::from("a.rs"), Some((7, 1))).unwrap();
+        assert_eq!(argv, vec!["C:\\Program Files\\Notepad++\\notepad++.exe", "-n7", "a.rs"]);
+    }
+
+    #[test]
+    fn configured_command_wins_over_environment() {
+        let config = AppConfig {
+            editor_command: Some("nano +{line}".to_string()),
+            ..AppConfig::default()
+        };
+        assert_eq!(Editor::resolve(&config), Editor::External("nano +{line}".to_string()));
+    }
+}
@@ -0,0 +1,109 @@
+// output.rs
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use serde::{Serialize, Serializer};
+use crate::ansi_theme::AnsiTheme;
+
+// Bumped whenever a field is renamed or removed from a JSON document
+pub const JSON_SCHEMA_VERSION: u32 = 1;
+
+static JSON_MODE: AtomicBool = AtomicBool::new(false);
+static JSON_LINES: AtomicBool = AtomicBool::new(false);
+
+/// Switches the process into machine-readable mode: themed text is silenced
+/// and each operation emits a single JSON document on stdout instead.
+pub fn enable_json_mode() {
+    JSON_MODE.store(true, Ordering::Relaxed);
+    AnsiTheme::set_output_enabled(false);
+}
+
+pub fn is_json() -> bool {
+    JSON_MODE.load(Ordering::Relaxed)
+}
+
+/// For commands that report more than once (e.g. `watch`): each document is
+/// printed compactly on its own line, so the stream can be read as JSON lines.
+pub fn enable_json_lines() {
+    JSON_LINES.store(true, Ordering::Relaxed);
+}
+
+/// Envelope shared by every JSON document so consumers can rely on the same top-level shape
+#[derive(Serialize)]
+pub struct JsonDocument<'a, T: Serialize> {
+    pub schema_version: u32,
+    pub command: &'a str,
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<T>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+pub fn emit_json<T: Serialize>(command: &str, success: bool, data: Option<T>, error: Option<String>) {
+    match render_json(command, success, data, error, JSON_LINES.load(Ordering::Relaxed)) {
+        Ok(json) => println!("{}", json),
+        Err(e) => eprintln!("Failed to serialize JSON output: {}", e),
+    }
+}
+
+fn render_json<T: Serialize>(command: &str, success: bool, data: Option<T>, error: Option<String>, compact: bool) -> serde_json::Result<String> {
+    let document = JsonDocument {
+        schema_version: JSON_SCHEMA_VERSION,
+        command,
+        success,
+        data,
+        error,
+    };
+
+    if compact {
+        serde_json::to_string(&document)
+    } else {
+        serde_json::to_string_pretty(&document)
+    }
+}
+
+pub fn emit_success<T: Serialize>(command: &str, success: bool, data: T) {
+    emit_json(command, success, Some(data), None);
+}
+
+pub fn emit_error(command: &str, error: &str) {
+    emit_json::<()>(command, false, None, Some(error.to_string()));
+}
+
+/// Serializes a `Duration` as whole milliseconds for the `duration_ms` fields
+pub fn duration_ms<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u64(duration.as_millis() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    #[test]
+    fn success_documents_carry_the_envelope_and_data() {
+        let json = render_json("git.log", true, Some(vec!["abc"]), None, false).unwrap();
+        let document: Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(document["schema_version"], JSON_SCHEMA_VERSION);
+        assert_eq!(document["command"], "git.log");
+        assert_eq!(document["success"], true);
+        assert_eq!(document["data"][0], "abc");
+        assert!(document.get("error").is_none());
+    }
+
+    #[test]
+    fn error_documents_omit_data() {
+        let json = render_json::<()>("build", false, None, Some("Project 'x' does not exist!".to_string()), false).unwrap();
+        let document: Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(document["success"], false);
+        assert_eq!(document["error"], "Project 'x' does not exist!");
+        assert!(document.get("data").is_none());
+    }
+
+    #[test]
+    fn compact_documents_fit_on_one_line() {
+        let json = render_json("watch", true, Some(vec![1, 2]), None, true).unwrap();
+        assert!(!json.contains('\n'));
+        assert_eq!(serde_json::from_str::<Value>(&json).unwrap()["data"][1], 2);
+    }
+}
@@ -0,0 +1,183 @@
+// packager.rs
+use serde::Serialize;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use crate::archive::{self, ArchiveFormat, ArchiveWriter};
+use crate::build_system::BuildInfo;
+use crate::config::AppConfig;
+use crate::project_settings::ProjectSettings;
+
+// Archives are written here, inside the project's target directory
+const DIST_DIR: &str = "dist";
+// Top-level files picked up automatically when their name starts with one of these
+const DOC_PREFIXES: [&str; 3] = ["README", "LICENSE", "COPYING"];
+
+#[derive(Debug, Serialize)]
+pub struct PackageResult {
+    pub archive_path: PathBuf,
+    pub checksum_path: PathBuf,
+    pub sha256: String,
+    pub format: ArchiveFormat,
+    pub version: String,
+    /// Paths inside the archive
+    pub files: Vec<String>,
+    pub size_bytes: u64,
+}
+
+/// Bundles release binaries into versioned archives for distribution
+pub struct ReleasePackager {
+    config: AppConfig,
+}
+
+impl ReleasePackager {
+    pub fn new_with_config(config: AppConfig) -> Self {
+        ReleasePackager { config }
+    }
+
+    /// Packages the release binaries from `build_info` with the project's README/LICENSE files and
+    /// configured extra files into `target/dist/<name>-v<version>-<arch>-<os>.<ext>`, next to a
+    /// `.sha256` checksum file. `format` overrides the project's configured format.
+    pub fn package(&self, project_name: &str, build_info: &BuildInfo, format: Option<ArchiveFormat>) -> Result<PackageResult, String> {
+        let project_path = self.config.get_project_path(project_name);
+        if build_info.release_binaries.is_empty() {
+            return Err("No release binaries found - build the project in release mode first".to_string());
+        }
+
+        let (name, version) = package_name_and_version(&project_path)?;
+        let settings = ProjectSettings::load(&project_path)?.package;
+        let format = format.or(settings.format).unwrap_or_else(ArchiveFormat::platform_default);
+
+        let stem = format!("{}-v{}-{}-{}", name, version, std::env::consts::ARCH, std::env::consts::OS);
+        let dist_dir = project_path.join("target").join(DIST_DIR);
+        fs::create_dir_all(&dist_dir)
+            .map_err(|e| format!("Failed to create {}: {}", dist_dir.display(), e))?;
+        let archive_path = dist_dir.join(format!("{}.{}", stem, format.extension()));
+
+        let mut archive = ArchiveWriter::create(&archive_path, format)?;
+        for binary in &build_info.release_binaries {
+            archive.add_file(binary, &format!("{}/{}", stem, file_name(binary)))?;
+        }
+        for doc in documentation_files(&project_path)? {
+            archive.add_file(&doc, &format!("{}/{}", stem, file_name(&doc)))?;
+        }
+        for extra in &settings.extra_files {
+            let source = project_path.join(extra);
+            if !source.exists() {
+                return Err(format!("Extra file not found: {}", source.display()));
+            }
+            let name = format!("{}/{}", stem, archive_name(Path::new(extra)));
+            if source.is_dir() {
+                archive.add_directory(&source, &name, &|_| false)?;
+            } else {
+                archive.add_file(&source, &name)?;
+            }
+        }
+        let files = archive.entries().to_vec();
+        archive.finish()?;
+
+        let (checksum_path, sha256) = archive::write_checksum(&archive_path)?;
+        let size_bytes = fs::metadata(&archive_path).map(|m| m.len()).unwrap_or(0);
+
+        Ok(PackageResult {
+            archive_path,
+            checksum_path,
+            sha256,
+            format,
+            version,
+            files,
+            size_bytes,
+        })
+    }
+}
+
+fn package_name_and_version(project_path: &Path) -> Result<(String, String), String> {
+    let cargo_toml = project_path.join("Cargo.toml");
+    let content = fs::read_to_string(&cargo_toml)
+        .map_err(|e| format!("Failed to read {}: {}", cargo_toml.display(), e))?;
+    let manifest: toml::Value = toml::from_str(&content)
+        .map_err(|e| format!("Invalid {}: {}", cargo_toml.display(), e))?;
+    let package = manifest.get("package").ok_or("Cargo.toml has no [package] section")?;
+
+    let name = package.get("name").and_then(|v| v.as_str()).ok_or("Cargo.toml has no package name")?;
+    // Inherited versions (`version.workspace = true`) are not strings
+    let version = package.get("version").and_then(|v| v.as_str())
+        .ok_or("Could not read the package version from Cargo.toml")?;
+    Ok((name.to_string(), version.to_string()))
+}
+
+// README*, LICENSE* and COPYING* files in the project root, sorted
+fn documentation_files(project_path: &Path) -> Result<Vec<PathBuf>, String> {
+    let mut docs: Vec<PathBuf> = fs::read_dir(project_path)
+        .map_err(|e| format!("Failed to read directory {}: {}", project_path.display(), e))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.is_file())
+        .filter(|path| {
+            let name = file_name(path).to_uppercase();
+            DOC_PREFIXES.iter().any(|prefix| name.starts_with(prefix))
+        })
+        .collect();
+    docs.sort();
+    Ok(docs)
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default()
+}
+
+// Relative path inside the archive with forward slashes; paths leaving the project keep only their name
+fn archive_name(relative: &Path) -> String {
+    let parts: Vec<String> = relative.components()
+        .filter_map(|component| match component {
+            Component::Normal(part) => Some(part.to_string_lossy().to_string()),
+            _ => None,
+        })
+        .collect();
+    let escapes = relative.components().any(|c| matches!(c, Component::ParentDir | Component::RootDir | Component::Prefix(_)));
+    if escapes { file_name(relative) } else { parts.join("/") }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    #[test]
+    fn packages_binaries_docs_and_extra_files() {
+        let workspace = TempDir::new("package");
+        let project = workspace.join("demo");
+        fs::create_dir_all(project.join("target/release")).unwrap();
+        fs::create_dir_all(project.join("assets/icons")).unwrap();
+        fs::write(project.join("Cargo.toml"), "[package]\nname = \"demo\"\nversion = \"1.4.0\"\n").unwrap();
+        fs::write(project.join("README.md"), "readme").unwrap();
+        fs::write(project.join("LICENSE-MIT"), "mit").unwrap();
+        fs::write(project.join("assets/icons/app.png"), "png").unwrap();
+        fs::write(project.join("target/release/demo"), "binary").unwrap();
+
+        let mut settings = ProjectSettings::default();
+        settings.package.extra_files = vec!["assets".to_string()];
+        settings.save(&project).unwrap();
+
+        let config = AppConfig { workspace_path: workspace.display().to_string(), ..AppConfig::default() };
+        let build_info = BuildInfo {
+            debug_exists: false,
+            release_exists: true,
+            debug_path: project.join("target/debug/demo"),
+            release_path: project.join("target/release/demo"),
+            release_binaries: vec![project.join("target/release/demo")],
+        };
+        let result = ReleasePackager::new_with_config(config)
+            .package("demo", &build_info, Some(ArchiveFormat::Zip))
+            .unwrap();
+
+        let stem = format!("demo-v1.4.0-{}-{}", std::env::consts::ARCH, std::env::consts::OS);
+        assert_eq!(result.archive_path, project.join("target/dist").join(format!("{}.zip", stem)));
+        assert_eq!(result.files, vec![
+            format!("{}/demo", stem),
+            format!("{}/LICENSE-MIT", stem),
+            format!("{}/README.md", stem),
+            format!("{}/assets/icons/app.png", stem),
+        ]);
+        let checksum = fs::read_to_string(&result.checksum_path).unwrap();
+        assert_eq!(checksum, format!("{}  {}.zip\n", result.sha256, stem));
+    }
+}
@@ -0,0 +1,259 @@
+// process.rs
+use serde::Serialize;
+use std::io::{self, Read};
+use std::process::{Child, Command, ExitStatus, Output, Stdio};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+// Supervised children currently running; Ctrl-C cancels them instead of quitting when non-zero
+static ACTIVE_CHILDREN: AtomicUsize = AtomicUsize::new(0);
+static CANCEL_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+/// How a supervised child process ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Termination {
+    Exited,
+    TimedOut,
+    Cancelled,
+}
+
+impl Termination {
+    /// "timed out" or "cancelled", or None when the process exited on its own
+    pub fn interruption(&self) -> Option<&'static str> {
+        match self {
+            Termination::Exited => None,
+            Termination::TimedOut => Some("timed out"),
+            Termination::Cancelled => Some("cancelled"),
+        }
+    }
+}
+
+/// Routes Ctrl-C to running child processes. While a supervised child runs, Ctrl-C kills its
+/// process tree and the operation reports `Cancelled`; otherwise the terminal exits as before.
+pub fn install_interrupt_handler() {
+    let result = ctrlc::set_handler(|| {
+        if ACTIVE_CHILDREN.load(Ordering::SeqCst) > 0 {
+            CANCEL_REQUESTED.store(true, Ordering::SeqCst);
+        } else {
+            crate::jobs::kill_all_jobs();
+            crate::config::reset_terminal_colors();
+            std::process::exit(130);
+        }
+    });
+    if let Err(e) = result {
+        eprintln!("Warning: Ctrl-C handling unavailable: {}", e);
+    }
+}
+
+/// Runs an interactive child (a REPL or editor) that handles Ctrl-C itself, without a time limit.
+/// The terminal ignores Ctrl-C until the child exits.
+pub fn run_interactive(command: &mut Command) -> io::Result<ExitStatus> {
+    ACTIVE_CHILDREN.fetch_add(1, Ordering::SeqCst);
+    let status = command.status();
+    ACTIVE_CHILDREN.fetch_sub(1, Ordering::SeqCst);
+    status
+}
+
+/// Watches one child for its deadline and for Ctrl-C
+pub struct Supervisor {
+    deadline: Option<Instant>,
+    termination: Termination,
+}
+
+impl Supervisor {
+    pub fn start(timeout: Option<Duration>) -> Self {
+        CANCEL_REQUESTED.store(false, Ordering::SeqCst);
+        ACTIVE_CHILDREN.fetch_add(1, Ordering::SeqCst);
+        Supervisor {
+            deadline: timeout.map(|t| Instant::now() + t),
+            termination: Termination::Exited,
+        }
+    }
+
+    /// Whether Ctrl-C was pressed since this supervisor started
+    pub fn cancel_requested(&self) -> bool {
+        CANCEL_REQUESTED.load(Ordering::SeqCst)
+    }
+
+    /// Kills the child's process tree once the deadline passes or Ctrl-C was pressed.
+    /// Call regularly while the child runs; returns how it is ending so far.
+    pub fn poll(&mut self, child: &mut Child) -> Termination {
+        if self.termination == Termination::Exited {
+            if CANCEL_REQUESTED.load(Ordering::SeqCst) {
+                self.termination = Termination::Cancelled;
+            } else if self.deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                self.termination = Termination::TimedOut;
+            } else {
+                return Termination::Exited;
+            }
+            kill_tree(child);
+        }
+        self.termination
+    }
+
+    /// Waits for the child to exit, killing it when it runs too long or is cancelled
+    pub fn wait(mut self, child: &mut Child) -> io::Result<(ExitStatus, Termination)> {
+        loop {
+            self.poll(child);
+            if let Some(status) = child.try_wait()? {
+                return Ok((status, self.termination));
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+}
+
+impl Drop for Supervisor {
+    fn drop(&mut self) {
+        ACTIVE_CHILDREN.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Supervised replacements for `Command::output` and `Command::status`. Unlike `output()` the
+/// child inherits stdin, so callers set `Stdio::null()` unless they give it input.
+pub trait SupervisedCommand {
+    /// Like `output()`, but also reports whether the child was stopped early
+    fn supervised_output(&mut self, timeout: Option<Duration>) -> io::Result<(Output, Termination)>;
+
+    /// Like `output()`, failing with a "timed out"/"cancelled" error when the child was stopped
+    fn output_within(&mut self, timeout: Option<Duration>) -> io::Result<Output> {
+        let (output, termination) = self.supervised_output(timeout)?;
+        interruption_error(termination)?;
+        Ok(output)
+    }
+
+    /// Like `status()`: output goes straight to the terminal
+    fn status_within(&mut self, timeout: Option<Duration>) -> io::Result<ExitStatus>;
+}
+
+impl SupervisedCommand for Command {
+    fn supervised_output(&mut self, timeout: Option<Duration>) -> io::Result<(Output, Termination)> {
+        let mut child = self.stdout(Stdio::piped()).stderr(Stdio::piped()).spawn()?;
+        let supervisor = Supervisor::start(timeout);
+
+        // Drain both pipes on threads so a chatty child cannot block on a full pipe
+        let stdout = spawn_collector(child.stdout.take());
+        let stderr = spawn_collector(child.stderr.take());
+        let (status, termination) = supervisor.wait(&mut child)?;
+
+        Ok((Output {
+            status,
+            stdout: stdout.join().unwrap_or_default(),
+            stderr: stderr.join().unwrap_or_default(),
+        }, termination))
+    }
+
+    fn status_within(&mut self, timeout: Option<Duration>) -> io::Result<ExitStatus> {
+        let mut child = self.spawn()?;
+        let (status, termination) = Supervisor::start(timeout).wait(&mut child)?;
+        interruption_error(termination)?;
+        Ok(status)
+    }
+}
+
+fn interruption_error(termination: Termination) -> io::Result<()> {
+    match termination {
+        Termination::Exited => Ok(()),
+        Termination::TimedOut => Err(io::Error::new(io::ErrorKind::TimedOut, "timed out")),
+        Termination::Cancelled => Err(io::Error::new(io::ErrorKind::Interrupted, "cancelled")),
+    }
+}
+
+fn spawn_collector<R: Read + Send + 'static>(pipe: Option<R>) -> thread::JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut buffer = Vec::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut buffer);
+        }
+        buffer
+    })
+}
+
+/// Kills the child and everything it started (e.g. `cargo run` and the program it runs)
+pub fn kill_tree(child: &mut Child) {
+    #[cfg(windows)]
+    {
+        let _ = Command::new("taskkill")
+            .args(["/T", "/F", "/PID", &child.id().to_string()])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status();
+        let _ = child.kill();
+    }
+    #[cfg(unix)]
+    {
+        // Snapshot the tree first: once the child dies its descendants are reparented
+        let descendants = descendant_pids(child.id());
+        let _ = child.kill();
+        if !descendants.is_empty() {
+            let _ = Command::new("kill")
+                .arg("-KILL")
+                .args(descendants.iter().map(|pid| pid.to_string()))
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .status();
+        }
+    }
+}
+
+// All processes below `root`, deepest first, from a `ps` snapshot
+#[cfg(unix)]
+fn descendant_pids(root: u32) -> Vec<u32> {
+    let listing = match Command::new("ps").args(["-A", "-o", "pid=,ppid="]).output() {
+        Ok(output) => String::from_utf8_lossy(&output.stdout).to_string(),
+        Err(_) => return Vec::new(),
+    };
+    let pairs: Vec<(u32, u32)> = listing.lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace().map(|f| f.parse::<u32>());
+            match (fields.next(), fields.next()) {
+                (Some(Ok(pid)), Some(Ok(ppid))) => Some((pid, ppid)),
+                _ => None,
+            }
+        })
+        .collect();
+
+    let mut found = Vec::new();
+    let mut frontier = vec![root];
+    while let Some(parent) = frontier.pop() {
+        for &(pid, ppid) in &pairs {
+            if ppid == parent && !found.contains(&pid) {
+                found.push(pid);
+                frontier.push(pid);
+            }
+        }
+    }
+    found.reverse();
+    found
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kills_the_whole_tree_when_the_timeout_expires() {
+        let started = Instant::now();
+        // The inner sleep is a grandchild that would otherwise keep the pipes open
+        let (output, termination) = Command::new("sh")
+            .args(["-c", "sleep 30 & echo started; wait"])
+            .supervised_output(Some(Duration::from_millis(300)))
+            .unwrap();
+
+        assert_eq!(termination, Termination::TimedOut);
+        assert!(!output.status.success());
+        assert_eq!(String::from_utf8_lossy(&output.stdout), "started\n");
+        assert!(started.elapsed() < Duration::from_secs(10));
+    }
+
+    #[test]
+    fn fast_commands_exit_normally() {
+        let output = Command::new("sh").args(["-c", "echo hi"]).output_within(Some(Duration::from_secs(10))).unwrap();
+        assert!(output.status.success());
+        assert_eq!(String::from_utf8_lossy(&output.stdout), "hi\n");
+    }
+}
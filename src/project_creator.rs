@@ -65,12 +65,12 @@ edition = "2021"
         AnsiTheme::print_success(" Created main.rs\n", theme);
 
         // Initialize git repository (optional)
-        if let Ok(status) = Command::new("git")
+        if let Ok(output) = Command::new("git")
             .current_dir(&project_path)
             .arg("init")
-            .status()
+            .output()
         {
-            if status.success() {
+            if output.status.success() {
                 AnsiTheme::print_success(" Initialized git repository\n", theme);
             }
         }
@@ -0,0 +1,390 @@
+// project_settings.rs
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use crate::archive::ArchiveFormat;
+
+// Per-project settings and run history live in this directory next to the project's Cargo.toml
+pub const STATE_DIR: &str = ".rust_dev_terminal";
+pub const SETTINGS_FILE: &str = "settings.toml";
+
+/// The project's state directory, created on first use. Its `.gitignore` keeps
+/// machine-local history out of version control while settings can still be committed.
+pub fn state_dir(project_path: &Path) -> Result<PathBuf, String> {
+    let dir = project_path.join(STATE_DIR);
+    if !dir.exists() {
+        fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+        fs::write(dir.join(".gitignore"), "# Local run history\n*.json\n")
+            .map_err(|e| format!("Failed to write {}: {}", dir.join(".gitignore").display(), e))?;
+    }
+    Ok(dir)
+}
+
+/// A named set of cargo build options, e.g. "wasm-release" or "cli-only"
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BuildConfiguration {
+    pub name: String,
+    /// Cargo profile: `dev`, `release` or a custom `[profile.*]`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub features: Vec<String>,
+    #[serde(default)]
+    pub all_features: bool,
+    #[serde(default)]
+    pub no_default_features: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bin: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub example: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extra_args: Vec<String>,
+}
+
+impl BuildConfiguration {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("Build configuration name cannot be empty".to_string());
+        }
+        if self.bin.is_some() && self.example.is_some() {
+            return Err("Choose either a --bin or an --example, not both".to_string());
+        }
+        if self.all_features && !self.features.is_empty() {
+            return Err("--all-features already enables every feature; clear the feature list".to_string());
+        }
+        Ok(())
+    }
+
+    /// Arguments appended to `cargo build`
+    pub fn cargo_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+
+        match self.profile.as_deref() {
+            None | Some("") | Some("dev") | Some("debug") => {}
+            Some("release") => args.push("--release".to_string()),
+            Some(profile) => {
+                args.push("--profile".to_string());
+                args.push(profile.to_string());
+            }
+        }
+        if !self.features.is_empty() {
+            args.push("--features".to_string());
+            args.push(self.features.join(","));
+        }
+        if self.all_features {
+            args.push("--all-features".to_string());
+        }
+        if self.no_default_features {
+            args.push("--no-default-features".to_string());
+        }
+        if let Some(target) = &self.target {
+            args.push("--target".to_string());
+            args.push(target.clone());
+        }
+        if let Some(bin) = &self.bin {
+            args.push("--bin".to_string());
+            args.push(bin.clone());
+        }
+        if let Some(example) = &self.example {
+            args.push("--example".to_string());
+            args.push(example.clone());
+        }
+        args.extend(self.extra_args.iter().cloned());
+
+        args
+    }
+
+    /// Short description for menus, e.g. `--release --features cli`
+    pub fn summary(&self) -> String {
+        let args = self.cargo_args();
+        if args.is_empty() { "(default debug build)".to_string() } else { args.join(" ") }
+    }
+}
+
+/// A named way to run the project: program arguments plus the environment it runs in.
+/// Runs `cargo run` unless `script` names a Python script. Paths are relative to the project.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RunConfiguration {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub script: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+    /// Loaded before `env`, so explicit variables win over the file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub env_file: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stdin_file: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub working_dir: Option<String>,
+}
+
+impl RunConfiguration {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("Run configuration name cannot be empty".to_string());
+        }
+        if let Some(key) = self.env.keys().find(|k| k.is_empty() || k.contains('=')) {
+            return Err(format!("Invalid environment variable name '{}'", key));
+        }
+        Ok(())
+    }
+
+    pub fn is_python(&self) -> bool {
+        self.script.is_some()
+    }
+
+    /// The environment from `env_file` followed by `env`
+    pub fn environment(&self, project_path: &Path) -> Result<Vec<(String, String)>, String> {
+        let mut variables = match &self.env_file {
+            Some(env_file) => {
+                let path = project_path.join(env_file);
+                let content = fs::read_to_string(&path)
+                    .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+                parse_env_file(&content)
+            }
+            None => Vec::new(),
+        };
+        variables.extend(self.env.iter().map(|(k, v)| (k.clone(), v.clone())));
+        Ok(variables)
+    }
+
+    /// Directory the program runs in: `working_dir` or the project itself
+    pub fn directory(&self, project_path: &Path) -> Result<PathBuf, String> {
+        match &self.working_dir {
+            Some(dir) => {
+                let path = project_path.join(dir);
+                if !path.is_dir() {
+                    return Err(format!("Working directory not found: {}", path.display()));
+                }
+                Ok(path)
+            }
+            None => Ok(project_path.to_path_buf()),
+        }
+    }
+
+    /// Sets the working directory, environment and stdin of `command`
+    pub fn apply(&self, command: &mut Command, project_path: &Path) -> Result<(), String> {
+        command.current_dir(self.directory(project_path)?);
+        command.envs(self.environment(project_path)?);
+        if let Some(stdin_file) = &self.stdin_file {
+            let path = project_path.join(stdin_file);
+            let file = File::open(&path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+            command.stdin(Stdio::from(file));
+        }
+        Ok(())
+    }
+
+    /// Short description for menus, e.g. `cargo run -- --port 80 (2 env, stdin: input.txt)`
+    pub fn summary(&self) -> String {
+        let mut summary = match &self.script {
+            Some(script) => format!("python {}", script),
+            None => "cargo run".to_string(),
+        };
+        if !self.args.is_empty() {
+            let separator = if self.is_python() { " " } else { " -- " };
+            summary.push_str(separator);
+            summary.push_str(&self.args.join(" "));
+        }
+
+        let mut details = Vec::new();
+        if let Some(env_file) = &self.env_file {
+            details.push(env_file.clone());
+        }
+        if !self.env.is_empty() {
+            details.push(format!("{} env", self.env.len()));
+        }
+        if let Some(stdin_file) = &self.stdin_file {
+            details.push(format!("stdin: {}", stdin_file));
+        }
+        if let Some(dir) = &self.working_dir {
+            details.push(format!("in {}", dir));
+        }
+        if !details.is_empty() {
+            summary.push_str(&format!(" ({})", details.join(", ")));
+        }
+        summary
+    }
+}
+
+/// Parses `KEY=VALUE` lines as written in `.env` files. Blank lines, `#` comments and an
+/// `export ` prefix are allowed, and values may be wrapped in single or double quotes.
+pub fn parse_env_file(content: &str) -> Vec<(String, String)> {
+    content.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let line = line.strip_prefix("export ").unwrap_or(line);
+            let (key, value) = line.split_once('=')?;
+            let value = value.trim();
+            let value = [('"', '"'), ('\'', '\'')].iter()
+                .find_map(|&(open, close)| value.strip_prefix(open)?.strip_suffix(close))
+                .unwrap_or(value);
+            Some((key.trim().to_string(), value.to_string()))
+        })
+        .filter(|(key, _)| !key.is_empty())
+        .collect()
+}
+
+/// What goes into release archives besides the binaries, README and LICENSE files
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PackageSettings {
+    /// `zip` or `tar.gz`; zip on Windows and tar.gz elsewhere when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<ArchiveFormat>,
+    /// Files or directories relative to the project, e.g. `config/default.toml` or `assets`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extra_files: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProjectSettings {
+    #[serde(default)]
+    pub build_configurations: Vec<BuildConfiguration>,
+    #[serde(default)]
+    pub run_configurations: Vec<RunConfiguration>,
+    #[serde(default)]
+    pub package: PackageSettings,
+}
+
+impl ProjectSettings {
+    pub fn path(project_path: &Path) -> PathBuf {
+        project_path.join(STATE_DIR).join(SETTINGS_FILE)
+    }
+
+    /// Loads the project's settings, or defaults when the file does not exist yet
+    pub fn load(project_path: &Path) -> Result<Self, String> {
+        let path = Self::path(project_path);
+        if !path.exists() {
+            return Ok(ProjectSettings::default());
+        }
+        let content = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        toml::from_str(&content).map_err(|e| format!("Invalid {}: {}", path.display(), e))
+    }
+
+    pub fn save(&self, project_path: &Path) -> Result<(), String> {
+        let path = state_dir(project_path)?.join(SETTINGS_FILE);
+        let content = toml::to_string_pretty(self).map_err(|e| e.to_string())?;
+        fs::write(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+
+    pub fn build_configuration(&self, name: &str) -> Option<&BuildConfiguration> {
+        self.build_configurations.iter().find(|c| c.name == name)
+    }
+
+    /// Adds the configuration, replacing any existing one with the same name
+    pub fn set_build_configuration(&mut self, configuration: BuildConfiguration) {
+        match self.build_configurations.iter_mut().find(|c| c.name == configuration.name) {
+            Some(existing) => *existing = configuration,
+            None => self.build_configurations.push(configuration),
+        }
+    }
+
+    pub fn remove_build_configuration(&mut self, name: &str) -> bool {
+        let before = self.build_configurations.len();
+        self.build_configurations.retain(|c| c.name != name);
+        self.build_configurations.len() != before
+    }
+
+    pub fn run_configuration(&self, name: &str) -> Option<&RunConfiguration> {
+        self.run_configurations.iter().find(|c| c.name == name)
+    }
+
+    /// Adds the configuration, replacing any existing one with the same name
+    pub fn set_run_configuration(&mut self, configuration: RunConfiguration) {
+        match self.run_configurations.iter_mut().find(|c| c.name == configuration.name) {
+            Some(existing) => *existing = configuration,
+            None => self.run_configurations.push(configuration),
+        }
+    }
+
+    pub fn remove_run_configuration(&mut self, name: &str) -> bool {
+        let before = self.run_configurations.len();
+        self.run_configurations.retain(|c| c.name != name);
+        self.run_configurations.len() != before
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    #[test]
+    fn builds_cargo_arguments_in_order() {
+        let configuration = BuildConfiguration {
+            name: "wasm".to_string(),
+            profile: Some("bench-lto".to_string()),
+            features: vec!["web".to_string(), "simd".to_string()],
+            no_default_features: true,
+            target: Some("wasm32-unknown-unknown".to_string()),
+            example: Some("demo".to_string()),
+            extra_args: vec!["--locked".to_string()],
+            ..BuildConfiguration::default()
+        };
+
+        assert_eq!(configuration.cargo_args().join(" "),
+            "--profile bench-lto --features web,simd --no-default-features --target wasm32-unknown-unknown --example demo --locked");
+        assert!(configuration.validate().is_ok());
+    }
+
+    #[test]
+    fn rejects_bin_and_example_together() {
+        let configuration = BuildConfiguration {
+            name: "both".to_string(),
+            bin: Some("a".to_string()),
+            example: Some("b".to_string()),
+            ..BuildConfiguration::default()
+        };
+        assert!(configuration.validate().is_err());
+    }
+
+    #[test]
+    fn settings_round_trip_through_toml() {
+        let mut settings = ProjectSettings::default();
+        settings.set_build_configuration(BuildConfiguration {
+            name: "release".to_string(),
+            profile: Some("release".to_string()),
+            ..BuildConfiguration::default()
+        });
+        settings.package.format = Some(ArchiveFormat::TarGz);
+
+        let text = toml::to_string_pretty(&settings).unwrap();
+        let loaded: ProjectSettings = toml::from_str(&text).unwrap();
+        assert_eq!(loaded.build_configuration("release").unwrap().cargo_args(), vec!["--release"]);
+        assert_eq!(loaded.package.format, Some(ArchiveFormat::TarGz));
+    }
+
+    #[test]
+    fn run_configuration_env_overrides_env_file() {
+        let project_path = TempDir::new("run_config");
+        fs::write(project_path.join(".env"), "# local\nexport PORT=8080\nNAME=\"dev server\"\n\nMODE='debug'\n").unwrap();
+
+        let mut configuration = RunConfiguration {
+            name: "serve".to_string(),
+            env_file: Some(".env".to_string()),
+            ..RunConfiguration::default()
+        };
+        configuration.env.insert("PORT".to_string(), "9000".to_string());
+
+        let environment = configuration.environment(&project_path).unwrap();
+        assert_eq!(environment, vec![
+            ("PORT".to_string(), "8080".to_string()),
+            ("NAME".to_string(), "dev server".to_string()),
+            ("MODE".to_string(), "debug".to_string()),
+            ("PORT".to_string(), "9000".to_string()),
+        ]);
+
+        let mut settings = ProjectSettings::default();
+        settings.set_run_configuration(configuration.clone());
+        let loaded: ProjectSettings = toml::from_str(&toml::to_string_pretty(&settings).unwrap()).unwrap();
+        assert_eq!(loaded.run_configuration("serve"), Some(&configuration));
+    }
+}
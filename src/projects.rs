@@ -12,11 +12,9 @@ pub fn list_projects_with_config(config: &AppConfig) {
 
     if let Ok(entries) = fs::read_dir(&workspace) {
         for entry in entries.flatten() {
-            if entry.path().is_dir() {
-                if entry.path().join("Cargo.toml").exists() {
-                    if let Some(name) = entry.file_name().to_str() {
-                        AnsiTheme::print_themed(&format!(" - {}\n", name), &config.theme);
-                    }
+            if entry.path().is_dir() && entry.path().join("Cargo.toml").exists() {
+                if let Some(name) = entry.file_name().to_str() {
+                    AnsiTheme::print_themed(&format!(" - {}\n", name), &config.theme);
                 }
             }
         }
@@ -26,23 +24,32 @@ pub fn list_projects_with_config(config: &AppConfig) {
     }
 }
 
-pub fn select_project_with_config(config: &AppConfig) -> Option<String> {
+pub fn find_projects(config: &AppConfig) -> Result<Vec<String>, String> {
     let workspace = config.get_workspace_path();
-    let mut projects = vec![];
+    let entries = fs::read_dir(&workspace)
+        .map_err(|_| format!("Workspace directory not found: {}", workspace.display()))?;
 
-    if let Ok(entries) = fs::read_dir(&workspace) {
-        for entry in entries.flatten() {
-            if entry.path().is_dir() && entry.path().join("Cargo.toml").exists() {
-                if let Some(name) = entry.file_name().to_str() {
-                    projects.push(name.to_string());
-                }
+    let mut projects = vec![];
+    for entry in entries.flatten() {
+        if entry.path().is_dir() && entry.path().join("Cargo.toml").exists() {
+            if let Some(name) = entry.file_name().to_str() {
+                projects.push(name.to_string());
             }
         }
-    } else {
-        AnsiTheme::print_warning(&format!(" Workspace directory not found: {}\n", workspace.display()), &config.theme);
-        AnsiTheme::print_themed("Update the workspace path in configuration settings.\n", &config.theme);
-        return None;
     }
+    projects.sort();
+    Ok(projects)
+}
+
+pub fn select_project_with_config(config: &AppConfig) -> Option<String> {
+    let projects = match find_projects(config) {
+        Ok(projects) => projects,
+        Err(e) => {
+            AnsiTheme::print_warning(&format!(" {}\n", e), &config.theme);
+            AnsiTheme::print_themed("Update the workspace path in configuration settings.\n", &config.theme);
+            return None;
+        }
+    };
 
     if projects.is_empty() {
         AnsiTheme::print_themed("No Rust projects found in workspace.\n", &config.theme);
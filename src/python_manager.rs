@@ -5,6 +5,9 @@ use std::fs;
 use std::io::{self, Write};
 use crate::ansi_theme::AnsiTheme;
 use crate::config::ThemeConfig;
+use crate::process::{self, SupervisedCommand, Termination};
+use crate::project_settings::RunConfiguration;
+use serde::Serialize;
 
 pub struct PythonManager {
     config: crate::config::AppConfig,
@@ -40,6 +43,21 @@ impl PythonManager {
     }
 
     pub fn run_python_script(&self, project_name: &str, script_path: &str, args: &[String], theme: &ThemeConfig) -> Result<PythonRunResult, String> {
+        self.run_script(project_name, script_path, args, None, theme)
+    }
+
+    /// Runs the configuration's script with its arguments, environment, stdin and working
+    /// directory. `extra_args` are passed after the configured ones.
+    pub fn run_with_configuration(&self, project_name: &str, configuration: &RunConfiguration, extra_args: &[String], theme: &ThemeConfig) -> Result<PythonRunResult, String> {
+        configuration.validate()?;
+        let script = configuration.script.as_deref()
+            .ok_or_else(|| format!("Run configuration '{}' has no Python script", configuration.name))?;
+        let mut args = configuration.args.clone();
+        args.extend(extra_args.iter().cloned());
+        self.run_script(project_name, script, &args, Some(configuration), theme)
+    }
+
+    fn run_script(&self, project_name: &str, script_path: &str, args: &[String], configuration: Option<&RunConfiguration>, theme: &ThemeConfig) -> Result<PythonRunResult, String> {
         let project_path = self.config.get_project_path(project_name);
         let full_script_path = project_path.join(script_path);
         
@@ -49,33 +67,42 @@ impl PythonManager {
 
         AnsiTheme::print_themed(&format!("Running Python script: {}\n", script_path), theme);
         
+        // Scripts get an empty stdin unless the configuration gives them a file
         let mut command = Command::new("python");
-        command.current_dir(&project_path).arg(&full_script_path);
+        command.current_dir(&project_path).arg(&full_script_path).stdin(Stdio::null());
         
         for arg in args {
             command.arg(arg);
         }
 
+        if let Some(configuration) = configuration {
+            configuration.apply(&mut command, &project_path)?;
+        }
+
         command.stdout(Stdio::piped()).stderr(Stdio::piped());
 
-        let output = command
-            .output()
+        let (output, termination) = command
+            .supervised_output(self.config.timeouts.get("python"))
             .map_err(|e| format!("Failed to run Python script: {}", e))?;
 
         let stdout = String::from_utf8_lossy(&output.stdout).to_string();
         let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+        let success = output.status.success() && termination == Termination::Exited;
 
-        if output.status.success() {
+        if let Some(reason) = termination.interruption() {
+            AnsiTheme::print_error(&format!(" Python script {}\n", reason), theme);
+        } else if success {
             AnsiTheme::print_success(" Python script executed successfully\n", theme);
         } else {
             AnsiTheme::print_error(" Python script execution failed\n", theme);
         }
 
         Ok(PythonRunResult {
-            success: output.status.success(),
+            success,
             stdout,
             stderr,
             exit_code: output.status.code().unwrap_or(-1),
+            termination,
         })
     }
 
@@ -100,9 +127,10 @@ impl PythonManager {
             .arg("-m")
             .arg("venv")
             .arg("venv")
+            .stdin(Stdio::null())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
-            .output()
+            .output_within(self.config.timeouts.get("python"))
             .map_err(|e| format!("Failed to create virtual environment: {}", e))?;
 
         if output.status.success() {
@@ -132,9 +160,10 @@ impl PythonManager {
             .arg("pip")
             .arg("install")
             .arg(package)
+            .stdin(Stdio::null())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
-            .output()
+            .output_within(self.config.timeouts.get("python"))
             .map_err(|e| format!("Failed to install package: {}", e))?;
 
         if output.status.success() {
@@ -170,9 +199,10 @@ impl PythonManager {
             .arg("install")
             .arg("-r")
             .arg("requirements.txt")
+            .stdin(Stdio::null())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
-            .output()
+            .output_within(self.config.timeouts.get("python"))
             .map_err(|e| format!("Failed to install requirements: {}", e))?;
 
         if output.status.success() {
@@ -197,9 +227,7 @@ impl PythonManager {
         AnsiTheme::print_themed("Starting Python REPL...\n", theme);
         AnsiTheme::print_themed("Type 'exit()' or press Ctrl+Z then Enter to exit\n", theme);
         
-        let status = Command::new(&python_cmd)
-            .current_dir(&project_path)
-            .status()
+        let status = process::run_interactive(Command::new(&python_cmd).current_dir(&project_path))
             .map_err(|e| format!("Failed to start Python REPL: {}", e))?;
 
         if status.success() {
@@ -223,7 +251,7 @@ impl PythonManager {
 
                 if path.is_dir() {
                     Self::walk_dir(&path, files)?;
-                } else if path.extension().map_or(false, |ext| ext == "py") {
+                } else if path.extension().is_some_and(|ext| ext == "py") {
                     files.push(path);
                 }
             }
@@ -294,10 +322,45 @@ impl PythonManager {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct PythonRunResult {
     pub success: bool,
     pub stdout: String,
     pub stderr: String,
     pub exit_code: i32,
-}
\ No newline at end of file
+    pub termination: Termination,
+}
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::config::{AppConfig, TimeoutConfig};
+    use crate::test_support::TempDir;
+
+    #[test]
+    fn scripts_read_an_empty_stdin_unless_configured() {
+        let workspace = TempDir::new("python_stdin");
+        fs::create_dir_all(workspace.join("app")).unwrap();
+        fs::write(workspace.join("app").join("ask.py"),
+            "try:\n    print('read', input())\nexcept EOFError:\n    print('eof')\n").unwrap();
+        fs::write(workspace.join("app").join("input.txt"), "answer\n").unwrap();
+        // A script waiting on the terminal would hit the timeout instead
+        let manager = PythonManager::new_with_config(AppConfig {
+            workspace_path: workspace.display().to_string(),
+            timeouts: TimeoutConfig { python_secs: Some(10), ..TimeoutConfig::default() },
+            ..AppConfig::default()
+        });
+        let theme = ThemeConfig::default();
+
+        let result = manager.run_script("app", "ask.py", &[], None, &theme).unwrap();
+        assert_eq!(result.termination, Termination::Exited);
+        assert_eq!(result.stdout.trim(), "eof");
+
+        let configuration = RunConfiguration {
+            name: "with input".to_string(),
+            stdin_file: Some("input.txt".to_string()),
+            ..RunConfiguration::default()
+        };
+        let result = manager.run_script("app", "ask.py", &[], Some(&configuration), &theme).unwrap();
+        assert_eq!(result.stdout.trim(), "read answer");
+    }
+}
@@ -1,79 +1,196 @@
 // self_update.rs (CONVERTED)
+use serde::Serialize;
 use std::env;
 use std::fs::{self, rename, File};
-use std::io::Write;
 use std::path::{Path, PathBuf};
-use std::process::{Command, exit};
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::process::{Command, Stdio};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use crate::ansi_theme::AnsiTheme;
-use crate::config::ThemeConfig;
+use crate::backup::{self, BackupInfo};
+use crate::build_system::BuildSystem;
+use crate::config::{AppConfig, ThemeConfig};
+use crate::process::{SupervisedCommand, Termination};
+use crate::size_report::format_bytes;
+use crate::version::{Bump, Version};
 
-pub struct SelfUpdater;
+// How long a freshly built binary gets to answer `--version`
+const SELF_CHECK_TIMEOUT: Duration = Duration::from_secs(30);
+// How long a binary that may predate `--version` has to start without failing
+const START_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Where `SelfUpdater::restore_backup` writes a backup's source tree
+pub enum RestoreTarget {
+    /// A new or empty directory
+    Directory(PathBuf),
+    /// Over the project the terminal was built from, after backing up its current state
+    CurrentProject,
+}
+
+/// What a self-update builds from
+#[derive(Debug, Clone, PartialEq)]
+pub enum UpdateSource {
+    /// The project in the current directory, with its patch version bumped
+    WorkingTree,
+    /// A tag, branch or commit of that project, checked out into a temporary worktree
+    GitRef(String),
+    /// Another checkout of the terminal's source
+    Directory(PathBuf),
+}
+
+impl UpdateSource {
+    pub fn describe(&self) -> String {
+        match self {
+            UpdateSource::WorkingTree => "working tree".to_string(),
+            UpdateSource::GitRef(reference) => format!("git {}", reference),
+            UpdateSource::Directory(directory) => format!("directory {}", directory.display()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct UpdateOptions {
+    pub source: UpdateSource,
+    /// Report what would happen and build, but change nothing
+    pub dry_run: bool,
+    /// Re-run the new binary with the same arguments afterwards (the menu does, the CLI just exits)
+    pub restart: bool,
+}
+
+impl Default for UpdateOptions {
+    fn default() -> Self {
+        UpdateOptions { source: UpdateSource::WorkingTree, dry_run: false, restart: true }
+    }
+}
+
+/// What a self-update did; the executable is only installed when it was not a dry run
+#[derive(Debug, Clone, Serialize)]
+pub struct UpdateOutcome {
+    pub source: String,
+    pub previous_version: String,
+    pub new_version: String,
+    pub dry_run: bool,
+    pub installed_path: Option<PathBuf>,
+}
+
+pub struct SelfUpdater {
+    config: AppConfig,
+}
 
 impl SelfUpdater {
-    pub fn new() -> Self {
-        SelfUpdater
+    pub fn new_with_config(config: AppConfig) -> Self {
+        SelfUpdater { config }
     }
 
     // Add the missing method
     pub fn check_for_updates(&self, theme: &ThemeConfig) {
+        self.run_update(&UpdateOptions::default(), theme);
+    }
+
+    /// Runs `perform_self_update` and reports any error
+    pub fn run_update(&self, options: &UpdateOptions, theme: &ThemeConfig) {
         AnsiTheme::print_themed("Checking for updates...\n", theme);
-        // For now, just redirect to perform_self_update
-        // In a real implementation, you might check a remote repository first
-        match self.perform_self_update(theme) {
-            Ok(()) => AnsiTheme::print_themed("Update process started...\n", theme),
+        match self.perform_self_update(options, theme) {
+            Ok(_) if options.dry_run => AnsiTheme::print_themed("Dry run finished\n", theme),
+            Ok(_) => AnsiTheme::print_themed("Update process started...\n", theme),
             Err(e) => AnsiTheme::print_error(&format!("Error during self-update: {}\n", e), theme),
         }
     }
 
-    pub fn perform_self_update(&self, theme: &ThemeConfig) -> Result<(), String> {
-        AnsiTheme::print_themed("Starting self-update process...\n", theme);
-        
-        let build_system = crate::build_system::BuildSystem::new(Some("self_update.log".to_string()), true);
+    /// Builds the terminal from `options.source`, backs up the project and restarts with the new
+    /// binary. A dry run reports the version change and backup location and builds into a scratch
+    /// target directory, leaving Cargo.toml, the backups and the running executable untouched.
+    pub fn perform_self_update(&self, options: &UpdateOptions, theme: &ThemeConfig) -> Result<UpdateOutcome, String> {
+        if options.dry_run {
+            AnsiTheme::print_themed("Starting self-update dry run...\n", theme);
+        } else {
+            AnsiTheme::print_themed("Starting self-update process...\n", theme);
+        }
+        AnsiTheme::print_themed(&format!("Update source: {}\n", options.source.describe()), theme);
 
-        let current_exe: PathBuf = env::current_exe()
-            .map_err(|e| format!("Failed to get current executable path: {}", e))?;
+        let build_system = self_build_system();
+
+        let current_exe = launch_path()?;
 
         AnsiTheme::print_themed(&format!("Current executable: {}\n", current_exe.display()), theme);
 
-        // Step 0: Read current version and increment it
-        let (new_version, mut cargo_restore) = self.increment_version(theme)
-            .map_err(|e| format!("Failed to increment version: {}", e))?;
-        
+        // Step 0: Resolve the source tree and the version it builds. Only a real update from the
+        // working tree bumps the version; the guards undo the bump and remove the worktree on error.
+        let project_root = self.project_root();
+        let mut cargo_restore = None;
+        let mut worktree = None;
+        let (source_dir, new_version) = match &options.source {
+            UpdateSource::WorkingTree => {
+                let root = project_root.clone().ok_or("Could not find project root directory")?;
+                if options.dry_run {
+                    let (current_version, next_version) = self.preview_version_bump()?;
+                    AnsiTheme::print_themed(&format!("Version would change: {} → {} (Cargo.toml left unchanged)\n", current_version, next_version), theme);
+                    (root, next_version)
+                } else {
+                    let (new_version, restore) = self.increment_version(theme)
+                        .map_err(|e| format!("Failed to increment version: {}", e))?;
+                    cargo_restore = Some(restore);
+                    (root, new_version)
+                }
+            }
+            UpdateSource::GitRef(reference) => {
+                let root = project_root.clone().ok_or("Could not find the git repository to check out from")?;
+                let checkout = self.checkout_worktree(&root, reference, theme)?;
+                let directory = checkout.path.clone();
+                worktree = Some(checkout);
+                let version = package_version(&directory.join("Cargo.toml"))?;
+                (directory, version)
+            }
+            UpdateSource::Directory(directory) => {
+                if !directory.join("Cargo.toml").exists() {
+                    return Err(format!("No Cargo.toml found in {}", directory.display()));
+                }
+                let version = package_version(&directory.join("Cargo.toml"))?;
+                (directory.clone(), version)
+            }
+        };
+
+        if !matches!(options.source, UpdateSource::WorkingTree) {
+            AnsiTheme::print_themed(&format!("Version change: {} → {}\n", env!("CARGO_PKG_VERSION"), new_version), theme);
+        }
         AnsiTheme::print_themed(&format!("New version will be: {}\n", new_version), theme);
 
         // Step 1: Create backup of entire project
-        self.create_backup(&current_exe, &new_version, theme)
-            .map_err(|e| format!("Failed to create backup: {}", e))?;
-
-        // Step 2: On Windows, rename the current exe to free up the path for the new build
-        if cfg!(target_os = "windows") {
-            let stem = current_exe.file_stem().unwrap().to_str().unwrap();
-            let ext = current_exe.extension().map_or("", |e| e.to_str().unwrap());
-            let old_exe = current_exe.with_file_name(format!("{}-old.{}", stem, ext));
-            
-            AnsiTheme::print_themed(&format!("Renaming current executable to: {}\n", old_exe.display()), theme);
-            
-            // Try to remove old file if it exists
-            let _ = fs::remove_file(&old_exe);
-            
-            rename(&current_exe, &old_exe)
-                .map_err(|e| format!("Failed to rename current executable: {}", e))?;
-            
-            AnsiTheme::print_success("Renamed current executable for update\n", theme);
+        if project_root.is_none() {
+            AnsiTheme::print_warning("Project root not found from this directory, skipping the backup\n", theme);
+        } else if options.dry_run {
+            let backup_path = backup::backup_path(
+                &self.get_backups_dir()?,
+                &executable_name(&current_exe).ok_or("Invalid executable name")?,
+                &new_version,
+                unix_now()?,
+                &self.config.backup,
+            );
+            AnsiTheme::print_themed(&format!("Backup would be written to: {}\n", backup_path.display()), theme);
+        } else {
+            self.create_backup(&current_exe, &new_version, theme)
+                .map_err(|e| format!("Failed to create backup: {}", e))?;
         }
-        
-        // Step 3: Build ourselves (now the path is free on Windows)
-        AnsiTheme::print_themed("Building new version...\n", theme);
-        match build_system.build_self(theme) {
+
+        // Step 2: Build ourselves. Nothing is built where the running executable lives: dry runs use
+        // a scratch target directory and project updates build into target/self-update, so the
+        // install below is the only step that touches it.
+        let target_dir = if options.dry_run {
+            Some(env::temp_dir().join("rust_dev_terminal_dry_run_target"))
+        } else if matches!(options.source, UpdateSource::Directory(_)) {
+            None
+        } else {
+            project_root.as_ref().map(|root| root.join("target").join("self-update"))
+        };
+        AnsiTheme::print_themed(&format!("Building {}...\n", source_dir.display()), theme);
+        match build_system.build_self_in(&source_dir, target_dir.as_deref(), theme) {
             Ok(result) => {
                 if !result.success {
                     return Err(format!("Self-build failed: {}", result.stderr));
                 }
                 AnsiTheme::print_success("Self-build successful\n", theme);
                 
-                // Show build output if there were warnings/errors
-                if !result.stderr.is_empty() {
+                // Show build output if there were warnings/errors, and always on a dry run
+                if !result.stderr.is_empty() || options.dry_run {
                     AnsiTheme::print_themed("Build output:\n", theme);
                     AnsiTheme::print_themed(&result.stderr, theme);
                 }
@@ -81,32 +198,175 @@ impl SelfUpdater {
             Err(e) => return Err(format!("Self-build error: {}", e)),
         }
 
-        // Step 4: Get the new binary path
-        let new_binary_path = build_system.get_self_binary_path(theme)
-            .map_err(|e| format!("Failed to get new binary path: {}", e))?;
+        // Step 3: Get the new binary path and check that it starts
+        let name = package_name(&source_dir.join("Cargo.toml"))?;
+        let new_binary_path = target_dir.unwrap_or_else(|| source_dir.join("target"))
+            .join("release")
+            .join(format!("{}{}", name, env::consts::EXE_SUFFIX));
         
         if !new_binary_path.exists() {
             return Err(format!("New binary not found at: {}", new_binary_path.display()));
         }
 
         AnsiTheme::print_success(&format!("New binary ready: {}\n", new_binary_path.display()), theme);
+        // A dry run of the working tree builds the version Cargo.toml still has
+        let expected = format!("{} {}", name, package_version(&source_dir.join("Cargo.toml"))?);
+        let allow_start_check = matches!(options.source, UpdateSource::GitRef(_));
+        verify_executable(&new_binary_path, Some(&expected), allow_start_check, theme)?;
+
+        let mut outcome = UpdateOutcome {
+            source: options.source.describe(),
+            previous_version: env!("CARGO_PKG_VERSION").to_string(),
+            new_version: new_version.clone(),
+            dry_run: options.dry_run,
+            installed_path: None,
+        };
+        if options.dry_run {
+            AnsiTheme::print_success(&format!("Dry run complete: {} was not replaced\n", current_exe.display()), theme);
+            return Ok(outcome);
+        }
+
+        // Step 4: Install the new binary where the terminal was launched from, keeping the current
+        // one next to it for rollback
+        install_executable(&new_binary_path, &current_exe, theme)?;
 
         // Step 5: Commit the Cargo.toml changes (prevent restore)
-        cargo_restore.commit();
+        if let Some(restore) = cargo_restore.as_mut() {
+            restore.commit();
+        }
+
+        // exec() and exit() skip destructors, so finish with the guards first
+        drop(cargo_restore);
+        drop(worktree);
+
+        if !options.restart {
+            AnsiTheme::print_success(&format!("Updated to v{}: {}\n", new_version, current_exe.display()), theme);
+            outcome.installed_path = Some(current_exe);
+            return Ok(outcome);
+        }
 
         // Step 6: Restart with the new binary
         AnsiTheme::print_themed("Restarting with new version...\n", theme);
+        restart(&current_exe, theme).map(|()| outcome)
+    }
 
-        let mut command = Command::new(&new_binary_path);
-        command.args(env::args().skip(1));
-        
-        AnsiTheme::print_themed(&format!("Command: {:?}\n", command), theme);
-        
-        command.spawn()
-            .map_err(|e| format!("Failed to spawn new process: {}", e))?;
+    /// Swaps the executable kept by the last update or rollback back into place
+    pub fn rollback_executable(&self, theme: &ThemeConfig) -> Result<PathBuf, String> {
+        let current_exe = launch_path()?;
+        let previous = previous_executable_path(&current_exe);
+        if !previous.exists() {
+            return Err(format!("No previous executable found at {}", previous.display()));
+        }
+        verify_executable(&previous, None, false, theme)?;
+        install_executable(&previous, &current_exe, theme)?;
+        Ok(current_exe)
+    }
+
+    /// Checks `reference` out into a temporary detached worktree of the repository at `root`
+    fn checkout_worktree(&self, root: &Path, reference: &str, theme: &ThemeConfig) -> Result<GitWorktree, String> {
+        let timeout = self.config.timeouts.get("git");
+        let verify = Command::new("git")
+            .current_dir(root)
+            .args(["rev-parse", "--verify", "--quiet", &format!("{}^{{commit}}", reference)])
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .output_within(timeout)
+            .map_err(|e| format!("Failed to run git: {}", e))?;
+        if !verify.status.success() {
+            return Err(format!("'{}' is not a tag, branch or commit in {}", reference, root.display()));
+        }
+        let commit = String::from_utf8_lossy(&verify.stdout).trim().to_string();
+
+        let path = env::temp_dir().join(format!("rust_dev_terminal_update_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&path);
+        AnsiTheme::print_themed(&format!("Checking out {} ({}) into {}\n", reference, &commit[..commit.len().min(12)], path.display()), theme);
+
+        let output = Command::new("git")
+            .current_dir(root)
+            .args(["worktree", "add", "--detach"])
+            .arg(&path)
+            .arg(&commit)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .output_within(timeout)
+            .map_err(|e| format!("Failed to run git: {}", e))?;
+        if !output.status.success() {
+            return Err(format!("Failed to check out {}: {}", reference, String::from_utf8_lossy(&output.stderr).trim()));
+        }
+        Ok(GitWorktree { repository: root.to_path_buf(), path, timeout })
+    }
+
+    /// Backups in the backups directory, newest first
+    pub fn list_backups(&self) -> Result<Vec<BackupInfo>, String> {
+        backup::list_backups(&self.get_backups_dir()?)
+    }
 
-        AnsiTheme::print_success("New process spawned successfully, exiting...\n", theme);
-        exit(0);
+    /// The project directory self-updates build from
+    pub fn project_root(&self) -> Option<PathBuf> {
+        self.find_cargo_toml().and_then(|p| p.parent().map(PathBuf::from))
+    }
+
+    /// Extracts `backup` into `target` and returns the directory it was restored to. Restoring over
+    /// the current project first backs up its present state, so the restore itself can be undone;
+    /// files that are not in the backup are left in place.
+    pub fn restore_backup(&self, backup: &BackupInfo, target: RestoreTarget, theme: &ThemeConfig) -> Result<PathBuf, String> {
+        let destination = match target {
+            RestoreTarget::Directory(directory) => {
+                let is_empty = fs::read_dir(&directory).map(|mut entries| entries.next().is_none()).unwrap_or(true);
+                if !is_empty {
+                    return Err(format!("{} already exists and is not empty", directory.display()));
+                }
+                directory
+            }
+            RestoreTarget::CurrentProject => {
+                let project_root = self.project_root().ok_or("Could not find project root directory")?;
+                let current_exe = env::current_exe()
+                    .map_err(|e| format!("Failed to get current executable path: {}", e))?;
+                let current_version = package_version(&project_root.join("Cargo.toml"))?;
+                AnsiTheme::print_themed("Backing up the current project before restoring...\n", theme);
+                self.create_backup(&current_exe, &current_version, theme)
+                    .map_err(|e| format!("Failed to back up the current project: {}", e))?;
+                project_root
+            }
+        };
+
+        AnsiTheme::print_themed(&format!("Restoring {} to {}\n", backup.path.display(), destination.display()), theme);
+        let count = backup::restore_backup(backup, &destination)?;
+        AnsiTheme::print_success(&format!("Restored {} files (v{})\n", count, backup.version), theme);
+        Ok(destination)
+    }
+
+    /// Builds the project in `source_dir` in release mode and installs the binary in place of the
+    /// running executable once it starts. Like an update it builds into target/self-update of the
+    /// current project, so the running executable is only touched by the install. The new version
+    /// is used from the next start.
+    pub fn rebuild_and_install(&self, source_dir: &Path, theme: &ThemeConfig) -> Result<PathBuf, String> {
+        let build_system = self_build_system();
+        let target_dir = self.project_root()
+            .map(|root| root.join("target").join("self-update"))
+            .unwrap_or_else(|| source_dir.join("target"));
+        AnsiTheme::print_themed(&format!("Building {}...\n", source_dir.display()), theme);
+        let result = build_system.build_self_in(source_dir, Some(&target_dir), theme)?;
+        if !result.success {
+            return Err(format!("Build failed: {}", result.stderr));
+        }
+        AnsiTheme::print_success("Build successful\n", theme);
+
+        let manifest = source_dir.join("Cargo.toml");
+        let name = package_name(&manifest)?;
+        let new_binary = target_dir.join("release").join(format!("{}{}", name, env::consts::EXE_SUFFIX));
+        if !new_binary.exists() {
+            return Err(format!("Built binary not found at: {}", new_binary.display()));
+        }
+        // A backup can predate `--version`
+        let expected = format!("{} {}", name, package_version(&manifest)?);
+        verify_executable(&new_binary, Some(&expected), true, theme)?;
+
+        let current_exe = launch_path()?;
+        install_executable(&new_binary, &current_exe, theme)?;
+        Ok(current_exe)
     }
 
     /// Increments the version in Cargo.toml and returns the new version
@@ -119,10 +379,42 @@ impl SelfUpdater {
         let content = fs::read_to_string(&cargo_toml_path)
             .map_err(|e| format!("Failed to read Cargo.toml: {}", e))?;
         
-        // Parse and increment version
+        let (new_content, current_version, new_version) = self.bump_manifest_version(&content)?;
+        AnsiTheme::print_success(&format!("Version updated: {} → {}\n", current_version, new_version), theme);
+        
+        // Create backup of original Cargo.toml
+        let backup_path = cargo_toml_path.with_extension("toml.backup");
+        fs::copy(&cargo_toml_path, &backup_path)
+            .map_err(|e| format!("Failed to backup Cargo.toml: {}", e))?;
+        
+        // Write updated Cargo.toml
+        fs::write(&cargo_toml_path, new_content)
+            .map_err(|e| format!("Failed to write updated Cargo.toml: {}", e))?;
+        
+        AnsiTheme::print_success("Cargo.toml updated successfully\n", theme);
+        
+        // Create restore guard
+        let backup_guard = CargoTomlRestore::new(cargo_toml_path, backup_path);
+        
+        Ok((new_version, backup_guard))
+    }
+    
+    /// The current and next version of the working tree, without touching Cargo.toml
+    fn preview_version_bump(&self) -> Result<(String, String), String> {
+        let cargo_toml_path = self.find_cargo_toml()
+            .ok_or("Could not find Cargo.toml".to_string())?;
+        let content = fs::read_to_string(&cargo_toml_path)
+            .map_err(|e| format!("Failed to read Cargo.toml: {}", e))?;
+        let (_, current_version, new_version) = self.bump_manifest_version(&content)?;
+        Ok((current_version, new_version))
+    }
+
+    /// Rewrites the `[package]` version of a Cargo.toml with the patch version incremented.
+    /// Returns the new content, the current version and the new version.
+    fn bump_manifest_version(&self, content: &str) -> Result<(String, String, String), String> {
         let mut in_package_section = false;
         let mut new_content = String::new();
-        let mut _current_version = None;
+        let mut current_version = None;
         let mut new_version = None;
         
         for line in content.lines() {
@@ -144,7 +436,7 @@ impl SelfUpdater {
                 let end_idx = version_str.rfind('"').ok_or("Invalid version format")?;
                 let version = &version_str[start_idx..end_idx];
                 
-                _current_version = Some(version.to_string());
+                current_version = Some(version.to_string());
                 
                 // Increment patch version
                 let new_ver = self.increment_patch_version(version)
@@ -154,168 +446,53 @@ impl SelfUpdater {
                 let new_line = format!("version = \"{}\"", new_ver);
                 new_content.push_str(&new_line);
                 new_content.push('\n');
-                
-                AnsiTheme::print_success(&format!("Version updated: {} → {}\n", version, new_ver), theme);
             } else {
                 new_content.push_str(line);
                 new_content.push('\n');
             }
         }
         
-        let new_version = new_version.ok_or("Could not find version in Cargo.toml".to_string())?;
-        
-        // Create backup of original Cargo.toml
-        let backup_path = cargo_toml_path.with_extension("toml.backup");
-        fs::copy(&cargo_toml_path, &backup_path)
-            .map_err(|e| format!("Failed to backup Cargo.toml: {}", e))?;
-        
-        // Write updated Cargo.toml
-        fs::write(&cargo_toml_path, new_content)
-            .map_err(|e| format!("Failed to write updated Cargo.toml: {}", e))?;
-        
-        AnsiTheme::print_success("Cargo.toml updated successfully\n", theme);
-        
-        // Create restore guard
-        let backup_guard = CargoTomlRestore::new(cargo_toml_path, backup_path);
-        
-        Ok((new_version, backup_guard))
+        match (current_version, new_version) {
+            (Some(current), Some(new)) => Ok((new_content, current, new)),
+            _ => Err("Could not find version in Cargo.toml".to_string()),
+        }
     }
     
-    /// Increments the patch version (major.minor.patch)
+    /// The version a self-update builds: the next pre-release on the same channel while on one
+    /// (`1.3.0-beta.1` → `1.3.0-beta.2`), otherwise the next patch version
     fn increment_patch_version(&self, version: &str) -> Result<String, String> {
-        let parts: Vec<&str> = version.split('.').collect();
-        if parts.len() != 3 {
-            return Err(format!("Invalid version format: {}", version));
-        }
-        
-        let patch: u32 = parts[2].parse()
-            .map_err(|_| format!("Invalid patch version: {}", parts[2]))?;
-        
-        let new_version = format!("{}.{}.{}", parts[0], parts[1], patch + 1);
-        Ok(new_version)
+        let version = Version::parse(version)?;
+        let bump = match version.channel() {
+            Some(channel) => Bump::Pre(channel),
+            None => Bump::Patch,
+        };
+        Ok(version.bump(bump)?.to_string())
     }
     
-    /// Creates a backup zip of the entire project
+    /// Backs up the project's source tree as configured in `[backup]`, then applies the retention policy
     fn create_backup(&self, current_exe: &Path, new_version: &str, theme: &ThemeConfig) -> Result<(), String> {
         let backups_dir = self.get_backups_dir()?;
-        
-        // Create backups directory if it doesn't exist
-        fs::create_dir_all(&backups_dir)
-            .map_err(|e| format!("Failed to create backups directory: {}", e))?;
-
         AnsiTheme::print_themed(&format!("Backups directory: {}\n", backups_dir.display()), theme);
-        
-        let timestamp = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .map_err(|e| format!("Failed to get timestamp: {}", e))?
-            .as_secs();
-        
-        let exe_name = current_exe.file_stem()
-            .and_then(|s| s.to_str())
-            .ok_or("Invalid executable name")?;
-        
-        let backup_filename = format!("{}-backup-v{}-{}.zip", exe_name, new_version, timestamp);
-        let backup_path = backups_dir.join(&backup_filename);
-        
-        AnsiTheme::print_themed(&format!("Creating full project backup: {}\n", backup_path.display()), theme);
-        
+
+        let timestamp = unix_now()?;
+
+        let exe_name = executable_name(current_exe).ok_or("Invalid executable name")?;
+
         // Get project root directory
-        let project_root = self.find_cargo_toml()
-            .and_then(|p| p.parent().map(PathBuf::from))
+        let project_root = self.project_root()
             .ok_or("Could not find project root directory".to_string())?;
-        
+
         AnsiTheme::print_themed(&format!("Project root: {}\n", project_root.display()), theme);
-        
-        // Create zip file
-        let file = File::create(&backup_path)
-            .map_err(|e| format!("Failed to create backup file: {}", e))?;
-        
-        let mut zip = zip::ZipWriter::new(file);
-        let options = zip::write::FileOptions::default()
-            .compression_method(zip::CompressionMethod::Stored);
-        
-        // Walk through project directory and add all relevant files
-        self.add_directory_to_zip(&mut zip, &project_root, &project_root, &options)
-            .map_err(|e| format!("Failed to add directory to zip: {}", e))?;
-        
-        zip.finish()
-            .map_err(|e| format!("Failed to finalize backup zip: {}", e))?;
+        AnsiTheme::print_themed(&format!("Backup settings: {}\n", self.config.backup.describe()), theme);
 
-        // Verify the backup was created
-        let backup_metadata = fs::metadata(&backup_path)
-            .map_err(|e| format!("Failed to verify backup creation: {}", e))?;
-        
-        AnsiTheme::print_success(&format!("Full project backup created: {} ({} bytes)\n", backup_path.display(), backup_metadata.len()), theme);
-        
-        Ok(())
-    }
-    
-    /// Recursively adds a directory to the zip file
-    fn add_directory_to_zip(
-        &self,
-        zip: &mut zip::ZipWriter<File>,
-        base_path: &Path,
-        current_path: &Path,
-        options: &zip::write::FileOptions,
-    ) -> Result<(), String> {
-        let entries = fs::read_dir(current_path)
-            .map_err(|e| format!("Failed to read directory {}: {}", current_path.display(), e))?;
-        
-        for entry in entries {
-            let entry = entry.map_err(|e| format!("Failed to read directory entry: {}", e))?;
-            let path = entry.path();
-            let metadata = entry.metadata()
-                .map_err(|e| format!("Failed to get metadata for {}: {}", path.display(), e))?;
-            
-            // Calculate relative path for zip
-            let relative_path = path.strip_prefix(base_path)
-                .map_err(|e| format!("Failed to calculate relative path: {}", e))?;
-            
-            if metadata.is_dir() {
-                // Skip target directory and .git directory to avoid huge backups
-                if path.file_name().and_then(|s| s.to_str()) == Some("target") 
-                    || path.file_name().and_then(|s| s.to_str()) == Some(".git") {
-                    AnsiTheme::print_themed(&format!("Skipping directory: {}\n", path.display()), &ThemeConfig::default());
-                    continue;
-                }
-                
-                // Add directory entry to zip
-                if !relative_path.as_os_str().is_empty() {
-                    let dir_name = format!("{}/", relative_path.to_string_lossy());
-                    zip.add_directory(&dir_name, *options)
-                        .map_err(|e| format!("Failed to add directory to zip: {}", e))?;
-                }
-                
-                // Recursively add contents
-                self.add_directory_to_zip(zip, base_path, &path, options)?;
-            } else {
-                // Skip large or unnecessary files
-                if let Some(ext) = path.extension().and_then(|s| s.to_str()) {
-                    if ext == "zip" || ext == "backup" {
-                        AnsiTheme::print_themed(&format!("Skipping backup file: {}\n", path.display()), &ThemeConfig::default());
-                        continue;
-                    }
-                }
-                
-                // Skip files in target directory (in case we encounter any)
-                if path.components().any(|c| c.as_os_str() == "target") {
-                    continue;
-                }
-                
-                // Add file to zip
-                let file_data = fs::read(&path)
-                    .map_err(|e| format!("Failed to read file {}: {}", path.display(), e))?;
-                
-                zip.start_file(relative_path.to_string_lossy().as_ref(), *options)
-                    .map_err(|e| format!("Failed to start zip entry: {}", e))?;
-                
-                zip.write_all(&file_data)
-                    .map_err(|e| format!("Failed to write file to zip: {}", e))?;
-                
-                AnsiTheme::print_themed(&format!("Added to backup: {}\n", relative_path.display()), &ThemeConfig::default());
-            }
+        let summary = backup::create_backup(&project_root, &backups_dir, &exe_name, new_version, timestamp, &self.config.backup)?;
+
+        AnsiTheme::print_success(&format!("Project backup created: {} ({} files, {}, {} written)\n",
+            summary.path.display(), summary.files, format_bytes(summary.total_bytes), format_bytes(summary.stored_bytes)), theme);
+        for removed in &summary.removed {
+            AnsiTheme::print_themed(&format!("Removed old backup: {}\n", removed.display()), theme);
         }
-        
+
         Ok(())
     }
     
@@ -351,7 +528,7 @@ impl SelfUpdater {
         
         let exe_name = env::current_exe()
             .ok()
-            .and_then(|p| p.file_stem().map(|s| s.to_string_lossy().to_string()))
+            .and_then(|p| executable_name(&p))
             .unwrap_or_else(|| "app".to_string());
         
         dir.push(&exe_name);
@@ -362,6 +539,207 @@ impl SelfUpdater {
     }
 }
 
+// Self-builds choose their own directory, so only the logging settings matter here
+fn self_build_system() -> BuildSystem {
+    BuildSystem::new_with_config(crate::config::AppConfig {
+        log_file: "self_update.log".to_string(),
+        verbose_logging: true,
+        ..crate::config::AppConfig::default()
+    })
+}
+
+fn unix_now() -> Result<u64, String> {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .map_err(|e| format!("Failed to get timestamp: {}", e))
+}
+
+// Linux reports a running executable that has been replaced as "<path> (deleted)"
+fn executable_name(path: &Path) -> Option<String> {
+    path.file_stem()
+        .and_then(|s| s.to_str())
+        .map(|name| name.trim_end_matches(" (deleted)").to_string())
+}
+
+fn read_package_field(cargo_toml: &Path, field: &str) -> Result<String, String> {
+    let content = fs::read_to_string(cargo_toml)
+        .map_err(|e| format!("Failed to read {}: {}", cargo_toml.display(), e))?;
+    let manifest: toml::Value = toml::from_str(&content)
+        .map_err(|e| format!("Invalid {}: {}", cargo_toml.display(), e))?;
+    manifest.get("package")
+        .and_then(|package| package.get(field))
+        .and_then(|value| value.as_str())
+        .map(str::to_string)
+        .ok_or_else(|| format!("Could not read the package {} from {}", field, cargo_toml.display()))
+}
+
+fn package_version(cargo_toml: &Path) -> Result<String, String> {
+    read_package_field(cargo_toml, "version")
+}
+
+fn package_name(cargo_toml: &Path) -> Result<String, String> {
+    read_package_field(cargo_toml, "name")
+}
+
+/// Where the executable replaced by the last update is kept, e.g. `rust_dev_terminal-old`
+fn previous_executable_path(current_exe: &Path) -> PathBuf {
+    let stem = current_exe.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+    match current_exe.extension() {
+        Some(ext) => current_exe.with_file_name(format!("{}-old.{}", stem, ext.to_string_lossy())),
+        None => current_exe.with_file_name(format!("{}-old", stem)),
+    }
+}
+
+/// The running executable's path. Linux reports one that has been replaced as "<path> (deleted)".
+fn launch_path() -> Result<PathBuf, String> {
+    let current_exe = env::current_exe()
+        .map_err(|e| format!("Failed to get current executable path: {}", e))?;
+    match current_exe.to_str().and_then(|path| path.strip_suffix(" (deleted)")) {
+        Some(path) => Ok(PathBuf::from(path)),
+        None => Ok(current_exe),
+    }
+}
+
+/// Runs `binary --version` and checks that it succeeds and, if given, reports `expected`
+/// (e.g. "rust_dev_terminal 1.1.3"). Sources older than the `--version` flag (a git ref or a
+/// restored backup) start the interactive menu instead, so with `allow_start_check` a binary that
+/// does not answer falls back to a plain start check: it passes unless it exits with an error.
+fn verify_executable(binary: &Path, expected: Option<&str>, allow_start_check: bool, theme: &ThemeConfig) -> Result<(), String> {
+    let timeout = if allow_start_check { START_CHECK_TIMEOUT } else { SELF_CHECK_TIMEOUT };
+    let (output, termination) = Command::new(binary)
+        .arg("--version")
+        .stdin(Stdio::null())
+        .supervised_output(Some(timeout))
+        .map_err(|e| format!("Self-check of {} failed to start: {}", binary.display(), e))?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let answered = termination == Termination::Exited && output.status.success()
+        && expected.is_none_or(|expected| stdout.contains(expected));
+    // A binary that reports its name did answer, so a wrong version is never a passed start check
+    let named = expected.and_then(|expected| expected.split_whitespace().next()).is_some_and(|name| stdout.contains(name));
+    let started = termination == Termination::TimedOut || (output.status.success() && !named);
+    if !answered && allow_start_check && started {
+        AnsiTheme::print_warning(&format!("{} did not answer --version, it may predate the flag\n", binary.display()), theme);
+        AnsiTheme::print_success("Start check passed\n", theme);
+        return Ok(());
+    }
+    if let Some(interruption) = termination.interruption() {
+        return Err(format!("Self-check of {} {}", binary.display(), interruption));
+    }
+    if !answered {
+        return Err(format!("Self-check of {} failed: expected '{}' from --version, got: {} {}",
+            binary.display(), expected.unwrap_or("a version"), stdout.trim(), String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    AnsiTheme::print_success(&format!("Self-check passed: {}\n", expected.unwrap_or("--version succeeded")), theme);
+    Ok(())
+}
+
+/// Puts `new_binary` where `current_exe` is and keeps the replaced executable at
+/// `previous_executable_path`. The new binary is staged next to the target first, so `new_binary`
+/// may be that previous executable itself (a rollback swaps the two). Windows cannot overwrite a
+/// running executable, so it is moved aside; elsewhere the staged copy takes the current one's
+/// permissions and is renamed over it, which is atomic on the same filesystem.
+fn install_executable(new_binary: &Path, current_exe: &Path, theme: &ThemeConfig) -> Result<(), String> {
+    if fs::canonicalize(new_binary).ok() == fs::canonicalize(current_exe).ok() {
+        AnsiTheme::print_success(&format!("Executable rebuilt in place: {}\n", current_exe.display()), theme);
+        return Ok(());
+    }
+
+    let staged = current_exe.with_extension("new");
+    let previous = previous_executable_path(current_exe);
+    fs::copy(new_binary, &staged)
+        .map_err(|e| format!("Failed to copy {}: {}", new_binary.display(), e))?;
+
+    if cfg!(target_os = "windows") {
+        let _ = fs::remove_file(&previous);
+        rename(current_exe, &previous)
+            .map_err(|e| format!("Failed to rename current executable: {}", e))?;
+    } else {
+        let permissions = fs::metadata(current_exe)
+            .map_err(|e| format!("Failed to read {}: {}", current_exe.display(), e))?
+            .permissions();
+        fs::copy(current_exe, &previous)
+            .map_err(|e| format!("Failed to keep the current executable as {}: {}", previous.display(), e))?;
+        fs::set_permissions(&staged, permissions)
+            .map_err(|e| format!("Failed to set permissions on {}: {}", staged.display(), e))?;
+        File::open(&staged)
+            .and_then(|file| file.sync_all())
+            .map_err(|e| format!("Failed to flush {}: {}", staged.display(), e))?;
+    }
+    if let Err(e) = rename(&staged, current_exe) {
+        let _ = fs::remove_file(&staged);
+        if cfg!(target_os = "windows") {
+            let _ = rename(&previous, current_exe);
+        }
+        return Err(format!("Failed to install {}: {}", current_exe.display(), e));
+    }
+
+    AnsiTheme::print_success(&format!("Installed {} as {}\n", new_binary.display(), current_exe.display()), theme);
+    AnsiTheme::print_themed(&format!("Previous executable kept as {}\n", previous.display()), theme);
+    Ok(())
+}
+
+/// Replaces this process with `executable`, run with the same arguments
+#[cfg(unix)]
+fn restart(executable: &Path, theme: &ThemeConfig) -> Result<(), String> {
+    use std::os::unix::process::CommandExt;
+
+    let mut command = Command::new(executable);
+    command.args(env::args().skip(1));
+    AnsiTheme::print_themed(&format!("Command: {:?}\n", command), theme);
+
+    crate::jobs::kill_all_jobs();
+    let error = command.exec();
+    Err(format!("Failed to re-exec {}: {}", executable.display(), error))
+}
+
+/// Starts `executable` with the same arguments and exits
+#[cfg(not(unix))]
+fn restart(executable: &Path, theme: &ThemeConfig) -> Result<(), String> {
+    let mut command = Command::new(executable);
+    command.args(env::args().skip(1));
+    AnsiTheme::print_themed(&format!("Command: {:?}\n", command), theme);
+
+    command.spawn()
+        .map_err(|e| format!("Failed to spawn new process: {}", e))?;
+
+    AnsiTheme::print_success("New process spawned successfully, exiting...\n", theme);
+    crate::jobs::kill_all_jobs();
+    std::process::exit(0);
+}
+
+/// A temporary `git worktree` checkout, removed again when dropped
+struct GitWorktree {
+    repository: PathBuf,
+    path: PathBuf,
+    timeout: Option<Duration>,
+}
+
+impl Drop for GitWorktree {
+    fn drop(&mut self) {
+        let removed = Command::new("git")
+            .current_dir(&self.repository)
+            .args(["worktree", "remove", "--force"])
+            .arg(&self.path)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .output_within(self.timeout)
+            .map(|output| output.status.success())
+            .unwrap_or(false);
+        if !removed {
+            let _ = fs::remove_dir_all(&self.path);
+            let _ = Command::new("git")
+                .current_dir(&self.repository)
+                .args(["worktree", "prune"])
+                .stdin(Stdio::null())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .output_within(self.timeout);
+        }
+    }
+}
+
 /// Helper struct to restore Cargo.toml backup if something goes wrong
 struct CargoTomlRestore {
     original_path: PathBuf,
@@ -395,4 +773,78 @@ impl Drop for CargoTomlRestore {
             let _ = fs::remove_file(&self.backup_path);
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    #[test]
+    fn bumps_only_the_package_version() {
+        let updater = SelfUpdater::new_with_config(AppConfig::default());
+        let manifest = "[package]\nname = \"app\"\nversion = \"1.2.9\"\n\n[dependencies]\nversion = \"0.1.0\"\n";
+        let (content, current, next) = updater.bump_manifest_version(manifest).unwrap();
+        assert_eq!((current.as_str(), next.as_str()), ("1.2.9", "1.2.10"));
+        assert!(content.contains("version = \"1.2.10\"") && content.contains("version = \"0.1.0\""));
+        assert!(updater.bump_manifest_version("[package]\nname = \"app\"\n").is_err());
+
+        let (_, _, next) = updater.bump_manifest_version("[package]\nversion = \"1.3.0-beta.1+abc\"\n").unwrap();
+        assert_eq!(next, "1.3.0-beta.2");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn installs_atomically_keeping_the_previous_executable() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = TempDir::new("install");
+        fs::create_dir_all(dir.join("build")).unwrap();
+        let current = dir.join("app");
+        let built = dir.join("build").join("app");
+        fs::write(&current, "old").unwrap();
+        fs::set_permissions(&current, fs::Permissions::from_mode(0o750)).unwrap();
+        fs::write(&built, "new").unwrap();
+        fs::set_permissions(&built, fs::Permissions::from_mode(0o644)).unwrap();
+
+        let theme = ThemeConfig::default();
+        install_executable(&built, &current, &theme).unwrap();
+        let previous = previous_executable_path(&current);
+        assert_eq!(previous, dir.join("app-old"));
+        assert_eq!(fs::read_to_string(&current).unwrap(), "new");
+        assert_eq!(fs::metadata(&current).unwrap().permissions().mode() & 0o777, 0o750);
+        assert_eq!(fs::read_to_string(&previous).unwrap(), "old");
+        assert!(!current.with_extension("new").exists());
+
+        // Rolling back swaps the two
+        install_executable(&previous, &current, &theme).unwrap();
+        assert_eq!(fs::read_to_string(&current).unwrap(), "old");
+        assert_eq!(fs::read_to_string(&previous).unwrap(), "new");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn falls_back_to_a_start_check_only_when_allowed() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = TempDir::new("verify");
+        let script = |name: &str, body: &str| {
+            let path = dir.join(name);
+            fs::write(&path, format!("#!/bin/sh\n{}\n", body)).unwrap();
+            fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+            path
+        };
+        let current = script("current", "echo app 1.2.0");
+        let wrong = script("wrong", "echo app 1.1.0");
+        let silent = script("silent", "exit 0");
+        let failing = script("failing", "exit 1");
+
+        let theme = ThemeConfig::default();
+        let expected = Some("app 1.2.0");
+        assert!(verify_executable(&current, expected, false, &theme).is_ok());
+        assert!(verify_executable(&wrong, expected, true, &theme).is_err());
+        assert!(verify_executable(&silent, expected, false, &theme).is_err());
+        assert!(verify_executable(&silent, expected, true, &theme).is_ok());
+        assert!(verify_executable(&failing, expected, true, &theme).is_err());
+    }
+}
@@ -0,0 +1,468 @@
+// size_report.rs
+use object::{BinaryFormat, Object, ObjectSection, ObjectSymbol, SectionIndex, SymbolKind};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+use chrono::Local;
+use crate::ansi_theme::AnsiTheme;
+use crate::config::ThemeConfig;
+use crate::project_settings;
+
+const HISTORY_FILE: &str = "size_history.json";
+// Release builds remembered for comparison
+const HISTORY_LIMIT: usize = 20;
+// Symbols that do not demangle to a Rust path: C, assembly and linker-generated code
+const NON_RUST: &str = "[non-Rust]";
+// Rows shown per table
+const SHOWN_SECTIONS: usize = 12;
+const SHOWN_CRATES: usize = 15;
+const SHOWN_CHANGES: usize = 15;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SectionSize {
+    pub name: String,
+    pub size: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SymbolSize {
+    /// Demangled, without the trailing hash
+    pub name: String,
+    pub crate_name: String,
+    pub size: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CrateSize {
+    pub name: String,
+    pub size: u64,
+    pub symbols: usize,
+}
+
+/// Section and symbol sizes of one ELF, PE or Mach-O binary
+#[derive(Debug, Clone, Serialize)]
+pub struct SizeReport {
+    pub path: PathBuf,
+    pub format: String,
+    pub file_size: u64,
+    /// Largest first
+    pub sections: Vec<SectionSize>,
+    /// Code and data symbol sizes summed per crate, largest first
+    pub crates: Vec<CrateSize>,
+    /// The largest symbols, largest first
+    pub largest_symbols: Vec<SymbolSize>,
+}
+
+impl SizeReport {
+    /// Parses the binary at `path`, keeping the `top_symbols` largest symbols
+    pub fn analyze(path: &Path, top_symbols: usize) -> Result<Self, String> {
+        let data = fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let file = object::File::parse(&*data)
+            .map_err(|e| format!("Could not parse {} as a binary: {}", path.display(), e))?;
+
+        let format = match file.format() {
+            BinaryFormat::Elf => "ELF",
+            BinaryFormat::Pe => "PE",
+            BinaryFormat::MachO => "Mach-O",
+            BinaryFormat::Coff => "COFF",
+            _ => "other",
+        };
+
+        let mut sections: Vec<SectionSize> = file.sections()
+            .filter(|section| section.size() > 0)
+            .map(|section| SectionSize {
+                name: section.name().unwrap_or("?").to_string(),
+                size: section.size(),
+            })
+            .collect();
+        sections.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.name.cmp(&b.name)));
+
+        let mut symbols = symbol_sizes(&file);
+        let mut crates: HashMap<String, CrateSize> = HashMap::new();
+        for symbol in &symbols {
+            let entry = crates.entry(symbol.crate_name.clone()).or_insert_with(|| CrateSize {
+                name: symbol.crate_name.clone(),
+                size: 0,
+                symbols: 0,
+            });
+            entry.size += symbol.size;
+            entry.symbols += 1;
+        }
+        let mut crates: Vec<CrateSize> = crates.into_values().collect();
+        crates.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.name.cmp(&b.name)));
+
+        symbols.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.name.cmp(&b.name)));
+        symbols.truncate(top_symbols);
+
+        Ok(SizeReport {
+            path: path.to_path_buf(),
+            format: format.to_string(),
+            file_size: data.len() as u64,
+            sections,
+            crates,
+            largest_symbols: symbols,
+        })
+    }
+
+    pub fn snapshot(&self, commit: Option<String>) -> SizeSnapshot {
+        let modified = fs::metadata(&self.path)
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        SizeSnapshot {
+            timestamp: Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+            commit,
+            modified,
+            file_size: self.file_size,
+            sections: self.sections.iter().map(|s| (s.name.clone(), s.size)).collect(),
+            crates: self.crates.iter().map(|c| (c.name.clone(), c.size)).collect(),
+        }
+    }
+
+    pub fn print(&self, label: &str, top_symbols: usize, theme: &ThemeConfig) {
+        AnsiTheme::print_themed("\n", theme);
+        AnsiTheme::print_blue(&format!("{} binary: {} ({}, {})\n", label, self.path.display(), self.format, format_bytes(self.file_size)), theme);
+
+        AnsiTheme::print_cyan(&format!(" {:<28} {:>10} {:>7}\n", "SECTION", "SIZE", "FILE%"), theme);
+        for section in self.sections.iter().take(SHOWN_SECTIONS) {
+            AnsiTheme::print_themed(&format!(" {:<28} {:>10} {:>6.1}%\n",
+                section.name, format_bytes(section.size), percent(section.size, self.file_size)), theme);
+        }
+        if self.sections.len() > SHOWN_SECTIONS {
+            AnsiTheme::print_themed(&format!(" ... {} smaller sections\n", self.sections.len() - SHOWN_SECTIONS), theme);
+        }
+
+        if self.crates.is_empty() {
+            AnsiTheme::print_warning(" No symbols found - the binary is stripped\n", theme);
+            return;
+        }
+        let symbol_total: u64 = self.crates.iter().map(|c| c.size).sum();
+        AnsiTheme::print_cyan(&format!(" {:<28} {:>10} {:>7} {:>8}\n", "CRATE", "SIZE", "SYMS%", "SYMBOLS"), theme);
+        for entry in self.crates.iter().take(SHOWN_CRATES) {
+            AnsiTheme::print_themed(&format!(" {:<28} {:>10} {:>6.1}% {:>8}\n",
+                entry.name, format_bytes(entry.size), percent(entry.size, symbol_total), entry.symbols), theme);
+        }
+        if self.crates.len() > SHOWN_CRATES {
+            AnsiTheme::print_themed(&format!(" ... {} smaller crates\n", self.crates.len() - SHOWN_CRATES), theme);
+        }
+
+        AnsiTheme::print_cyan(&format!(" {:>10}  {}\n", "SIZE", "LARGEST SYMBOLS"), theme);
+        for symbol in self.largest_symbols.iter().take(top_symbols) {
+            AnsiTheme::print_themed(&format!(" {:>10}  {}\n", format_bytes(symbol.size), truncate(&symbol.name, 100)), theme);
+        }
+    }
+}
+
+// Defined code and data symbols. Symbols without a recorded size (common in PE/COFF)
+// are sized up to the next symbol or the end of their section.
+fn symbol_sizes(file: &object::File) -> Vec<SymbolSize> {
+    let mut by_section: BTreeMap<usize, Vec<(u64, u64, String)>> = BTreeMap::new();
+    for symbol in file.symbols() {
+        if !symbol.is_definition() || !matches!(symbol.kind(), SymbolKind::Text | SymbolKind::Data) {
+            continue;
+        }
+        let (Some(index), Ok(name)) = (symbol.section_index(), symbol.name()) else { continue };
+        if name.is_empty() {
+            continue;
+        }
+        by_section.entry(index.0).or_default().push((symbol.address(), symbol.size(), name.to_string()));
+    }
+
+    let mut sizes = Vec::new();
+    for (index, mut symbols) in by_section {
+        let section_end = file.section_by_index(SectionIndex(index))
+            .map(|s| s.address() + s.size())
+            .unwrap_or(0);
+        symbols.sort();
+        symbols.dedup_by(|b, a| a.0 == b.0 && a.2 == b.2);
+
+        for i in 0..symbols.len() {
+            let (address, size, ref raw) = symbols[i];
+            let size = if size > 0 {
+                size
+            } else {
+                let next = symbols[i + 1..].iter().map(|s| s.0).find(|&a| a > address).unwrap_or(section_end);
+                next.saturating_sub(address)
+            };
+            if size == 0 {
+                continue;
+            }
+            let name = demangle(raw);
+            sizes.push(SymbolSize { crate_name: crate_name(&name), name, size });
+        }
+    }
+    sizes
+}
+
+fn demangle(raw: &str) -> String {
+    // PE symbols can carry a leading underscore the demangler does not expect
+    match rustc_demangle::try_demangle(raw).or_else(|_| rustc_demangle::try_demangle(raw.trim_start_matches('_'))) {
+        Ok(demangled) => format!("{:#}", demangled),
+        Err(_) => raw.to_string(),
+    }
+}
+
+/// The crate a demangled Rust path belongs to: the identifier before the first `::`.
+/// `<alloc::vec::Vec<T> as core::fmt::Debug>::fmt` is `alloc`, and blanket impls like
+/// `<&T as core::fmt::Display>::fmt` fall back to the trait's crate.
+pub fn crate_name(demangled: &str) -> String {
+    let Some(separator) = demangled.find("::") else {
+        return NON_RUST.to_string();
+    };
+    let start = demangled[..separator]
+        .rfind(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .map(|i| i + 1)
+        .unwrap_or(0);
+    match &demangled[start..separator] {
+        "" => NON_RUST.to_string(),
+        name => name.to_string(),
+    }
+}
+
+/// Sizes of one build, stored to compare later builds against
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SizeSnapshot {
+    pub timestamp: String,
+    pub commit: Option<String>,
+    /// Binary modification time in seconds since the epoch, identifying the build
+    pub modified: u64,
+    pub file_size: u64,
+    pub sections: BTreeMap<String, u64>,
+    pub crates: BTreeMap<String, u64>,
+}
+
+impl SizeSnapshot {
+    fn same_build(&self, other: &SizeSnapshot) -> bool {
+        self.modified == other.modified && self.file_size == other.file_size
+    }
+}
+
+/// Recent release builds of one project, oldest first
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SizeHistory {
+    pub snapshots: Vec<SizeSnapshot>,
+}
+
+impl SizeHistory {
+    pub fn load(project_path: &Path) -> Self {
+        fs::read_to_string(project_path.join(project_settings::STATE_DIR).join(HISTORY_FILE))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, project_path: &Path) -> Result<(), String> {
+        let path = project_settings::state_dir(project_path)?.join(HISTORY_FILE);
+        let content = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        fs::write(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+
+    /// The most recent snapshot of a different build than `current`
+    pub fn previous(&self, current: &SizeSnapshot) -> Option<&SizeSnapshot> {
+        self.snapshots.iter().rev().find(|snapshot| !snapshot.same_build(current))
+    }
+
+    /// Adds `snapshot` unless it is the build recorded last; returns whether it was added
+    pub fn record(&mut self, snapshot: SizeSnapshot) -> bool {
+        if self.snapshots.last().is_some_and(|last| last.same_build(&snapshot)) {
+            return false;
+        }
+        self.snapshots.push(snapshot);
+        if self.snapshots.len() > HISTORY_LIMIT {
+            self.snapshots.drain(..self.snapshots.len() - HISTORY_LIMIT);
+        }
+        true
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SizeDelta {
+    pub name: String,
+    pub before: u64,
+    pub after: u64,
+}
+
+impl SizeDelta {
+    pub fn change(&self) -> i64 {
+        self.after as i64 - self.before as i64
+    }
+}
+
+/// Size differences between two builds
+#[derive(Debug, Clone, Serialize)]
+pub struct SizeComparison {
+    pub before_label: String,
+    pub after_label: String,
+    pub file_size: SizeDelta,
+    /// Changed sections, largest change first
+    pub sections: Vec<SizeDelta>,
+    /// Changed crates, largest change first
+    pub crates: Vec<SizeDelta>,
+}
+
+impl SizeComparison {
+    pub fn new(before_label: &str, before: &SizeSnapshot, after_label: &str, after: &SizeSnapshot) -> Self {
+        SizeComparison {
+            before_label: before_label.to_string(),
+            after_label: after_label.to_string(),
+            file_size: SizeDelta { name: "file".to_string(), before: before.file_size, after: after.file_size },
+            sections: deltas(&before.sections, &after.sections),
+            crates: deltas(&before.crates, &after.crates),
+        }
+    }
+
+    /// Growth is shown in red and shrinkage in green
+    pub fn print(&self, theme: &ThemeConfig) {
+        AnsiTheme::print_themed("\n", theme);
+        AnsiTheme::print_blue(&format!("{} -> {}: {} -> {} ({})\n", self.before_label, self.after_label,
+            format_bytes(self.file_size.before), format_bytes(self.file_size.after), format_change(&self.file_size)), theme);
+
+        for (title, deltas) in [("SECTION", &self.sections), ("CRATE", &self.crates)] {
+            if deltas.is_empty() {
+                continue;
+            }
+            AnsiTheme::print_cyan(&format!(" {:<28} {:>10} {:>10} {:>18}\n", title, "BEFORE", "AFTER", "CHANGE"), theme);
+            for delta in deltas.iter().take(SHOWN_CHANGES) {
+                let line = format!(" {:<28} {:>10} {:>10} {:>18}\n",
+                    delta.name, format_bytes(delta.before), format_bytes(delta.after), format_change(delta));
+                if delta.change() > 0 {
+                    AnsiTheme::print_error(&line, theme);
+                } else {
+                    AnsiTheme::print_success(&line, theme);
+                }
+            }
+            if deltas.len() > SHOWN_CHANGES {
+                AnsiTheme::print_themed(&format!(" ... {} smaller changes\n", deltas.len() - SHOWN_CHANGES), theme);
+            }
+        }
+        if self.sections.is_empty() && self.crates.is_empty() {
+            AnsiTheme::print_info(" No section or crate sizes changed\n", theme);
+        }
+    }
+}
+
+fn deltas(before: &BTreeMap<String, u64>, after: &BTreeMap<String, u64>) -> Vec<SizeDelta> {
+    let mut deltas: Vec<SizeDelta> = before.keys().chain(after.keys())
+        .collect::<std::collections::BTreeSet<_>>()
+        .into_iter()
+        .map(|name| SizeDelta {
+            name: name.clone(),
+            before: before.get(name).copied().unwrap_or(0),
+            after: after.get(name).copied().unwrap_or(0),
+        })
+        .filter(|delta| delta.change() != 0)
+        .collect();
+    deltas.sort_by(|a, b| b.change().unsigned_abs().cmp(&a.change().unsigned_abs()).then_with(|| a.name.cmp(&b.name)));
+    deltas
+}
+
+/// Debug and release sizes of a project with comparisons between them and the previous release build
+#[derive(Debug, Clone, Serialize)]
+pub struct BinarySizeReport {
+    pub debug: Option<SizeReport>,
+    pub release: Option<SizeReport>,
+    pub debug_vs_release: Option<SizeComparison>,
+    pub since_previous_release: Option<SizeComparison>,
+    /// When the previous release build was recorded, if there was one
+    pub previous_release: Option<String>,
+}
+
+impl BinarySizeReport {
+    pub fn print(&self, top_symbols: usize, theme: &ThemeConfig) {
+        if let Some(report) = &self.debug {
+            report.print("Debug", top_symbols, theme);
+        }
+        if let Some(report) = &self.release {
+            report.print("Release", top_symbols, theme);
+        }
+        if let Some(comparison) = &self.debug_vs_release {
+            comparison.print(theme);
+        }
+        match &self.since_previous_release {
+            Some(comparison) => comparison.print(theme),
+            None if self.release.is_some() => {
+                AnsiTheme::print_info("\n No earlier release build recorded yet - the next release build will be compared with this one\n", theme);
+            }
+            None => {}
+        }
+    }
+}
+
+pub fn format_bytes(bytes: u64) -> String {
+    let bytes = bytes as f64;
+    if bytes >= 1024.0 * 1024.0 {
+        format!("{:.2} MiB", bytes / (1024.0 * 1024.0))
+    } else if bytes >= 1024.0 {
+        format!("{:.1} KiB", bytes / 1024.0)
+    } else {
+        format!("{} B", bytes)
+    }
+}
+
+fn format_change(delta: &SizeDelta) -> String {
+    let change = delta.change();
+    let sign = if change >= 0 { "+" } else { "-" };
+    let magnitude = format_bytes(change.unsigned_abs());
+    match delta.before {
+        0 => format!("{}{} new", sign, magnitude),
+        before => format!("{}{} {:+.1}%", sign, magnitude, change as f64 / before as f64 * 100.0),
+    }
+}
+
+fn percent(part: u64, total: u64) -> f64 {
+    if total == 0 { 0.0 } else { part as f64 / total as f64 * 100.0 }
+}
+
+fn truncate(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        text.to_string()
+    } else {
+        format!("{}...", text.chars().take(max - 3).collect::<String>())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn groups_symbols_by_crate() {
+        assert_eq!(crate_name("core::fmt::write"), "core");
+        assert_eq!(crate_name("<alloc::vec::Vec<T> as core::fmt::Debug>::fmt"), "alloc");
+        assert_eq!(crate_name("<&T as core::fmt::Display>::fmt"), "core");
+        assert_eq!(crate_name("my_app::main"), "my_app");
+        assert_eq!(crate_name("memcpy"), NON_RUST);
+        assert_eq!(crate_name(&demangle("_ZN4core3fmt5write17h0123456789abcdefE")), "core");
+    }
+
+    #[test]
+    fn analyzes_own_binary_and_tracks_history() {
+        let report = SizeReport::analyze(&std::env::current_exe().unwrap(), 5).unwrap();
+        assert!(report.file_size > 0);
+        assert!(!report.sections.is_empty());
+        assert!(report.largest_symbols.len() <= 5);
+        assert!(report.crates.iter().any(|c| c.name == "core" || c.name == "std"));
+
+        let first = report.snapshot(None);
+        let mut second = first.clone();
+        second.modified += 1;
+        second.file_size += 100;
+        *second.crates.entry("serde".to_string()).or_default() += 100;
+
+        let mut history = SizeHistory::default();
+        assert!(history.record(first.clone()));
+        assert!(!history.record(first.clone()));
+        assert!(history.record(second.clone()));
+        assert!(history.previous(&second).unwrap().same_build(&first));
+
+        let comparison = SizeComparison::new("previous", &first, "current", &second);
+        assert_eq!(comparison.file_size.change(), 100);
+        assert_eq!(comparison.crates.len(), 1);
+        assert_eq!(comparison.crates[0].name, "serde");
+        assert!(comparison.sections.is_empty());
+    }
+}
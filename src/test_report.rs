@@ -0,0 +1,254 @@
+// test_report.rs
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+use chrono::Local;
+use crate::project_settings;
+
+const HISTORY_FILE: &str = "test_history.json";
+// Outcomes kept per test, and the window inspected for flakiness
+const HISTORY_LIMIT: usize = 20;
+const FLAKY_WINDOW: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TestStatus {
+    Failed,
+    Ok,
+    Ignored,
+}
+
+impl TestStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TestStatus::Ok => "ok",
+            TestStatus::Failed => "FAILED",
+            TestStatus::Ignored => "ignored",
+        }
+    }
+}
+
+/// One test from libtest's `test <name> ... <status>` lines
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TestCase {
+    pub name: String,
+    pub module: String,
+    pub status: TestStatus,
+    /// Only reported by libtest when run with `-Z unstable-options --report-time`
+    #[serde(rename = "duration_ms", serialize_with = "serialize_optional_duration")]
+    pub duration: Option<Duration>,
+    pub failure_output: Option<String>,
+}
+
+fn serialize_optional_duration<S: serde::Serializer>(duration: &Option<Duration>, serializer: S) -> Result<S::Ok, S::Error> {
+    match duration {
+        Some(duration) => crate::output::duration_ms(duration, serializer),
+        None => serializer.serialize_none(),
+    }
+}
+
+/// Parses libtest's human-readable output (all test binaries concatenated)
+pub fn parse_test_output(stdout: &str) -> Vec<TestCase> {
+    let mut cases: Vec<TestCase> = Vec::new();
+    let mut failure_outputs: BTreeMap<String, String> = BTreeMap::new();
+    let mut current_failure: Option<(String, Vec<&str>)> = None;
+
+    for line in stdout.lines() {
+        // Captured output sections: "---- name stdout ----" up to the next section or "failures:"
+        if let Some(name) = line.strip_prefix("---- ").and_then(|l| l.strip_suffix(" stdout ----")) {
+            if let Some((previous, lines)) = current_failure.take() {
+                failure_outputs.insert(previous, lines.join("\n").trim().to_string());
+            }
+            current_failure = Some((name.to_string(), Vec::new()));
+            continue;
+        }
+        if let Some((name, lines)) = current_failure.as_mut() {
+            if line == "failures:" || line.starts_with("test result:") {
+                failure_outputs.insert(name.clone(), lines.join("\n").trim().to_string());
+                current_failure = None;
+            } else {
+                lines.push(line);
+                continue;
+            }
+        }
+
+        if let Some(case) = parse_test_line(line) {
+            cases.push(case);
+        }
+    }
+    if let Some((name, lines)) = current_failure {
+        failure_outputs.insert(name, lines.join("\n").trim().to_string());
+    }
+
+    for case in cases.iter_mut().filter(|c| c.status == TestStatus::Failed) {
+        case.failure_output = failure_outputs.get(&case.name).cloned();
+    }
+    cases
+}
+
+fn parse_test_line(line: &str) -> Option<TestCase> {
+    let rest = line.strip_prefix("test ")?;
+    let (name, outcome) = rest.split_once(" ... ")?;
+
+    let (status_text, time) = match outcome.split_once(" <") {
+        Some((status, time)) => (status, time.strip_suffix('>')),
+        None => (outcome, None),
+    };
+    let status = match status_text.split(',').next()?.trim() {
+        "ok" => TestStatus::Ok,
+        "FAILED" => TestStatus::Failed,
+        "ignored" => TestStatus::Ignored,
+        _ => return None,
+    };
+    let duration = time
+        .and_then(|t| t.strip_suffix('s'))
+        .and_then(|t| t.parse::<f64>().ok())
+        .map(Duration::from_secs_f64);
+
+    // Doc tests are named like "src/lib.rs - add (line 3)"; everything else is a module path
+    let name = name.trim().to_string();
+    let module = match name.rsplit_once("::") {
+        Some((module, _)) if !name.contains(" - ") => module.to_string(),
+        _ => String::new(),
+    };
+
+    Some(TestCase { name, module, status, duration, failure_output: None })
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TestOutcome {
+    pub timestamp: String,
+    pub passed: bool,
+}
+
+/// Pass/fail outcomes per test across runs, stored in the project's state directory
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TestHistory {
+    #[serde(default)]
+    pub tests: BTreeMap<String, Vec<TestOutcome>>,
+    /// Tests whose latest recorded outcome is a failure, for "rerun failed"
+    #[serde(default)]
+    pub last_failed: Vec<String>,
+}
+
+impl TestHistory {
+    pub fn load(project_path: &Path) -> Self {
+        fs::read_to_string(project_path.join(project_settings::STATE_DIR).join(HISTORY_FILE))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, project_path: &Path) -> Result<(), String> {
+        let path = project_settings::state_dir(project_path)?.join(HISTORY_FILE);
+        let content = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        fs::write(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+
+    /// Adds the outcomes of one run; ignored tests are not recorded.
+    /// A filtered run only updates the tests it actually ran.
+    pub fn record(&mut self, cases: &[TestCase]) {
+        let timestamp = Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+
+        for case in cases.iter().filter(|c| c.status != TestStatus::Ignored) {
+            let outcomes = self.tests.entry(case.name.clone()).or_default();
+            outcomes.push(TestOutcome { timestamp: timestamp.clone(), passed: case.status == TestStatus::Ok });
+            if outcomes.len() > HISTORY_LIMIT {
+                outcomes.drain(..outcomes.len() - HISTORY_LIMIT);
+            }
+        }
+
+        for case in cases {
+            let failed = case.status == TestStatus::Failed;
+            let listed = self.last_failed.contains(&case.name);
+            if failed && !listed {
+                self.last_failed.push(case.name.clone());
+            } else if !failed && listed {
+                self.last_failed.retain(|name| name != &case.name);
+            }
+        }
+    }
+
+    /// A test is flaky when it flipped between pass and fail at least twice in recent runs
+    pub fn is_flaky(&self, name: &str) -> bool {
+        let outcomes = match self.tests.get(name) {
+            Some(outcomes) => outcomes,
+            None => return false,
+        };
+        let recent = &outcomes[outcomes.len().saturating_sub(FLAKY_WINDOW)..];
+        recent.windows(2).filter(|pair| pair[0].passed != pair[1].passed).count() >= 2
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OUTPUT: &str = "
+running 4 tests
+test tests::fails ... FAILED
+test tests::inner::deep ... FAILED
+test tests::passes ... ok <0.015s>
+test tests::slow ... ignored, slow
+
+failures:
+
+---- tests::fails stdout ----
+
+thread 'tests::fails' panicked at src/main.rs:5:26:
+boom
+
+---- tests::inner::deep stdout ----
+out
+deep fail
+
+
+failures:
+    tests::fails
+    tests::inner::deep
+
+test result: FAILED. 1 passed; 2 failed; 1 ignored; 0 measured; 0 filtered out; finished in 0.01s
+
+running 1 test
+test src/lib.rs - add (line 3) ... ok
+";
+
+    #[test]
+    fn parses_statuses_durations_and_failure_output() {
+        let cases = parse_test_output(OUTPUT);
+        assert_eq!(cases.len(), 5);
+
+        let fails = &cases[0];
+        assert_eq!((fails.module.as_str(), fails.status), ("tests", TestStatus::Failed));
+        assert_eq!(fails.failure_output.as_deref(), Some("thread 'tests::fails' panicked at src/main.rs:5:26:\nboom"));
+
+        assert_eq!(cases[1].module, "tests::inner");
+        assert_eq!(cases[1].failure_output.as_deref(), Some("out\ndeep fail"));
+        assert_eq!(cases[2].duration, Some(Duration::from_millis(15)));
+        assert_eq!(cases[3].status, TestStatus::Ignored);
+        assert_eq!(cases[4].module, "");
+    }
+
+    #[test]
+    fn flags_tests_that_flip_between_runs() {
+        let mut history = TestHistory::default();
+        let run = |status| vec![TestCase {
+            name: "t".to_string(),
+            module: String::new(),
+            status,
+            duration: None,
+            failure_output: None,
+        }];
+
+        history.record(&run(TestStatus::Ok));
+        history.record(&run(TestStatus::Failed));
+        assert!(!history.is_flaky("t"));
+        assert_eq!(history.last_failed, vec!["t"]);
+
+        history.record(&run(TestStatus::Ok));
+        assert!(history.is_flaky("t"));
+        assert!(history.last_failed.is_empty());
+    }
+}
@@ -0,0 +1,301 @@
+// timings_report.rs
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use crate::ansi_theme::AnsiTheme;
+use crate::config::ThemeConfig;
+
+/// Where `cargo build --timings` writes its latest report, relative to the target directory
+pub const REPORT_PATH: &str = "cargo-timings/cargo-timing.html";
+// Slowest crates kept in the build history
+const SUMMARY_CRATES: usize = 5;
+
+// One entry of the report's `UNIT_DATA` array. Recent cargo versions split each unit into
+// `sections` (frontend, codegen, link); older ones only give `rmeta_time`, the end of the frontend.
+#[derive(Debug, Deserialize)]
+struct Unit {
+    i: usize,
+    name: String,
+    version: String,
+    #[serde(default)]
+    target: String,
+    start: f64,
+    duration: f64,
+    #[serde(default)]
+    rmeta_time: Option<f64>,
+    #[serde(default)]
+    unblocked_units: Vec<usize>,
+    #[serde(default)]
+    unblocked_rmeta_units: Vec<usize>,
+    #[serde(default)]
+    sections: Option<Vec<(String, Section)>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Section {
+    start: f64,
+    end: f64,
+}
+
+impl Unit {
+    fn end(&self) -> f64 {
+        self.start + self.duration
+    }
+
+    // Seconds spent in the named section(s)
+    fn section_time(&self, names: &[&str]) -> f64 {
+        self.sections.iter().flatten()
+            .filter(|(name, _)| names.contains(&name.as_str()))
+            .map(|(_, section)| (section.end - section.start).max(0.0))
+            .sum()
+    }
+
+    fn frontend(&self) -> f64 {
+        match (&self.sections, self.rmeta_time) {
+            (Some(_), _) => self.section_time(&["frontend"]),
+            (None, Some(rmeta)) => rmeta.min(self.duration),
+            (None, None) => 0.0,
+        }
+    }
+
+    fn codegen(&self) -> f64 {
+        match (&self.sections, self.rmeta_time) {
+            (Some(_), _) => self.section_time(&["codegen", "link"]),
+            (None, Some(rmeta)) => (self.duration - rmeta).max(0.0),
+            (None, None) => 0.0,
+        }
+    }
+
+    // "serde 1.0.210 (build script)"; the library target has no suffix
+    fn label(&self) -> String {
+        let target = self.target.trim();
+        if target.is_empty() {
+            format!("{} {}", self.name, self.version)
+        } else {
+            format!("{} {} ({})", self.name, self.version, target.replace('"', ""))
+        }
+    }
+}
+
+/// Compile time of one crate, summed over its units (library, build script, binaries)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrateTiming {
+    pub name: String,
+    pub version: String,
+    pub seconds: f64,
+    pub frontend_seconds: f64,
+    pub codegen_seconds: f64,
+    pub units: usize,
+}
+
+/// One unit on the critical path, in build order
+#[derive(Debug, Clone, Serialize)]
+pub struct PathStep {
+    pub unit: String,
+    pub start_seconds: f64,
+    pub seconds: f64,
+}
+
+/// Per-crate compile times parsed from a `cargo build --timings` HTML report
+#[derive(Debug, Clone, Serialize)]
+pub struct TimingsReport {
+    pub report_path: PathBuf,
+    /// Wall-clock time from the first unit starting to the last one finishing
+    pub total_seconds: f64,
+    pub units: usize,
+    /// Slowest first
+    pub crates: Vec<CrateTiming>,
+    /// The chain of units that each had to wait for the one before; it bounds the build time
+    pub critical_path: Vec<PathStep>,
+    /// Type checking, borrow checking and metadata, summed over all units
+    pub frontend_seconds: f64,
+    /// LLVM code generation and linking, summed over all units
+    pub codegen_seconds: f64,
+    /// Units cargo does not split into phases: build scripts, proc macros and binaries
+    pub other_seconds: f64,
+}
+
+impl TimingsReport {
+    pub fn from_html(html: &str, report_path: &Path) -> Result<Self, String> {
+        let units = parse_unit_data(html)?;
+
+        let mut crates: BTreeMap<(String, String), CrateTiming> = BTreeMap::new();
+        let (mut frontend_seconds, mut codegen_seconds, mut other_seconds) = (0.0, 0.0, 0.0);
+        for unit in &units {
+            let (frontend, codegen) = (unit.frontend(), unit.codegen());
+            frontend_seconds += frontend;
+            codegen_seconds += codegen;
+            other_seconds += (unit.duration - frontend - codegen).max(0.0);
+
+            let entry = crates.entry((unit.name.clone(), unit.version.clone())).or_insert_with(|| CrateTiming {
+                name: unit.name.clone(),
+                version: unit.version.clone(),
+                seconds: 0.0,
+                frontend_seconds: 0.0,
+                codegen_seconds: 0.0,
+                units: 0,
+            });
+            entry.seconds += unit.duration;
+            entry.frontend_seconds += frontend;
+            entry.codegen_seconds += codegen;
+            entry.units += 1;
+        }
+        let mut crates: Vec<CrateTiming> = crates.into_values().collect();
+        crates.sort_by(|a, b| b.seconds.total_cmp(&a.seconds).then_with(|| a.name.cmp(&b.name)));
+
+        let start = units.iter().map(|u| u.start).fold(f64::INFINITY, f64::min);
+        let end = units.iter().map(Unit::end).fold(0.0, f64::max);
+
+        Ok(TimingsReport {
+            report_path: report_path.to_path_buf(),
+            total_seconds: if units.is_empty() { 0.0 } else { end - start },
+            units: units.len(),
+            crates,
+            critical_path: critical_path(&units),
+            frontend_seconds,
+            codegen_seconds,
+            other_seconds,
+        })
+    }
+
+    /// False when every unit was fresh and cargo compiled nothing
+    pub fn compiled(&self) -> bool {
+        self.crates.iter().any(|c| c.seconds > 0.0)
+    }
+
+    pub fn critical_path_seconds(&self) -> f64 {
+        self.critical_path.iter().map(|step| step.seconds).sum()
+    }
+
+    pub fn summary(&self) -> TimingsSummary {
+        TimingsSummary {
+            units: self.units,
+            critical_path_ms: (self.critical_path_seconds() * 1000.0) as u64,
+            frontend_ms: (self.frontend_seconds * 1000.0) as u64,
+            codegen_ms: (self.codegen_seconds * 1000.0) as u64,
+            slowest: self.crates.iter().take(SUMMARY_CRATES)
+                .map(|c| (c.name.clone(), (c.seconds * 1000.0) as u64))
+                .collect(),
+        }
+    }
+
+    pub fn print(&self, top: usize, theme: &ThemeConfig) {
+        AnsiTheme::print_themed("\n", theme);
+        AnsiTheme::print_blue(&format!("Compile timings: {} units in {:.2}s ({})\n",
+            self.units, self.total_seconds, self.report_path.display()), theme);
+        if !self.compiled() {
+            AnsiTheme::print_info(" Nothing needed recompiling - clean the project to time a full build\n", theme);
+            return;
+        }
+
+        let compile = self.frontend_seconds + self.codegen_seconds + self.other_seconds;
+        AnsiTheme::print_themed(&format!(" Frontend {:.2}s ({:.0}%)  Codegen {:.2}s ({:.0}%)  Not split {:.2}s ({:.0}%)\n",
+            self.frontend_seconds, percent(self.frontend_seconds, compile),
+            self.codegen_seconds, percent(self.codegen_seconds, compile),
+            self.other_seconds, percent(self.other_seconds, compile)), theme);
+
+        AnsiTheme::print_themed("\n", theme);
+        AnsiTheme::print_cyan(&format!(" {:<36} {:>9} {:>10} {:>9} {:>6}\n", "SLOWEST CRATES", "TOTAL", "FRONTEND", "CODEGEN", "UNITS"), theme);
+        for timing in self.crates.iter().take(top) {
+            AnsiTheme::print_themed(&format!(" {:<36} {:>8.2}s {:>9.2}s {:>8.2}s {:>6}\n",
+                format!("{} {}", timing.name, timing.version), timing.seconds,
+                timing.frontend_seconds, timing.codegen_seconds, timing.units), theme);
+        }
+
+        AnsiTheme::print_themed("\n", theme);
+        AnsiTheme::print_cyan(&format!(" Critical path ({} units, {:.2}s):\n", self.critical_path.len(), self.critical_path_seconds()), theme);
+        for step in &self.critical_path {
+            AnsiTheme::print_themed(&format!("  {:>7.2}s  +{:<7.2} {}\n", step.start_seconds, step.seconds, step.unit), theme);
+        }
+    }
+}
+
+/// What the build history keeps of a timings run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TimingsSummary {
+    pub units: usize,
+    pub critical_path_ms: u64,
+    pub frontend_ms: u64,
+    pub codegen_ms: u64,
+    /// Slowest crates and their compile time in milliseconds
+    pub slowest: Vec<(String, u64)>,
+}
+
+// The report embeds its data as `const UNIT_DATA = [...];` inside a script block
+fn parse_unit_data(html: &str) -> Result<Vec<Unit>, String> {
+    let start = html.find("const UNIT_DATA = ")
+        .map(|i| i + "const UNIT_DATA = ".len())
+        .ok_or("No unit data found in the timings report")?;
+    let rest = &html[start..];
+    let end = rest.find("];").map(|i| i + 1).ok_or("Unterminated unit data in the timings report")?;
+    serde_json::from_str(&rest[..end]).map_err(|e| format!("Could not parse the timings report: {}", e))
+}
+
+// Walks back from the unit that finished last. Cargo lists, for every unit, the units its
+// completion unblocked, so the unit that unblocked another is the dependency it waited for.
+fn critical_path(units: &[Unit]) -> Vec<PathStep> {
+    let by_index: BTreeMap<usize, &Unit> = units.iter().map(|u| (u.i, u)).collect();
+    let mut current = units.iter().max_by(|a, b| a.end().total_cmp(&b.end()));
+    let mut path = Vec::new();
+
+    while let Some(unit) = current {
+        path.push(PathStep { unit: unit.label(), start_seconds: unit.start, seconds: unit.duration });
+        current = by_index.values()
+            .filter(|u| u.unblocked_units.contains(&unit.i) || u.unblocked_rmeta_units.contains(&unit.i))
+            .max_by(|a, b| a.end().total_cmp(&b.end()))
+            .copied();
+        if path.len() > units.len() {
+            break;
+        }
+    }
+    path.reverse();
+    path
+}
+
+fn percent(part: f64, total: f64) -> f64 {
+    if total > 0.0 { part / total * 100.0 } else { 0.0 }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REPORT: &str = r#"<script>
+const UNIT_DATA = [
+  {"i": 0, "name": "proc-macro2", "version": "1.0.86", "mode": "todo", "target": "", "features": [],
+   "start": 0.1, "duration": 1.0, "unblocked_units": [1, 2], "unblocked_rmeta_units": [],
+   "sections": [["frontend", {"start": 0.0, "end": 0.6}], ["codegen", {"start": 0.6, "end": 1.0}]]},
+  {"i": 1, "name": "serde", "version": "1.0.210", "target": " build-script-build \"build-script\"",
+   "start": 1.1, "duration": 0.5, "unblocked_units": [], "unblocked_rmeta_units": [], "sections": null},
+  {"i": 2, "name": "serde_derive", "version": "1.0.210", "target": "", "start": 1.1, "duration": 3.0,
+   "rmeta_time": 1.0, "unblocked_units": [3], "unblocked_rmeta_units": []},
+  {"i": 3, "name": "app", "version": "0.1.0", "target": " app \"bin\"", "start": 4.1, "duration": 2.0,
+   "unblocked_units": [], "unblocked_rmeta_units": [],
+   "sections": [["frontend", {"start": 0.0, "end": 0.5}], ["codegen", {"start": 0.5, "end": 1.5}], ["link", {"start": 1.5, "end": 2.0}]]}
+];
+const CONCURRENCY_DATA = [];
+</script>"#;
+
+    #[test]
+    fn parses_crates_critical_path_and_phases() {
+        let report = TimingsReport::from_html(REPORT, Path::new("cargo-timing.html")).unwrap();
+
+        assert_eq!(report.units, 4);
+        assert!((report.total_seconds - 6.0).abs() < 1e-9);
+        let names: Vec<&str> = report.crates.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["serde_derive", "app", "proc-macro2", "serde"]);
+
+        let path: Vec<&str> = report.critical_path.iter().map(|s| s.unit.as_str()).collect();
+        assert_eq!(path, vec!["proc-macro2 1.0.86", "serde_derive 1.0.210", "app 0.1.0 (app bin)"]);
+        assert!((report.critical_path_seconds() - 6.0).abs() < 1e-9);
+
+        // 0.6 + 1.0 (rmeta) + 0.5 frontend; 0.4 + 2.0 + 1.5 codegen and link; the build script is other
+        assert!((report.frontend_seconds - 2.1).abs() < 1e-9);
+        assert!((report.codegen_seconds - 3.9).abs() < 1e-9);
+        assert!((report.other_seconds - 0.5).abs() < 1e-9);
+
+        let summary = report.summary();
+        assert_eq!(summary.slowest[0], ("serde_derive".to_string(), 3000));
+        assert_eq!(summary.critical_path_ms, 6000);
+    }
+}
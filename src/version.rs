@@ -0,0 +1,240 @@
+// version.rs
+use std::cmp::Ordering;
+use std::fmt;
+
+/// A semantic version, `MAJOR.MINOR.PATCH[-PRE-RELEASE][+BUILD]` as described on semver.org
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Version {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+    /// Dot-separated pre-release identifiers, e.g. `["beta", "2"]`
+    pub pre: Vec<String>,
+    /// Dot-separated build metadata, ignored for precedence
+    pub build: Vec<String>,
+}
+
+/// Pre-release channels, from least to most stable
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Channel {
+    Alpha,
+    Beta,
+    Rc,
+}
+
+impl Channel {
+    pub fn parse(input: &str) -> Option<Channel> {
+        match input.to_lowercase().as_str() {
+            "alpha" | "a" => Some(Channel::Alpha),
+            "beta" | "b" => Some(Channel::Beta),
+            "rc" | "r" => Some(Channel::Rc),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Channel::Alpha => "alpha",
+            Channel::Beta => "beta",
+            Channel::Rc => "rc",
+        }
+    }
+}
+
+/// How `Version::bump` moves a version on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bump {
+    Major,
+    Minor,
+    Patch,
+    /// The next pre-release on a channel: `1.2.0-beta.1` → `1.2.0-beta.2`, `1.2.0-alpha.3` →
+    /// `1.2.0-beta.1`, or from a release the next patch, `1.2.0` → `1.2.1-beta.1`
+    Pre(Channel),
+    /// Promotes a pre-release to its release, `1.2.0-rc.2` → `1.2.0`
+    Release,
+}
+
+impl Version {
+    pub fn parse(input: &str) -> Result<Version, String> {
+        let input = input.trim();
+        let (rest, build) = match input.split_once('+') {
+            Some((rest, build)) => (rest, parse_identifiers(build, "build metadata", false)?),
+            None => (input, Vec::new()),
+        };
+        let (core, pre) = match rest.split_once('-') {
+            Some((core, pre)) => (core, parse_identifiers(pre, "pre-release", true)?),
+            None => (rest, Vec::new()),
+        };
+
+        let numbers: Vec<&str> = core.split('.').collect();
+        if numbers.len() != 3 {
+            return Err(format!("Invalid version '{}': expected MAJOR.MINOR.PATCH", input));
+        }
+        let number = |part: &str, name: &str| -> Result<u64, String> {
+            if part.len() > 1 && part.starts_with('0') {
+                return Err(format!("Invalid {} version '{}': leading zeros are not allowed", name, part));
+            }
+            part.parse::<u64>().map_err(|_| format!("Invalid {} version '{}' in '{}'", name, part, input))
+        };
+
+        Ok(Version {
+            major: number(numbers[0], "major")?,
+            minor: number(numbers[1], "minor")?,
+            patch: number(numbers[2], "patch")?,
+            pre,
+            build,
+        })
+    }
+
+    pub fn is_prerelease(&self) -> bool {
+        !self.pre.is_empty()
+    }
+
+    /// The channel of a pre-release such as `beta.2`, if it names one
+    pub fn channel(&self) -> Option<Channel> {
+        let first = self.pre.first()?;
+        [Channel::Alpha, Channel::Beta, Channel::Rc].into_iter().find(|channel| channel.as_str() == first)
+    }
+
+    /// The version `bump` leads to. Build metadata is dropped, as it describes a particular build.
+    /// Like cargo-release and npm, bumping a pre-release to the version it precedes just releases it:
+    /// `1.2.0-rc.1` → minor → `1.2.0`.
+    pub fn bump(&self, bump: Bump) -> Result<Version, String> {
+        let core = |major, minor, patch| Version { major, minor, patch, pre: Vec::new(), build: Vec::new() };
+        let pre = self.is_prerelease();
+        let next = match bump {
+            Bump::Major if pre && self.minor == 0 && self.patch == 0 => core(self.major, 0, 0),
+            Bump::Major => core(self.major + 1, 0, 0),
+            Bump::Minor if pre && self.patch == 0 => core(self.major, self.minor, 0),
+            Bump::Minor => core(self.major, self.minor + 1, 0),
+            Bump::Patch if pre => core(self.major, self.minor, self.patch),
+            Bump::Patch => core(self.major, self.minor, self.patch + 1),
+            Bump::Release if pre => core(self.major, self.minor, self.patch),
+            Bump::Release => return Err(format!("{} is already a release", self)),
+            Bump::Pre(channel) => {
+                let mut next = if pre { core(self.major, self.minor, self.patch) } else { core(self.major, self.minor, self.patch + 1) };
+                let number = match (self.channel(), self.pre.get(1).and_then(|n| n.parse::<u64>().ok())) {
+                    (Some(current), Some(n)) if current == channel && self.pre.len() == 2 => n + 1,
+                    _ => 1,
+                };
+                next.pre = vec![channel.as_str().to_string(), number.to_string()];
+                if next.precedence(self) != Ordering::Greater {
+                    return Err(format!("{} would not come after {}", next, self));
+                }
+                next
+            }
+        };
+        Ok(next)
+    }
+
+    /// Replaces the build metadata; an empty string removes it
+    pub fn with_build(&self, build: &str) -> Result<Version, String> {
+        let build = if build.trim().is_empty() { Vec::new() } else { parse_identifiers(build.trim(), "build metadata", false)? };
+        Ok(Version { build, ..self.clone() })
+    }
+
+    /// Semver precedence: build metadata is ignored and a pre-release comes before its release
+    pub fn precedence(&self, other: &Version) -> Ordering {
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(|| match (self.pre.is_empty(), other.pre.is_empty()) {
+                (true, true) => Ordering::Equal,
+                (true, false) => Ordering::Greater,
+                (false, true) => Ordering::Less,
+                (false, false) => compare_pre(&self.pre, &other.pre),
+            })
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        if !self.pre.is_empty() {
+            write!(f, "-{}", self.pre.join("."))?;
+        }
+        if !self.build.is_empty() {
+            write!(f, "+{}", self.build.join("."))?;
+        }
+        Ok(())
+    }
+}
+
+fn parse_identifiers(input: &str, what: &str, numeric_without_zeros: bool) -> Result<Vec<String>, String> {
+    input.split('.').map(|id| {
+        if id.is_empty() || !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+            return Err(format!("Invalid {} '{}': use dot-separated letters, digits and hyphens", what, input));
+        }
+        if numeric_without_zeros && id.len() > 1 && id.starts_with('0') && id.chars().all(|c| c.is_ascii_digit()) {
+            return Err(format!("Invalid {} '{}': numeric identifiers cannot have leading zeros", what, input));
+        }
+        Ok(id.to_string())
+    }).collect()
+}
+
+// Numeric identifiers compare numerically and before alphanumeric ones; a longer list wins a tie
+fn compare_pre(left: &[String], right: &[String]) -> Ordering {
+    for (a, b) in left.iter().zip(right) {
+        let ordering = match (a.parse::<u64>(), b.parse::<u64>()) {
+            (Ok(a), Ok(b)) => a.cmp(&b),
+            (Ok(_), Err(_)) => Ordering::Less,
+            (Err(_), Ok(_)) => Ordering::Greater,
+            (Err(_), Err(_)) => a.cmp(b),
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    left.len().cmp(&right.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bump(version: &str, bump: Bump) -> String {
+        Version::parse(version).unwrap().bump(bump).map(|v| v.to_string()).unwrap_or_else(|e| e)
+    }
+
+    #[test]
+    fn parses_and_orders_versions() {
+        let version = Version::parse("1.2.0-beta.11+build.5").unwrap();
+        assert_eq!((version.major, version.minor, version.patch), (1, 2, 0));
+        assert_eq!(version.pre, vec!["beta", "11"]);
+        assert_eq!(version.to_string(), "1.2.0-beta.11+build.5");
+        assert_eq!(version.channel(), Some(Channel::Beta));
+        assert!(Version::parse("1.2").is_err());
+        assert!(Version::parse("01.2.3").is_err());
+        assert!(Version::parse("1.2.3-beta..1").is_err());
+        assert!(Version::parse("1.2.3-01").is_err());
+
+        let ordered = ["1.0.0-alpha", "1.0.0-alpha.1", "1.0.0-alpha.beta", "1.0.0-beta.2", "1.0.0-beta.11", "1.0.0-rc.1", "1.0.0", "1.0.1"];
+        for pair in ordered.windows(2) {
+            let (a, b) = (Version::parse(pair[0]).unwrap(), Version::parse(pair[1]).unwrap());
+            assert_eq!(a.precedence(&b), Ordering::Less, "{} < {}", a, b);
+        }
+        assert_eq!(Version::parse("1.0.0+a").unwrap().precedence(&Version::parse("1.0.0+b").unwrap()), Ordering::Equal);
+    }
+
+    #[test]
+    fn bumps_releases_and_pre_releases() {
+        assert_eq!(bump("1.2.3+abc", Bump::Patch), "1.2.4");
+        assert_eq!(bump("1.2.3", Bump::Minor), "1.3.0");
+        assert_eq!(bump("1.2.3", Bump::Major), "2.0.0");
+        assert_eq!(bump("1.2.0-beta.1", Bump::Patch), "1.2.0");
+        assert_eq!(bump("1.2.0-beta.1", Bump::Minor), "1.2.0");
+        assert_eq!(bump("1.2.1-beta.1", Bump::Minor), "1.3.0");
+        assert_eq!(bump("2.0.0-rc.1", Bump::Major), "2.0.0");
+
+        assert_eq!(bump("1.2.3", Bump::Pre(Channel::Alpha)), "1.2.4-alpha.1");
+        assert_eq!(bump("1.2.4-alpha.1", Bump::Pre(Channel::Alpha)), "1.2.4-alpha.2");
+        assert_eq!(bump("1.2.4-alpha.2", Bump::Pre(Channel::Beta)), "1.2.4-beta.1");
+        assert_eq!(bump("1.2.4-beta", Bump::Pre(Channel::Beta)), "1.2.4-beta.1");
+        assert_eq!(bump("1.2.4-rc.1", Bump::Pre(Channel::Beta)), "1.2.4-beta.1 would not come after 1.2.4-rc.1");
+        assert_eq!(bump("1.2.4-rc.2+sha.1", Bump::Release), "1.2.4");
+        assert_eq!(bump("1.2.4", Bump::Release), "1.2.4 is already a release");
+
+        let version = Version::parse("1.2.4").unwrap().with_build("20240501.abc").unwrap();
+        assert_eq!(version.to_string(), "1.2.4+20240501.abc");
+        assert_eq!(version.with_build("").unwrap().to_string(), "1.2.4");
+    }
+}
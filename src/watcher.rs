@@ -0,0 +1,277 @@
+// watcher.rs
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+use std::process::Child;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, SystemTime};
+use ignore::WalkBuilder;
+use crate::ansi_theme::AnsiTheme;
+use crate::build_system::BuildSystem;
+use crate::config::ThemeConfig;
+use crate::process::{self, Supervisor};
+
+// Paths inside a project that trigger a re-run when they change
+const WATCHED_PATHS: [&str; 4] = ["src", "Cargo.toml", "tests", "examples"];
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+// Changes are batched until the tree has been quiet this long, so a multi-file save runs once
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WatchAction {
+    Check,
+    Build,
+    Test,
+    Run,
+}
+
+impl WatchAction {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "check" => Some(WatchAction::Check),
+            "build" => Some(WatchAction::Build),
+            "test" => Some(WatchAction::Test),
+            "run" => Some(WatchAction::Run),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            WatchAction::Check => "check",
+            WatchAction::Build => "build",
+            WatchAction::Test => "test",
+            WatchAction::Run => "run",
+        }
+    }
+}
+
+/// Polls a project's sources for changes, honoring `.gitignore` and skipping `target/`
+pub struct ProjectWatcher {
+    project_path: PathBuf,
+    snapshot: HashMap<PathBuf, (SystemTime, u64)>,
+}
+
+impl ProjectWatcher {
+    pub fn new(project_path: PathBuf) -> Self {
+        let mut watcher = ProjectWatcher { project_path, snapshot: HashMap::new() };
+        watcher.snapshot = watcher.scan();
+        watcher
+    }
+
+    fn scan(&self) -> HashMap<PathBuf, (SystemTime, u64)> {
+        let mut files = HashMap::new();
+
+        for watched in WATCHED_PATHS {
+            let path = self.project_path.join(watched);
+            if !path.exists() {
+                continue;
+            }
+
+            // Only components below the project count, so a project inside a `target` directory is still watched
+            let project_path = self.project_path.clone();
+            let walker = WalkBuilder::new(&path)
+                .require_git(false)  // .gitignore applies even before `git init`
+                .filter_entry(move |entry| {
+                    let relative = entry.path().strip_prefix(&project_path).unwrap_or(entry.path());
+                    !relative.components().any(|c| c == Component::Normal("target".as_ref()))
+                })
+                .build();
+
+            for entry in walker.flatten() {
+                if !entry.file_type().map(|t| t.is_file()).unwrap_or(false) {
+                    continue;
+                }
+                if let Ok(metadata) = entry.metadata() {
+                    let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+                    files.insert(entry.into_path(), (modified, metadata.len()));
+                }
+            }
+        }
+
+        files
+    }
+
+    /// Files added, modified or removed since the previous call
+    pub fn changed_files(&mut self) -> Vec<PathBuf> {
+        let current = self.scan();
+
+        let mut changed: Vec<PathBuf> = current.iter()
+            .filter(|(path, stamp)| self.snapshot.get(*path) != Some(stamp))
+            .map(|(path, _)| path.clone())
+            .chain(self.snapshot.keys().filter(|path| !current.contains_key(*path)).cloned())
+            .collect();
+        changed.sort();
+
+        self.snapshot = current;
+        changed
+    }
+
+    /// Blocks until something changes and the tree has settled, or until `stop` is set
+    pub fn wait_for_changes(&mut self, stop: &AtomicBool) -> Option<Vec<PathBuf>> {
+        self.wait_for_changes_while(stop, || true)
+    }
+
+    /// Like `wait_for_changes`, calling `keep_waiting` on every poll and giving up once it returns false
+    pub fn wait_for_changes_while(&mut self, stop: &AtomicBool, mut keep_waiting: impl FnMut() -> bool) -> Option<Vec<PathBuf>> {
+        let mut pending: Vec<PathBuf> = Vec::new();
+
+        loop {
+            if stop.load(Ordering::Relaxed) || !keep_waiting() {
+                return None;
+            }
+
+            let changed = self.changed_files();
+            if changed.is_empty() && !pending.is_empty() {
+                pending.sort();
+                pending.dedup();
+                return Some(pending);
+            }
+            pending.extend(changed);
+
+            std::thread::sleep(if pending.is_empty() { POLL_INTERVAL } else { DEBOUNCE });
+        }
+    }
+
+    pub fn relative<'a>(&self, path: &'a Path) -> &'a Path {
+        path.strip_prefix(&self.project_path).unwrap_or(path)
+    }
+}
+
+/// Runs `action` once, then again after every change until `stop` is set.
+/// `on_result` receives whether each run succeeded.
+pub fn watch_project(
+    build_system: &BuildSystem,
+    project_name: &str,
+    action: WatchAction,
+    stop: &AtomicBool,
+    theme: &ThemeConfig,
+    mut on_result: impl FnMut(WatchAction, Result<bool, String>),
+) {
+    let mut watcher = ProjectWatcher::new(build_system.get_project_path(project_name));
+    if action == WatchAction::Run {
+        watch_program(build_system, project_name, &mut watcher, stop, theme, on_result);
+        return;
+    }
+
+    on_result(action, run_action(build_system, project_name, action, theme));
+
+    while let Some(changed) = watcher.wait_for_changes(stop) {
+        print_changes(&watcher, &changed, theme);
+
+        on_result(action, run_action(build_system, project_name, action, theme));
+
+        // Ignore anything the action itself touched (e.g. a build script writing into src/)
+        watcher.changed_files();
+    }
+}
+
+// `WatchAction::Run`: the program keeps running between changes and is killed with its process
+// tree and started again when sources change. Ctrl-C stops the program and ends the watch.
+fn watch_program(
+    build_system: &BuildSystem,
+    project_name: &str,
+    watcher: &mut ProjectWatcher,
+    stop: &AtomicBool,
+    theme: &ThemeConfig,
+    mut on_result: impl FnMut(WatchAction, Result<bool, String>),
+) {
+    let supervisor = Supervisor::start(None);
+    let mut program = start_program(build_system, project_name, &mut on_result, theme);
+
+    loop {
+        let changed = watcher.wait_for_changes_while(stop, || {
+            if supervisor.cancel_requested() {
+                return false;
+            }
+            // Report a program that exited on its own, then keep waiting for changes
+            if let Some(Ok(Some(status))) = program.as_mut().map(|child| child.try_wait()) {
+                program = None;
+                on_result(WatchAction::Run, Ok(status.success()));
+            }
+            true
+        });
+        let Some(changed) = changed else { break };
+        print_changes(watcher, &changed, theme);
+
+        if let Some(child) = program.take() {
+            AnsiTheme::print_info(" Restarting...\n", theme);
+            stop_program(child);
+        }
+        program = start_program(build_system, project_name, &mut on_result, theme);
+
+        watcher.changed_files();
+    }
+
+    if let Some(child) = program.take() {
+        stop_program(child);
+    }
+}
+
+fn start_program(
+    build_system: &BuildSystem,
+    project_name: &str,
+    on_result: &mut impl FnMut(WatchAction, Result<bool, String>),
+    theme: &ThemeConfig,
+) -> Option<Child> {
+    match build_system.spawn_run(project_name, theme) {
+        Ok(child) => Some(child),
+        Err(e) => {
+            on_result(WatchAction::Run, Err(e));
+            None
+        }
+    }
+}
+
+fn stop_program(mut child: Child) {
+    process::kill_tree(&mut child);
+    let _ = child.wait();
+}
+
+fn print_changes(watcher: &ProjectWatcher, changed: &[PathBuf], theme: &ThemeConfig) {
+    AnsiTheme::print_themed("\n", theme);
+    for path in changed.iter().take(5) {
+        AnsiTheme::print_cyan(&format!(" Changed: {}\n", watcher.relative(path).display()), theme);
+    }
+    if changed.len() > 5 {
+        AnsiTheme::print_cyan(&format!(" ...and {} more\n", changed.len() - 5), theme);
+    }
+}
+
+fn run_action(build_system: &BuildSystem, project_name: &str, action: WatchAction, theme: &ThemeConfig) -> Result<bool, String> {
+    match action {
+        WatchAction::Check => build_system.check_project(project_name, theme).map(|r| r.success),
+        WatchAction::Build => build_system.build_project(project_name, theme).map(|r| r.success),
+        WatchAction::Test => build_system.test_project(project_name, theme).map(|r| r.success),
+        WatchAction::Run => build_system.run_project(project_name, theme).map(|r| r.success),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+    use std::fs;
+
+    #[test]
+    fn reports_changes_but_skips_ignored_and_target_files() {
+        // A `target` directory above the project must not hide it
+        let base = TempDir::new("watch");
+        let root = base.join("target").join("project");
+        fs::create_dir_all(root.join("src").join("target")).unwrap();
+        fs::write(root.join("Cargo.toml"), "[package]\n").unwrap();
+        fs::write(root.join("src").join("main.rs"), "fn main() {}\n").unwrap();
+        fs::write(root.join(".gitignore"), "*.generated.rs\n").unwrap();
+
+        let mut watcher = ProjectWatcher::new(root.clone());
+        assert!(watcher.changed_files().is_empty());
+
+        fs::write(root.join("src").join("main.rs"), "fn main() { println!(); }\n").unwrap();
+        fs::write(root.join("src").join("out.generated.rs"), "// ignored\n").unwrap();
+        fs::write(root.join("src").join("target").join("junk.rs"), "// ignored\n").unwrap();
+        assert_eq!(watcher.changed_files(), vec![root.join("src").join("main.rs")]);
+
+        fs::remove_file(root.join("src").join("main.rs")).unwrap();
+        assert_eq!(watcher.changed_files(), vec![root.join("src").join("main.rs")]);
+    }
+}